@@ -1,6 +1,6 @@
 use apicentric::simulator::config::{
-    EndpointDefinition, EndpointKind, PortRange, ResponseDefinition, ServerConfig,
-    ServiceDefinition, SimulatorConfig,
+    EndpointDefinition, PortRange, ResponseDefinition, ServerConfig, ServiceDefinition,
+    SimulatorConfig,
 };
 use apicentric::simulator::ApiSimulatorManager;
 use std::collections::HashMap;
@@ -50,22 +50,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .to_string(),
             script: None,
             headers: None,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
             schema: None,
         },
     );
 
     endpoints.push(EndpointDefinition {
-        kind: EndpointKind::Http,
         method: "GET".to_string(),
         path: "/status".to_string(),
-        header_match: None,
         description: Some("Check system status".to_string()),
-        parameters: None,
-        request_body: None,
         responses: responses_get,
-        scenarios: None,
-        stream: None,
+        ..Default::default()
     });
 
     // Define a POST endpoint that echoes data
@@ -78,22 +82,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             body: "{\"received\": {{json request.body}}, \"timestamp\": \"{{now}}\"}".to_string(), // Uses Handlebars
             script: None,
             headers: None,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
             schema: None,
         },
     );
 
     endpoints.push(EndpointDefinition {
-        kind: EndpointKind::Http,
         method: "POST".to_string(),
         path: "/data".to_string(),
-        header_match: None,
         description: Some("Submit data".to_string()),
-        parameters: None,
-        request_body: None,
         responses: responses_post,
-        scenarios: None,
-        stream: None,
+        ..Default::default()
     });
 
     let service_def = ServiceDefinition {
@@ -103,16 +111,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         server: Some(ServerConfig {
             port: Some(9500),
             base_path: "/api".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     };
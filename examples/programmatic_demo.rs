@@ -3,7 +3,7 @@ use apicentric::iot::{
     model::DigitalTwin, physics::sine::SineWaveStrategy, traits::SimulationStrategy,
 };
 #[cfg(all(feature = "iot", feature = "simulator"))]
-use apicentric::simulator::config::{EndpointKind, PortRange, ServerConfig};
+use apicentric::simulator::config::{PortRange, ServerConfig};
 #[cfg(all(feature = "iot", feature = "simulator"))]
 use apicentric::simulator::{
     ApiSimulatorManager, EndpointDefinition, ResponseDefinition, ServiceDefinition, SimulatorConfig,
@@ -72,22 +72,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .to_string(),
                 script: None,
                 headers: None,
+                trailers: None,
+                examples: None,
+                record_delay_ms: None,
+                pretty_json: None,
+                sparse_fields: false,
+                serve_times: None,
+                exec: None,
                 side_effects: None,
+                body_base64: None,
+                body_file: None,
                 schema: None, // Added missing field
             },
         );
 
         let endpoint = EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "POST".to_string(),
             path: "/telemetry".to_string(),
-            header_match: None,
             description: Some("Receive device telemetry".to_string()),
-            parameters: None,
-            request_body: None,
             responses: responses_telemetry,
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         };
 
         let service_def = ServiceDefinition {
@@ -97,16 +101,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             server: Some(ServerConfig {
                 port: Some(9500),
                 base_path: "/api/v1".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![endpoint]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
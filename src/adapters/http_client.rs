@@ -2,9 +2,10 @@
 //! crate to send HTTP requests.
 //!
 //! This module provides a `ReqwestHttpClientAdapter` that can be used to send
-//! HTTP requests to a real API for contract testing. It supports retries,
-//! timeouts, and custom headers.
+//! HTTP requests to a real API for contract testing. It supports retries
+//! with exponential backoff, timeouts, and custom headers.
 
+use crate::config::RetryPolicy;
 use crate::domain::contract_testing::*;
 use crate::domain::ports::contract::*;
 use async_trait::async_trait;
@@ -18,8 +19,7 @@ use tracing::{debug, info, warn};
 pub struct ReqwestHttpClientAdapter {
     client: Client,
     default_timeout: Duration,
-    max_retries: u32,
-    retry_delay: Duration,
+    retry: RetryPolicy,
 }
 
 impl ReqwestHttpClientAdapter {
@@ -34,8 +34,7 @@ impl ReqwestHttpClientAdapter {
         Self {
             client,
             default_timeout: Duration::from_secs(30),
-            max_retries: 3,
-            retry_delay: Duration::from_millis(1000),
+            retry: RetryPolicy::default(),
         }
     }
 
@@ -61,25 +60,43 @@ impl ReqwestHttpClientAdapter {
     /// * `max_retries` - The maximum number of retries.
     /// * `delay` - The delay between retries.
     pub fn with_retries(mut self, max_retries: u32, delay: Duration) -> Self {
-        self.max_retries = max_retries;
-        self.retry_delay = delay;
+        self.retry.max_retries = max_retries;
+        self.retry.initial_delay_ms = delay.as_millis() as u64;
         self
     }
 
+    /// Sets the full retry policy for HTTP requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The retry policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Sends the request, retrying transport errors and retryable statuses with
+    /// exponential backoff. Non-idempotent requests are sent only once.
     async fn execute_with_retries(
         &self,
         request_builder: RequestBuilder,
+        idempotent: bool,
     ) -> Result<reqwest::Response, HttpClientError> {
+        let max_retries = if idempotent {
+            self.retry.max_retries
+        } else {
+            0
+        };
         let mut last_error = None;
 
-        for attempt in 0..=self.max_retries {
+        for attempt in 0..=max_retries {
             if attempt > 0 {
                 debug!(
                     "Retrying request (attempt {}/{})",
                     attempt + 1,
-                    self.max_retries + 1
+                    max_retries + 1
                 );
-                tokio::time::sleep(self.retry_delay).await;
+                tokio::time::sleep(self.retry.backoff(attempt)).await;
             }
 
             // Clone the request for retry attempts
@@ -93,27 +110,34 @@ impl ReqwestHttpClientAdapter {
             };
 
             match request.send().await {
+                Ok(response)
+                    if attempt < max_retries
+                        && self.retry.retries_status(response.status().as_u16()) =>
+                {
+                    warn!(
+                        "Request returned {} on attempt {}",
+                        response.status(),
+                        attempt + 1
+                    );
+                    last_error = Some(format!("status {}", response.status()));
+                }
                 Ok(response) => {
                     debug!("Request successful on attempt {}", attempt + 1);
                     return Ok(response);
                 }
                 Err(e) => {
-                    last_error = Some(e);
-                    if attempt < self.max_retries {
-                        warn!(
-                            "Request failed on attempt {}: {}",
-                            attempt + 1,
-                            last_error.as_ref().unwrap()
-                        );
+                    if attempt < max_retries {
+                        warn!("Request failed on attempt {}: {}", attempt + 1, e);
                     }
+                    last_error = Some(e.to_string());
                 }
             }
         }
 
         Err(HttpClientError::RequestFailed(format!(
             "Request failed after {} attempts: {}",
-            self.max_retries + 1,
-            last_error.unwrap()
+            max_retries + 1,
+            last_error.unwrap_or_default()
         )))
     }
 
@@ -272,7 +296,8 @@ impl ContractHttpClient for ReqwestHttpClientAdapter {
         let request = self.build_request(base_url, config, scenario)?;
 
         // Execute with retries
-        let response = self.execute_with_retries(request).await?;
+        let idempotent = !matches!(scenario.method, HttpMethod::POST | HttpMethod::PATCH);
+        let response = self.execute_with_retries(request, idempotent).await?;
 
         // Parse response
         let api_response = self.parse_response(response, start_time).await?;
@@ -341,8 +366,7 @@ impl Default for ReqwestHttpClientAdapter {
 /// A builder for creating `ReqwestHttpClientAdapter` instances.
 pub struct HttpClientBuilder {
     timeout: Duration,
-    max_retries: u32,
-    retry_delay: Duration,
+    retry: RetryPolicy,
     user_agent: String,
     default_headers: HashMap<String, String>,
 }
@@ -352,8 +376,7 @@ impl HttpClientBuilder {
     pub fn new() -> Self {
         Self {
             timeout: Duration::from_secs(30),
-            max_retries: 3,
-            retry_delay: Duration::from_millis(1000),
+            retry: RetryPolicy::default(),
             user_agent: "apicentric-contract-tester/1.0".to_string(),
             default_headers: HashMap::new(),
         }
@@ -376,8 +399,18 @@ impl HttpClientBuilder {
     /// * `max_retries` - The maximum number of retries.
     /// * `delay` - The delay between retries.
     pub fn retries(mut self, max_retries: u32, delay: Duration) -> Self {
-        self.max_retries = max_retries;
-        self.retry_delay = delay;
+        self.retry.max_retries = max_retries;
+        self.retry.initial_delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    /// Sets the full retry policy for HTTP requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The retry policy.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
         self
     }
 
@@ -430,8 +463,7 @@ impl HttpClientBuilder {
         ReqwestHttpClientAdapter {
             client,
             default_timeout: self.timeout,
-            max_retries: self.max_retries,
-            retry_delay: self.retry_delay,
+            retry: self.retry,
         }
     }
 }
@@ -457,8 +489,8 @@ mod tests {
             .build();
 
         assert_eq!(client.default_timeout, Duration::from_secs(10));
-        assert_eq!(client.max_retries, 2);
-        assert_eq!(client.retry_delay, Duration::from_millis(500));
+        assert_eq!(client.retry.max_retries, 2);
+        assert_eq!(client.retry.initial_delay_ms, 500);
     }
 
     #[test]
@@ -492,4 +524,117 @@ mod tests {
         let request = client.build_request(&base_url, &config, &scenario);
         assert!(request.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_default_headers_sent_with_each_request() {
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&received);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                seen.lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&request).to_lowercase());
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+                    )
+                    .await;
+            }
+        });
+
+        let client = HttpClientBuilder::new()
+            .retries(0, Duration::from_millis(0))
+            .default_header("Authorization".to_string(), "Bearer secret".to_string())
+            .build();
+        let base_url = ApiUrl::new(format!("http://127.0.0.1:{}", port)).unwrap();
+        let config = RealApiConfig::new(
+            "test".to_string(),
+            base_url.clone(),
+            None,
+            RetryAttempts::new(0).unwrap(),
+        );
+        for path in ["/users", "/orders"] {
+            let scenario =
+                ValidationScenario::new(path.to_string(), path.to_string(), HttpMethod::GET);
+            let response = client
+                .execute_request(&base_url, &config, &scenario)
+                .await
+                .unwrap();
+            assert_eq!(response.status_code, 200);
+        }
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert!(received
+            .iter()
+            .all(|request| request.contains("authorization: bearer secret")));
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_status_until_success() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let response: &[u8] = if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                } else {
+                    b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}"
+                };
+                let _ = stream.write_all(response).await;
+            }
+        });
+
+        let client = HttpClientBuilder::new()
+            .retry_policy(RetryPolicy {
+                max_retries: 3,
+                initial_delay_ms: 5,
+                max_delay_ms: 20,
+                ..RetryPolicy::default()
+            })
+            .build();
+        let base_url = ApiUrl::new(format!("http://127.0.0.1:{}", port)).unwrap();
+        let config = RealApiConfig::new(
+            "test".to_string(),
+            base_url.clone(),
+            None,
+            RetryAttempts::new(3).unwrap(),
+        );
+        let scenario =
+            ValidationScenario::new("flaky".to_string(), "/flaky".to_string(), HttpMethod::GET);
+        let response = client
+            .execute_request(&base_url, &config, &scenario)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
 }
@@ -78,3 +78,34 @@ pub fn validate_token(token: &str, keys: &JwtKeys) -> Result<Claims, jsonwebtoke
     let data = decode::<Claims>(token, &keys.decoding, &Validation::default())?;
     Ok(data.claims)
 }
+
+/// Validates a JWT and returns all of its claims.
+///
+/// Unlike [`validate_token`], custom claims such as `scope` or `roles` are kept.
+///
+/// # Arguments
+///
+/// * `token` - The token to validate.
+/// * `keys` - The keys to use for decoding the token.
+pub fn validate_token_claims(
+    token: &str,
+    keys: &JwtKeys,
+) -> Result<serde_json::Value, jsonwebtoken::errors::Error> {
+    let data = decode::<serde_json::Value>(token, &keys.decoding, &Validation::default())?;
+    Ok(data.claims)
+}
+
+/// Reads the scopes granted by a claim.
+///
+/// The claim may be a space-separated string (`"scope": "read admin"`) or an
+/// array of strings (`"roles": ["admin"]`). Missing claims grant nothing.
+pub fn claim_scopes(claims: &serde_json::Value, claim: &str) -> Vec<String> {
+    match claims.get(claim) {
+        Some(serde_json::Value::String(s)) => s.split_whitespace().map(str::to_string).collect(),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
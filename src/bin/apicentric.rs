@@ -50,11 +50,10 @@ async fn run(cli: Cli) -> ApicentricResult<()> {
     // Override config with CLI args
     if let Commands::Simulator {
         action:
-            Some(SimulatorAction::Start {
-                services_dir,
-                force: _,
-                template: _,
-            }),
+            Some(
+                SimulatorAction::Start { services_dir, .. }
+                | SimulatorAction::Watch { services_dir },
+            ),
     } = &cli.command
     {
         if cfg.simulator.is_none() {
@@ -64,6 +63,19 @@ async fn run(cli: Cli) -> ApicentricResult<()> {
             sim_config.services_dir = std::path::PathBuf::from(services_dir);
         }
     }
+    if let Commands::Simulator {
+        action: Some(SimulatorAction::Start { env: Some(env), .. }),
+    } = &cli.command
+    {
+        if let Some(ref mut sim_config) = cfg.simulator {
+            sim_config.env = Some(env.clone());
+        }
+    }
+
+    // Open the database at `--db-path` only, not the default path first
+    if let Some(ref mut sim_config) = cfg.simulator {
+        sim_config.db_path = std::path::PathBuf::from(&cli.db_path);
+    }
 
     let builder = ContextBuilder::new(cfg.clone());
 
@@ -72,10 +84,6 @@ async fn run(cli: Cli) -> ApicentricResult<()> {
 
     let context = builder.with_api_simulator(api_simulator).build()?;
 
-    if let Some(sim) = context.api_simulator() {
-        sim.set_db_path(&cli.db_path).await.ok();
-    }
-
     let mut exec_ctx = ExecutionContext::new();
     if let Some(mode) = cli.mode {
         exec_ctx = exec_ctx.with_mode(mode.into());
@@ -100,6 +108,7 @@ async fn run(cli: Cli) -> ApicentricResult<()> {
                 println!();
                 println!("Common commands:");
                 println!("  {}     Start the simulator", "start".cyan());
+                println!("  {}     Start and reload on changes", "watch".cyan());
                 println!("  {}      Show status", "status".cyan());
                 println!("  {}     Show request logs", "logs".cyan());
                 println!();
@@ -126,6 +135,9 @@ async fn run(cli: Cli) -> ApicentricResult<()> {
         Commands::Mcp(mcp) => {
             apicentric::commands::mcp::mcp_command(&mcp, &context, &exec_ctx).await
         }
+        Commands::Config { action } => {
+            apicentric::commands::config::config_command(&action, &context).await
+        }
         Commands::Doctor => apicentric::commands::doctor::doctor_command().await,
         Commands::Open { port } => apicentric::commands::open::open_command(port).await,
         #[cfg(feature = "iot")]
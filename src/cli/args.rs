@@ -79,6 +79,9 @@ pub enum Commands {
     },
     #[cfg(feature = "mcp")]
     Mcp(Mcp),
+    Config {
+        action: ConfigAction,
+    },
     Doctor,
     Open {
         port: Option<u16>,
@@ -95,6 +98,13 @@ pub enum SimulatorAction {
         services_dir: String,
         force: bool,
         template: Option<String>,
+        stdin: bool,
+        json: bool,
+        set: Vec<String>,
+        env: Option<String>,
+    },
+    Watch {
+        services_dir: String,
     },
     Stop {
         force: bool,
@@ -106,6 +116,9 @@ pub enum SimulatorAction {
         file: String,
         recursive: bool,
         verbose: bool,
+        render: bool,
+        fix: bool,
+        tag: Option<String>,
     },
     Logs {
         service: String,
@@ -120,6 +133,9 @@ pub enum SimulatorAction {
         json: bool,
         interval: Option<u64>,
     },
+    Stats {
+        json: bool,
+    },
     SetScenario {
         scenario: String,
     },
@@ -132,17 +148,24 @@ pub enum SimulatorAction {
         output: String,
         format: ExportFormat,
     },
+    Graph {
+        input_dir: String,
+        format: GraphFormat,
+    },
     GenerateTypes {
         file: String,
         output: String,
+        watch: bool,
     },
     GenerateQuery {
         file: String,
         output: String,
+        watch: bool,
     },
     GenerateView {
         file: String,
         output: String,
+        watch: bool,
     },
     #[cfg(feature = "tui")]
     New {
@@ -159,17 +182,33 @@ pub enum SimulatorAction {
     Record {
         output: String,
         url: Option<String>,
+        infer_schemas: bool,
+    },
+    /// Standalone recording proxy that needs no configured simulator
+    Proxy {
+        target: String,
+        port: u16,
+        record: String,
+        infer_schemas: bool,
     },
     Dockerize {
         file: Vec<String>,
         output: String,
     },
+    /// Check that a running instance still answers as its definition says
+    #[cfg(feature = "contract-testing")]
+    Verify {
+        input: String,
+        url: String,
+    },
     #[cfg(feature = "contract-testing")]
     Test {
         path: String,
         url: String,
         env: String,
         quiet: bool,
+        /// Default headers sent with every request, as `Name: value`
+        headers: Vec<String>,
     },
 }
 
@@ -177,6 +216,12 @@ pub enum SimulatorAction {
 pub enum ExportFormat {
     Openapi,
     Postman,
+    Msw,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphFormat {
+    Dot,
 }
 
 #[derive(Debug, Clone)]
@@ -184,6 +229,11 @@ pub enum AiAction {
     Generate { prompt: String },
 }
 
+#[derive(Debug, Clone)]
+pub enum ConfigAction {
+    Show,
+}
+
 #[cfg(feature = "iot")]
 #[derive(Debug, Clone)]
 pub enum TwinCommands {
@@ -8,7 +8,7 @@ pub mod parser;
 
 #[cfg(feature = "iot")]
 pub use args::TwinCommands;
-pub use args::{AiAction, Cli, Commands, SimulatorAction};
+pub use args::{AiAction, Cli, Commands, ConfigAction, SimulatorAction};
 
 /// Parses the command-line arguments into a `Cli` instance.
 pub fn parse() -> Cli {
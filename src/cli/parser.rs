@@ -10,7 +10,10 @@
 
 #[cfg(feature = "mcp")]
 use crate::cli::args::Mcp;
-use crate::cli::args::{AiAction, Cli, CliExecutionMode, Commands, ExportFormat, SimulatorAction};
+use crate::cli::args::{
+    AiAction, Cli, CliExecutionMode, Commands, ConfigAction, ExportFormat, GraphFormat,
+    SimulatorAction,
+};
 #[cfg(feature = "iot")]
 use crate::cli::args::{TwinCommands, TwinRunArgs};
 use std::env;
@@ -91,6 +94,7 @@ Commands:
   gui                    Launch graphical UI
   cloud                  Launch cloud server
   new                    Create new service from template
+  config show            Print the effective configuration as JSON
   doctor                 Diagnose environment
   open                   Open WebUI
   twin                   Manage IoT Digital Twins
@@ -192,6 +196,11 @@ pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
                 }
                 cli.command = Commands::Mcp(Mcp { test });
             }
+            "config" => {
+                cli.command = Commands::Config {
+                    action: parse_config_action(&mut iter)?,
+                };
+            }
             "doctor" => cli.command = Commands::Doctor,
             "open" => {
                 let mut port = None;
@@ -241,6 +250,10 @@ where
                 let mut services_dir = "services".to_string();
                 let mut force = false;
                 let mut template = None;
+                let mut stdin = false;
+                let mut json = false;
+                let mut set = Vec::new();
+                let mut env = None;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--services-dir" | "-s" => {
@@ -250,6 +263,13 @@ where
                                 .clone()
                         }
                         "--force" => force = true,
+                        "--stdin" => stdin = true,
+                        "--json" => json = true,
+                        "--set" => set.push(
+                            iter.next()
+                                .ok_or(ParseError::MissingArgument("--set".into()))?
+                                .clone(),
+                        ),
                         "--template" => {
                             template = Some(
                                 iter.next()
@@ -257,6 +277,13 @@ where
                                     .clone(),
                             )
                         }
+                        "--env" => {
+                            env = Some(
+                                iter.next()
+                                    .ok_or(ParseError::MissingArgument("--env".into()))?
+                                    .clone(),
+                            )
+                        }
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
@@ -264,8 +291,27 @@ where
                     services_dir,
                     force,
                     template,
+                    stdin,
+                    json,
+                    set,
+                    env,
                 }))
             }
+            "watch" | "w" => {
+                let mut services_dir = "services".to_string();
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--services-dir" | "-s" => {
+                            services_dir = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--services-dir".into()))?
+                                .clone()
+                        }
+                        _ => return Err(ParseError::UnknownArgument(arg.clone())),
+                    }
+                }
+                Ok(Some(SimulatorAction::Watch { services_dir }))
+            }
             "stop" | "x" => {
                 let mut force = false;
                 while let Some(arg) = iter.next() {
@@ -290,6 +336,9 @@ where
                 let mut file = "services".to_string();
                 let mut recursive = false;
                 let mut verbose = false;
+                let mut render = false;
+                let mut fix = false;
+                let mut tag = None;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--file" | "--path" => {
@@ -300,6 +349,15 @@ where
                         }
                         "--recursive" | "-r" => recursive = true,
                         "--verbose" => verbose = true,
+                        "--render" => render = true,
+                        "--fix" => fix = true,
+                        "--tag" => {
+                            tag = Some(
+                                iter.next()
+                                    .ok_or(ParseError::MissingArgument("--tag".into()))?
+                                    .clone(),
+                            )
+                        }
                         _ => {
                             // Handle positional arg as file if not already set or starts with -
                             if !arg.starts_with('-') {
@@ -310,10 +368,20 @@ where
                         }
                     }
                 }
+                // Only rendering looks at individual endpoints
+                if let (Some(tag), false) = (&tag, render) {
+                    return Err(ParseError::InvalidValue(
+                        "--tag".into(),
+                        format!("'{}' requires --render", tag),
+                    ));
+                }
                 Ok(Some(SimulatorAction::Validate {
                     file,
                     recursive,
                     verbose,
+                    render,
+                    fix,
+                    tag,
                 }))
             }
             "logs" | "l" => {
@@ -409,6 +477,16 @@ where
                     interval,
                 }))
             }
+            "stats" => {
+                let mut json = false;
+                for arg in iter.by_ref() {
+                    match arg.as_str() {
+                        "--json" => json = true,
+                        _ => return Err(ParseError::UnknownArgument(arg.clone())),
+                    }
+                }
+                Ok(Some(SimulatorAction::Stats { json }))
+            }
             "set-scenario" => {
                 let scenario = iter
                     .next()
@@ -444,6 +522,36 @@ where
                 }
                 Ok(Some(SimulatorAction::Import { file, output }))
             }
+            "graph" => {
+                let mut input_dir = "services".to_string();
+                let mut format = GraphFormat::Dot;
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--input-dir" | "--services-dir" | "-i" => {
+                            input_dir = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--input-dir".into()))?
+                                .clone()
+                        }
+                        "--format" | "-f" => {
+                            let f = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--format".into()))?;
+                            format = match f.to_lowercase().as_str() {
+                                "dot" => GraphFormat::Dot,
+                                _ => {
+                                    return Err(ParseError::InvalidValue(
+                                        "--format".into(),
+                                        f.clone(),
+                                    ))
+                                }
+                            };
+                        }
+                        _ => return Err(ParseError::UnknownArgument(arg.clone())),
+                    }
+                }
+                Ok(Some(SimulatorAction::Graph { input_dir, format }))
+            }
             "export" => {
                 let mut file = String::new();
                 let mut output = String::new();
@@ -469,6 +577,7 @@ where
                             format = match f.to_lowercase().as_str() {
                                 "openapi" => ExportFormat::Openapi,
                                 "postman" => ExportFormat::Postman,
+                                "msw" => ExportFormat::Msw,
                                 _ => {
                                     return Err(ParseError::InvalidValue(
                                         "--format".into(),
@@ -495,6 +604,7 @@ where
             "generate-types" => {
                 let mut file = String::new();
                 let mut output = String::new();
+                let mut watch = false;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--file" | "--input" | "-i" => {
@@ -509,6 +619,7 @@ where
                                 .ok_or(ParseError::MissingArgument("--output".into()))?
                                 .clone()
                         }
+                        "--watch" | "-w" => watch = true,
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
@@ -518,11 +629,16 @@ where
                 if output.is_empty() {
                     return Err(ParseError::MissingArgument("--output".into()));
                 }
-                Ok(Some(SimulatorAction::GenerateTypes { file, output }))
+                Ok(Some(SimulatorAction::GenerateTypes {
+                    file,
+                    output,
+                    watch,
+                }))
             }
             "generate-query" => {
                 let mut file = String::new();
                 let mut output = String::new();
+                let mut watch = false;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--file" | "--input" | "-i" => {
@@ -537,6 +653,7 @@ where
                                 .ok_or(ParseError::MissingArgument("--output".into()))?
                                 .clone()
                         }
+                        "--watch" | "-w" => watch = true,
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
@@ -546,11 +663,16 @@ where
                 if output.is_empty() {
                     return Err(ParseError::MissingArgument("--output".into()));
                 }
-                Ok(Some(SimulatorAction::GenerateQuery { file, output }))
+                Ok(Some(SimulatorAction::GenerateQuery {
+                    file,
+                    output,
+                    watch,
+                }))
             }
             "generate-view" => {
                 let mut file = String::new();
                 let mut output = String::new();
+                let mut watch = false;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--file" | "--input" | "-i" => {
@@ -565,6 +687,7 @@ where
                                 .ok_or(ParseError::MissingArgument("--output".into()))?
                                 .clone()
                         }
+                        "--watch" | "-w" => watch = true,
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
@@ -574,7 +697,11 @@ where
                 if output.is_empty() {
                     return Err(ParseError::MissingArgument("--output".into()));
                 }
-                Ok(Some(SimulatorAction::GenerateView { file, output }))
+                Ok(Some(SimulatorAction::GenerateView {
+                    file,
+                    output,
+                    watch,
+                }))
             }
             #[cfg(feature = "tui")]
             "new" => {
@@ -639,6 +766,7 @@ where
             "record" => {
                 let mut output = "services".to_string();
                 let mut url = None;
+                let mut infer_schemas = false;
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--output" | "-o" => {
@@ -654,10 +782,54 @@ where
                                     .clone(),
                             )
                         }
+                        "--infer-schemas" => infer_schemas = true,
+                        _ => return Err(ParseError::UnknownArgument(arg.clone())),
+                    }
+                }
+                Ok(Some(SimulatorAction::Record {
+                    output,
+                    url,
+                    infer_schemas,
+                }))
+            }
+            "proxy" => {
+                let mut target = None;
+                let mut port = 9000;
+                let mut record = "services".to_string();
+                let mut infer_schemas = false;
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--target" | "-t" => {
+                            target = Some(
+                                iter.next()
+                                    .ok_or(ParseError::MissingArgument("--target".into()))?
+                                    .clone(),
+                            )
+                        }
+                        "--port" | "-p" => {
+                            let p = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--port".into()))?;
+                            port = p.parse().map_err(|_| {
+                                ParseError::InvalidValue("--port".into(), p.clone())
+                            })?;
+                        }
+                        "--record" | "-o" => {
+                            record = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--record".into()))?
+                                .clone()
+                        }
+                        "--infer-schemas" => infer_schemas = true,
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
-                Ok(Some(SimulatorAction::Record { output, url }))
+                Ok(Some(SimulatorAction::Proxy {
+                    target: target.ok_or(ParseError::MissingArgument("--target".into()))?,
+                    port,
+                    record,
+                    infer_schemas,
+                }))
             }
             "dockerize" => {
                 let mut files = Vec::new();
@@ -691,11 +863,41 @@ where
                 }))
             }
             #[cfg(feature = "contract-testing")]
+            "verify" => {
+                let mut input = String::new();
+                let mut url = String::new();
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--input" | "-i" => {
+                            input = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--input".into()))?
+                                .clone()
+                        }
+                        "--url" | "-u" => {
+                            url = iter
+                                .next()
+                                .ok_or(ParseError::MissingArgument("--url".into()))?
+                                .clone()
+                        }
+                        _ => return Err(ParseError::UnknownArgument(arg.clone())),
+                    }
+                }
+                if input.is_empty() {
+                    return Err(ParseError::MissingArgument("--input".into()));
+                }
+                if url.is_empty() {
+                    return Err(ParseError::MissingArgument("--url".into()));
+                }
+                Ok(Some(SimulatorAction::Verify { input, url }))
+            }
+            #[cfg(feature = "contract-testing")]
             "test" => {
                 let mut path = String::new();
                 let mut url = String::new();
                 let mut env = "default".to_string();
                 let mut quiet = false;
+                let mut headers = Vec::new();
                 while let Some(arg) = iter.next() {
                     match arg.as_str() {
                         "--path" | "-p" => {
@@ -717,6 +919,11 @@ where
                                 .clone()
                         }
                         "--quiet" | "-q" => quiet = true,
+                        "--header" | "-H" => headers.push(
+                            iter.next()
+                                .ok_or(ParseError::MissingArgument("--header".into()))?
+                                .clone(),
+                        ),
                         _ => return Err(ParseError::UnknownArgument(arg.clone())),
                     }
                 }
@@ -731,6 +938,7 @@ where
                     url,
                     env,
                     quiet,
+                    headers,
                 }))
             }
             _ => Err(ParseError::UnknownSubcommand(action.clone())),
@@ -760,6 +968,20 @@ where
     }
 }
 
+fn parse_config_action<'a, I>(iter: &mut I) -> Result<ConfigAction, ParseError>
+where
+    I: Iterator<Item = &'a String>,
+{
+    if let Some(action) = iter.next() {
+        match action.as_str() {
+            "show" => Ok(ConfigAction::Show),
+            _ => Err(ParseError::UnknownSubcommand(action.clone())),
+        }
+    } else {
+        Err(ParseError::MissingSubcommand)
+    }
+}
+
 #[cfg(feature = "iot")]
 fn parse_twin_command<'a, I>(iter: &mut I) -> Result<TwinCommands, ParseError>
 where
@@ -847,10 +1069,18 @@ mod tests {
                     services_dir,
                     force,
                     template,
+                    stdin,
+                    json,
+                    set,
+                    env,
                 } => {
                     assert_eq!(services_dir, "./myservices");
                     assert!(force);
                     assert!(template.is_none());
+                    assert!(!stdin);
+                    assert!(!json);
+                    assert!(set.is_empty());
+                    assert!(env.is_none());
                 }
                 _ => panic!("Wrong action"),
             },
@@ -873,6 +1103,155 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_simulator_start_stdin() {
+        let args = to_args("simulator start --stdin");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Start { stdin, .. } => assert!(stdin),
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_simulator_start_env() {
+        let args = to_args("simulator start --env staging");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Start { env, .. } => assert_eq!(env.as_deref(), Some("staging")),
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_simulator_start_fixture_overrides() {
+        let args = to_args("simulator start --set users=@users.json --set flag=true");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Start { set, .. } => {
+                    assert_eq!(set, vec!["users=@users.json", "flag=true"]);
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_simulator_watch() {
+        let args = to_args("simulator watch --services-dir ./services");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Watch { services_dir } => {
+                    assert_eq!(services_dir, "./services");
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_simulator_graph() {
+        let args = to_args("simulator graph --input-dir services --format dot");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Graph { input_dir, format } => {
+                    assert_eq!(input_dir, "services");
+                    assert_eq!(format, GraphFormat::Dot);
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+        assert!(parse_args(&to_args("simulator graph --format png")).is_err());
+    }
+
+    #[test]
+    fn test_simulator_generate_watch() {
+        let args = to_args("simulator generate-query --file shop.yaml --output shop.ts --watch");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::GenerateQuery { file, watch, .. } => {
+                    assert_eq!(file, "shop.yaml");
+                    assert!(watch);
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_simulator_proxy() {
+        let args = to_args("simulator proxy --target https://api.example.com --port 9100");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Proxy {
+                    target,
+                    port,
+                    record,
+                    infer_schemas,
+                } => {
+                    assert_eq!(target, "https://api.example.com");
+                    assert_eq!(port, 9100);
+                    assert_eq!(record, "services");
+                    assert!(!infer_schemas);
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+        assert!(parse_args(&to_args("simulator proxy --port 9100")).is_err());
+    }
+
+    #[cfg(feature = "contract-testing")]
+    #[test]
+    fn test_simulator_verify() {
+        let args = to_args("simulator verify --input svc.yaml --url http://localhost:8080");
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Verify { input, url } => {
+                    assert_eq!(input, "svc.yaml");
+                    assert_eq!(url, "http://localhost:8080");
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+        assert!(parse_args(&to_args("simulator verify --input svc.yaml")).is_err());
+    }
+
+    #[cfg(feature = "contract-testing")]
+    #[test]
+    fn test_simulator_test_headers() {
+        let args = to_args(
+            "simulator test --path api.yaml --url http://localhost -H X-Api-Key:abc --header Authorization:token",
+        );
+        let cli = parse_args(&args).unwrap();
+        match cli.command {
+            Commands::Simulator { action } => match action.unwrap() {
+                SimulatorAction::Test { headers, .. } => {
+                    assert_eq!(headers, vec!["X-Api-Key:abc", "Authorization:token"]);
+                }
+                _ => panic!("Wrong action"),
+            },
+            _ => panic!("Wrong command"),
+        }
+    }
+
     #[test]
     fn test_new_command() {
         let args = to_args("new my-service --template stripe");
@@ -900,6 +1279,18 @@ mod tests {
         assert!(matches!(err, ParseError::UnknownArgument(_)));
     }
 
+    #[test]
+    fn test_config_show() {
+        let args = to_args("config show");
+        let cli = parse_args(&args).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                action: ConfigAction::Show
+            }
+        ));
+    }
+
     #[test]
     fn test_open_port() {
         let args = to_args("open --port 3000");
@@ -930,17 +1321,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_tag_requires_render() {
+        let args = to_args("simulator validate services/api.yaml --tag beta");
+        assert!(matches!(
+            parse_args(&args),
+            Err(ParseError::InvalidValue(flag, _)) if flag == "--tag"
+        ));
+    }
+
     #[test]
     fn test_validate_positional() {
-        let args = to_args("simulator validate services/api.yaml -r");
+        let args = to_args("simulator validate services/api.yaml -r --render --fix --tag beta");
         let cli = parse_args(&args).unwrap();
         match cli.command {
             Commands::Simulator { action } => match action.unwrap() {
                 SimulatorAction::Validate {
-                    file, recursive, ..
+                    file,
+                    recursive,
+                    render,
+                    fix,
+                    tag,
+                    ..
                 } => {
                     assert_eq!(file, "services/api.yaml");
                     assert!(recursive);
+                    assert!(render);
+                    assert!(fix);
+                    assert_eq!(tag.as_deref(), Some("beta"));
                 }
                 _ => panic!("Wrong action"),
             },
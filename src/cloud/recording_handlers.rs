@@ -184,18 +184,18 @@ pub async fn generate_service_from_recording(
             session_id
         ))),
         server: Some(ServerConfig {
-            port: None,
             base_path: "/".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         twin: None,
     };
 
@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use crate::errors::{ApicentricError, ApicentricResult};
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ParameterDefinition, ParameterLocation, ResponseDefinition,
+    EndpointDefinition, ParameterDefinition, ParameterLocation, ResponseDefinition,
 };
 use crate::utils::validate_ssrf_url;
 use hyper::{HeaderMap, Method};
@@ -405,20 +405,14 @@ fn upsert_recorded_endpoint(
     let (normalized_path, parameter_defs, recorded_values) = parameterize_path(path);
     let key = (method.to_string(), normalized_path.clone());
     let entry = map.entry(key).or_insert_with(|| EndpointDefinition {
-        kind: EndpointKind::Http,
         method: method.to_string(),
         path: normalized_path.clone(),
-        header_match: None,
-        description: None,
         parameters: if parameter_defs.is_empty() {
             None
         } else {
             Some(parameter_defs.clone())
         },
-        request_body: None,
-        responses: HashMap::new(),
-        scenarios: None,
-        stream: None,
+        ..Default::default()
     });
 
     entry.path = normalized_path;
@@ -453,7 +447,16 @@ fn upsert_recorded_endpoint(
             schema: None,
             script: None,
             headers: headers_option,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
         },
     );
 }
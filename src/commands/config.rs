@@ -0,0 +1,12 @@
+use apicentric::cli::args::ConfigAction;
+use apicentric::{ApicentricResult, Context};
+
+/// Handles `apicentric config` subcommands.
+pub async fn config_command(action: &ConfigAction, context: &Context) -> ApicentricResult<()> {
+    match action {
+        ConfigAction::Show => {
+            println!("{}", context.config().to_effective_json()?);
+            Ok(())
+        }
+    }
+}
@@ -76,6 +76,11 @@ mod tests {
             db_path: std::path::PathBuf::from(":memory:"),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         };
         ApiSimulatorManager::new(config)
     }
@@ -404,13 +404,9 @@ impl EventHandler {
         for req in &session.captured_requests {
             let key = (req.method.clone(), req.url.clone());
             endpoints.entry(key).or_insert_with(|| EndpointDefinition {
-                kind: apicentric::simulator::config::EndpointKind::Http,
                 method: req.method.to_uppercase(),
                 path: req.url.split('?').next().unwrap_or("/").to_string(),
-                header_match: None,
                 description: Some("Recorded endpoint".to_string()),
-                parameters: None,
-                request_body: None,
                 responses: {
                     let mut map = HashMap::new();
                     map.insert(
@@ -421,14 +417,22 @@ impl EventHandler {
                             body: req.body.clone().unwrap_or_else(|| "{}".to_string()),
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                             schema: None,
                         },
                     );
                     map
                 },
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             });
         }
 
@@ -440,15 +444,17 @@ impl EventHandler {
                 port: Some(session.proxy_port),
                 base_path: "/".to_string(),
                 proxy_base_url: Some(session.target_url.clone()),
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(endpoints.into_values().collect()),
             graphql: None,
             behavior: None,
+            state_machine: None,
             twin: None,
         };
 
@@ -915,6 +921,11 @@ mod tests {
             db_path: std::path::PathBuf::from(":memory:"),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         };
         Arc::new(ApiSimulatorManager::new(config))
     }
@@ -55,6 +55,11 @@ pub async fn gui_command() -> crate::ApicentricResult<()> {
         db_path: std::path::PathBuf::from("apicentric.db"),
         admin_port: Some(8080),
         global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
     };
 
     let manager = Arc::new(ApiSimulatorManager::new(config));
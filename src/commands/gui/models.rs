@@ -67,7 +67,7 @@ impl RequestLogEntry {
             method: log.method.clone(),
             path: log.path.clone(),
             status_code: log.status,
-            duration_ms: 0, // Simulator log doesn't track duration
+            duration_ms: log.duration_ms.unwrap_or(0),
         }
     }
 }
@@ -22,7 +22,7 @@ use apicentric::domain::contract_testing::HttpMethod;
 use apicentric::domain::ports::contract::ServiceSpec;
 #[cfg(feature = "mcp")]
 use apicentric::simulator::config::{
-    EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig, ServiceDefinition,
+    EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
 };
 #[cfg(feature = "mcp")]
 use std::collections::HashMap;
@@ -111,22 +111,25 @@ impl ApicentricMcpService {
                     body: endpoint.response.body_template,
                     script: None,
                     headers: Some(endpoint.response.headers),
+                    trailers: None,
+                    examples: None,
+                    record_delay_ms: None,
+                    pretty_json: None,
+                    sparse_fields: false,
+                    serve_times: None,
+                    exec: None,
                     side_effects: None,
+                    body_base64: None,
+                    body_file: None,
                     schema: None,
                 },
             );
 
             let endpoint_def = EndpointDefinition {
-                kind: EndpointKind::Http,
                 method,
                 path: endpoint.path,
-                header_match: None,
-                description: None,
-                parameters: None,
-                request_body: None,
                 responses,
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             };
 
             endpoints.push(endpoint_def);
@@ -151,16 +154,17 @@ impl ApicentricMcpService {
             server: Some(ServerConfig {
                 port: Some(spec.port),
                 base_path: spec.base_path,
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: Some(fixtures),
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(endpoints),
             graphql: None,
             behavior: None,
+            state_machine: None,
             twin: None,
         })
     }
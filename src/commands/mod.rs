@@ -1,6 +1,7 @@
 pub mod ai;
 #[cfg(feature = "webui")]
 pub mod cloud;
+pub mod config;
 pub mod doctor;
 pub mod gui;
 #[cfg(feature = "mcp")]
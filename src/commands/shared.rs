@@ -1,9 +1,12 @@
 use crate::{ApicentricError, ApicentricResult};
-use apicentric::simulator::config::validation::{ConfigFileLoader, ConfigRepository};
+use apicentric::simulator::config::validation::{
+    fix_service_yaml, ConfigFileLoader, ConfigLoader, ConfigRepository,
+};
 #[cfg(feature = "tui")]
 use apicentric::simulator::config::{
-    EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig, ServiceDefinition,
+    EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
 };
+use apicentric::simulator::template::TemplateEngine;
 #[cfg(feature = "tui")]
 use inquire::{Confirm, Select, Text};
 #[cfg(feature = "tui")]
@@ -40,7 +43,88 @@ pub fn validate_yaml_file(file_path: &Path) -> ApicentricResult<()> {
     let loader = ConfigFileLoader::new(parent.to_path_buf());
 
     // This will attempt to read the file and parse it into UnifiedConfig -> ServiceDefinition
-    loader.load_service(file_path).map(|_| ())
+    // (every entry of a combined `services:` manifest)
+    loader.load_services(file_path).map(|_| ())
+}
+
+/// Applies the safe automatic fixes to a service file and writes it back.
+///
+/// Returns the list of changes; the file is left untouched when there are none.
+/// Rewriting would drop YAML comments, so a file with comments is never
+/// rewritten: the fixes it needs are returned as an error to apply by hand.
+pub fn fix_yaml_file(file_path: &Path) -> ApicentricResult<Vec<String>> {
+    let content = std::fs::read_to_string(file_path).map_err(|e| {
+        ApicentricError::fs_error(
+            format!("Failed to read {}: {}", file_path.display(), e),
+            None::<String>,
+        )
+    })?;
+    let mut doc: serde_yaml::Value = serde_yaml::from_str(&content).map_err(|e| {
+        ApicentricError::config_error(
+            format!("Invalid YAML in {}: {}", file_path.display(), e),
+            Some("Fix the YAML syntax; --fix only repairs well-formed files"),
+        )
+    })?;
+    let changes = fix_service_yaml(&mut doc);
+    if !changes.is_empty() && has_comments(&content) {
+        return Err(ApicentricError::config_error(
+            format!(
+                "Not rewriting {}: its comments would be lost. Needed fixes:\n   {}",
+                file_path.display(),
+                changes.join("\n   ")
+            ),
+            Some("Apply the listed fixes by hand"),
+        ));
+    }
+    if !changes.is_empty() {
+        let fixed = serde_yaml::to_string(&doc).map_err(|e| {
+            ApicentricError::config_error(
+                format!("Failed to serialize {}: {}", file_path.display(), e),
+                None::<String>,
+            )
+        })?;
+        let parent = file_path.parent().unwrap_or_else(|| Path::new("."));
+        ConfigLoader::new(parent.to_path_buf()).save_service(file_path, &fixed)?;
+    }
+    Ok(changes)
+}
+
+/// Whether any line carries a YAML comment. A `#` inside a quoted string
+/// also counts, which only means such a file is left for a manual fix.
+fn has_comments(content: &str) -> bool {
+    content.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with('#') || line.contains(" #") || line.contains("\t#")
+    })
+}
+
+/// Validates a service file and renders each of its response templates with
+/// sample data, failing with the location of every template that errors.
+///
+/// With a `tag`, only endpoints carrying that tag are rendered.
+pub fn render_yaml_file_templates(file_path: &Path, tag: Option<&str>) -> ApicentricResult<()> {
+    let parent = file_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut services = ConfigFileLoader::new(parent.to_path_buf()).load_services(file_path)?;
+    let mut failures = Vec::new();
+    for service in &mut services {
+        if let Some(tag) = tag {
+            service.retain_tagged(tag);
+        }
+        failures.extend(TemplateEngine::check_service_templates(service)?);
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(ApicentricError::validation_error(
+            format!(
+                "Template rendering failed in {}:\n   {}",
+                file_path.display(),
+                failures.join("\n   ")
+            ),
+            None::<String>,
+            Some("Fix the Handlebars syntax or helper arguments at the listed locations"),
+        ))
+    }
 }
 
 fn find_yaml_files_in_dir(dir: &Path, files: &mut Vec<PathBuf>) -> ApicentricResult<()> {
@@ -154,16 +238,17 @@ pub fn scaffold_service_definition() -> ApicentricResult<ServiceDefinition> {
         server: Some(ServerConfig {
             port: Some(port),
             base_path,
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     })
@@ -230,20 +315,57 @@ pub fn scaffold_endpoint_definition() -> ApicentricResult<EndpointDefinition> {
             schema: None,
             script: None,
             headers: None,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
         },
     );
 
     Ok(EndpointDefinition {
-        kind: EndpointKind::Http,
         method,
         path,
-        header_match: None,
         description: description.filter(|s: &String| !s.is_empty()),
-        parameters: None,
-        request_body: None,
         responses,
-        scenarios: None,
-        stream: None,
+        ..Default::default()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNFIXED: &str = "name: svc\nendpoints:\n  - method: get\n    path: /a\n    responses:\n      200:\n        body: ok\n        content_type: text/plain\n";
+
+    #[test]
+    fn fix_leaves_commented_files_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("svc.yaml");
+        let content = format!("# owned by the payments team\n{}", UNFIXED);
+        std::fs::write(&path, &content).unwrap();
+
+        let err = fix_yaml_file(&path).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("endpoints[0].method: 'get' -> 'GET'"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn fix_rewrites_files_without_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("svc.yaml");
+        std::fs::write(&path, UNFIXED).unwrap();
+
+        assert_eq!(fix_yaml_file(&path).unwrap().len(), 1);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("method: GET"));
+    }
+}
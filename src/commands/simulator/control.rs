@@ -1,10 +1,18 @@
+use apicentric::simulator::config::{ConfigLoader, LoadError};
+use apicentric::simulator::{ConfigChange, SimulatorStatus};
 use apicentric::{ApicentricError, ApicentricResult, Context, ExecutionContext};
+use serde_json::{json, Value};
+use std::collections::HashMap;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_start(
     context: &Context,
     services_dir: &str,
     force: bool,
     template: Option<&str>,
+    stdin: bool,
+    json: bool,
+    fixture_overrides: &[String],
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
     // Install template if provided
@@ -31,32 +39,83 @@ pub async fn handle_start(
         );
         return Ok(());
     }
-    println!(
-        "🚀 Starting API Simulator...\n📁 Services directory: {}",
-        services_dir
-    );
+    let piped_service = if stdin {
+        let mut yaml = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut yaml).map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to read service definition from stdin: {}", e),
+                None::<String>,
+            )
+        })?;
+        Some(ConfigLoader::load_service_from_yaml(&yaml, "<stdin>")?)
+    } else {
+        None
+    };
+    if !json {
+        let source = if stdin {
+            "📥 Service definition: <stdin>".to_string()
+        } else {
+            format!("📁 Services directory: {}", services_dir)
+        };
+        println!("🚀 Starting API Simulator...\n{}", source);
+    }
     if let Some(simulator) = context.api_simulator() {
         if force && simulator.is_active().await {
-            println!("🔄 Force stopping existing simulator...");
+            if !json {
+                println!("🔄 Force stopping existing simulator...");
+            }
             simulator.stop().await?;
         }
-        match simulator.start().await {
+        if !fixture_overrides.is_empty() {
+            let overrides = fixture_overrides
+                .iter()
+                .map(|raw| parse_fixture_override(raw))
+                .collect::<ApicentricResult<HashMap<_, _>>>()?;
+            simulator.set_fixture_overrides(overrides).await;
+        }
+        let (started, skipped) = match piped_service {
+            Some(service) => (
+                simulator.start_with_services(vec![service]).await,
+                Vec::new(),
+            ),
+            None => {
+                // Malformed files are skipped so the valid services still start
+                let loaded = simulator.load_services_with_summary()?;
+                (
+                    simulator.start_loaded_services(loaded.services).await,
+                    loaded.summary.errors,
+                )
+            }
+        };
+        match started {
             Ok(_) => {
                 let status = simulator.get_status().await;
-                println!(
-                    "✅ API Simulator started ({} services, {} active)",
-                    status.services_count,
-                    status.active_services.len()
-                );
-                for svc in &status.active_services {
+                if json {
+                    println!("{}", startup_summary(&status, &skipped));
+                } else {
+                    if !skipped.is_empty() {
+                        println!("⚠️  Skipped {} invalid service file(s):", skipped.len());
+                        for warning in skipped_file_warnings(&skipped) {
+                            println!("   - {}", warning);
+                        }
+                    }
                     println!(
-                        "   - {}: http://localhost:{}{}",
-                        svc.name, svc.port, svc.base_path
+                        "✅ API Simulator started ({} services, {} active)",
+                        status.services_count,
+                        status.active_services.len()
                     );
+                    for svc in &status.active_services {
+                        println!(
+                            "   - {}: http://localhost:{}{}",
+                            svc.name, svc.port, svc.base_path
+                        );
+                    }
+                    println!("🔄 Simulator running... Press Ctrl+C to stop");
                 }
-                println!("🔄 Simulator running... Press Ctrl+C to stop");
                 tokio::signal::ctrl_c().await.ok();
-                println!("🛑 Stopping simulator…");
+                if !json {
+                    println!("🛑 Stopping simulator…");
+                }
                 simulator.stop().await.ok();
             }
             Err(e) => {
@@ -75,6 +134,152 @@ pub async fn handle_start(
     Ok(())
 }
 
+pub async fn handle_watch(
+    context: &Context,
+    services_dir: &str,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    if exec_ctx.dry_run {
+        println!(
+            "🏃 Dry run: Would start API simulator and watch '{}' for changes",
+            services_dir
+        );
+        return Ok(());
+    }
+    if !cfg!(feature = "file-watch") {
+        return Err(ApicentricError::config_error(
+            "Watching requires the 'file-watch' feature",
+            Some("Rebuild apicentric with --features file-watch"),
+        ));
+    }
+    let Some(simulator) = context.api_simulator() else {
+        return Err(ApicentricError::config_error(
+            "API simulator is not enabled or configured",
+            Some("Enable simulator in apicentric.json"),
+        ));
+    };
+
+    // The simulator watches the directory it loads services from
+    let watched_dir = simulator.get_services_dir();
+    if watched_dir != std::path::Path::new(services_dir) {
+        return Err(ApicentricError::config_error(
+            format!(
+                "Simulator is configured for '{}', not '{}'",
+                watched_dir.display(),
+                services_dir
+            ),
+            Some("Set simulator.services_dir to the directory you want to watch"),
+        ));
+    }
+
+    // Subscribe before starting so no early change is missed
+    let mut changes = simulator.subscribe_config_changes();
+    println!(
+        "🚀 Starting API Simulator...\n👀 Watching services directory: {}",
+        watched_dir.display()
+    );
+    simulator.start().await.map_err(|e| {
+        ApicentricError::runtime_error(
+            format!("Failed to start simulator: {}", e),
+            Some("Check service configurations and port availability"),
+        )
+    })?;
+    let status = simulator.get_status().await;
+    println!(
+        "✅ API Simulator started ({} services, {} active)",
+        status.services_count,
+        status.active_services.len()
+    );
+    println!("🔄 Watching for changes... Press Ctrl+C to stop");
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            change = changes.recv() => match change {
+                Ok(change) => {
+                    match change {
+                        ConfigChange::ServiceAdded(name) => println!("📁 Service added: {}", name),
+                        ConfigChange::ServiceModified(name) => {
+                            println!("📝 Service modified: {}", name)
+                        }
+                        ConfigChange::ServiceRemoved(name) => {
+                            println!("🗑️ Service removed: {}", name)
+                        }
+                    }
+                    let status = simulator.get_status().await;
+                    println!(
+                        "♻️ Reloaded: {} services, {} active",
+                        status.services_count,
+                        status.active_services.len()
+                    );
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+
+    println!("🛑 Stopping simulator…");
+    simulator.stop().await.ok();
+    Ok(())
+}
+
+/// Parse a `--set key=value` fixture override. `key=@path` loads a JSON or
+/// YAML file; other values are read as JSON, falling back to a plain string.
+fn parse_fixture_override(raw: &str) -> ApicentricResult<(String, Value)> {
+    let (key, value) = raw.split_once('=').ok_or_else(|| {
+        ApicentricError::validation_error(
+            format!("Invalid fixture override '{}'", raw),
+            Some("set"),
+            Some("Use key=value or key=@file.json"),
+        )
+    })?;
+    let value = match value.strip_prefix('@') {
+        Some(path) => {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                ApicentricError::fs_error(
+                    format!("Cannot read fixture file {}: {}", path, e),
+                    Some("Check the path passed to --set"),
+                )
+            })?;
+            serde_yaml::from_str(&content).map_err(|e| {
+                ApicentricError::validation_error(
+                    format!("Invalid fixture file {}: {}", path, e),
+                    Some("set"),
+                    None::<String>,
+                )
+            })?
+        }
+        None => serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string())),
+    };
+    Ok((key.to_string(), value))
+}
+
+/// One line per service file that failed to load during start
+pub fn skipped_file_warnings(errors: &[LoadError]) -> Vec<String> {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.file_path.display(), e.message))
+        .collect()
+}
+
+/// Single-line JSON object describing where each started service listens.
+fn startup_summary(status: &SimulatorStatus, skipped: &[LoadError]) -> serde_json::Value {
+    let services: Vec<serde_json::Value> = status
+        .active_services
+        .iter()
+        .map(|svc| {
+            json!({
+                "name": svc.name,
+                "port": svc.port,
+                "base_path": svc.base_path,
+                "endpoints": svc.endpoints_count,
+            })
+        })
+        .collect();
+    json!({ "services": services, "skipped": skipped_file_warnings(skipped) })
+}
+
 pub async fn handle_stop(
     context: &Context,
     force: bool,
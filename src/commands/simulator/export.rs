@@ -1,4 +1,5 @@
-use apicentric::cli::args::ExportFormat;
+use apicentric::cli::args::{ExportFormat, GraphFormat};
+use apicentric::simulator::config::ConfigLoader;
 use apicentric::{ApicentricError, ApicentricResult, ExecutionContext};
 
 pub async fn handle_export(
@@ -41,6 +42,7 @@ pub async fn handle_export(
                 )
             })?
         }
+        ExportFormat::Msw => apicentric::simulator::msw::to_msw_handlers(&service),
     };
 
     std::fs::write(output, output_content).map_err(|e| {
@@ -54,93 +56,186 @@ pub async fn handle_export(
     Ok(())
 }
 
-pub async fn handle_export_types(
-    input: &str,
-    output: &str,
+pub async fn handle_graph(
+    input_dir: &str,
+    format: &GraphFormat,
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
     if exec_ctx.dry_run {
         println!(
-            "🏃 Dry run: Would export TypeScript types from '{}' to '{}'",
-            input, output
+            "🏃 Dry run: Would render a {:?} graph of services in '{}'",
+            format, input_dir
         );
         return Ok(());
     }
-    let yaml = std::fs::read_to_string(input).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to read service: {}", e), None::<String>)
-    })?;
-    let service: apicentric::simulator::config::ServiceDefinition = serde_yaml::from_str(&yaml)
-        .map_err(|e| {
-            ApicentricError::runtime_error(format!("Invalid service YAML: {}", e), None::<String>)
-        })?;
-    let ts = apicentric::simulator::typescript::to_typescript(&service).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to generate types: {}", e), None::<String>)
-    })?;
-    std::fs::write(output, ts).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to write types file: {}", e), None::<String>)
-    })?;
-    println!("✅ Exported TypeScript types to {}", output);
+
+    let services = ConfigLoader::new(input_dir.into()).load_all_services()?;
+    match format {
+        GraphFormat::Dot => print!("{}", apicentric::simulator::graph::to_dot(&services)),
+    }
     Ok(())
 }
 
-pub async fn handle_export_query(
-    input: &str,
-    output: &str,
-    exec_ctx: &ExecutionContext,
-) -> ApicentricResult<()> {
-    if exec_ctx.dry_run {
-        println!(
-            "🏃 Dry run: Would export React Query hooks from '{}' to '{}'",
-            input, output
-        );
-        return Ok(());
+/// Frontend code produced from a service definition by the `generate-*` commands
+#[derive(Debug, Clone, Copy)]
+enum Generator {
+    Types,
+    Query,
+    View,
+}
+
+impl Generator {
+    fn label(self) -> &'static str {
+        match self {
+            Generator::Types => "TypeScript types",
+            Generator::Query => "React Query hooks",
+            Generator::View => "React view",
+        }
     }
-    let yaml = std::fs::read_to_string(input).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to read service: {}", e), None::<String>)
-    })?;
-    let service: apicentric::simulator::config::ServiceDefinition = serde_yaml::from_str(&yaml)
-        .map_err(|e| {
-            ApicentricError::runtime_error(format!("Invalid service YAML: {}", e), None::<String>)
+
+    fn generate(
+        self,
+        service: &apicentric::simulator::config::ServiceDefinition,
+    ) -> ApicentricResult<String> {
+        match self {
+            Generator::Types => apicentric::simulator::typescript::to_typescript(service),
+            Generator::Query => {
+                apicentric::simulator::react_query::generate_react_query_hooks(service)
+            }
+            Generator::View => apicentric::simulator::react_view::generate_react_view(service),
+        }
+    }
+
+    /// Read `input`, generate the code and write it to `output`
+    fn write(self, input: &str, output: &str) -> ApicentricResult<()> {
+        let yaml = std::fs::read_to_string(input).map_err(|e| {
+            ApicentricError::runtime_error(format!("Failed to read service: {}", e), None::<String>)
         })?;
-    let ts =
-        apicentric::simulator::react_query::generate_react_query_hooks(&service).map_err(|e| {
+        let service: apicentric::simulator::config::ServiceDefinition = serde_yaml::from_str(&yaml)
+            .map_err(|e| {
+                ApicentricError::runtime_error(
+                    format!("Invalid service YAML: {}", e),
+                    None::<String>,
+                )
+            })?;
+        let code = self.generate(&service).map_err(|e| {
             ApicentricError::runtime_error(
-                format!("Failed to generate hooks: {}", e),
+                format!("Failed to generate {}: {}", self.label(), e),
                 None::<String>,
             )
         })?;
-    std::fs::write(output, ts).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to write hooks file: {}", e), None::<String>)
-    })?;
-    println!("✅ Exported React Query hooks to {}", output);
-    Ok(())
+        std::fs::write(output, code).map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to write {} file: {}", self.label(), e),
+                None::<String>,
+            )
+        })
+    }
+
+    async fn run(
+        self,
+        input: &str,
+        output: &str,
+        watch: bool,
+        exec_ctx: &ExecutionContext,
+    ) -> ApicentricResult<()> {
+        if exec_ctx.dry_run {
+            println!(
+                "🏃 Dry run: Would export {} from '{}' to '{}'{}",
+                self.label(),
+                input,
+                output,
+                if watch { " and keep watching" } else { "" }
+            );
+            return Ok(());
+        }
+        self.write(input, output)?;
+        println!("✅ Exported {} to {}", self.label(), output);
+        if watch {
+            self.watch(input, output).await?;
+        }
+        Ok(())
+    }
+
+    /// Regenerate `output` whenever `input` changes, until Ctrl+C
+    #[cfg(feature = "file-watch")]
+    async fn watch(self, input: &str, output: &str) -> ApicentricResult<()> {
+        use apicentric::simulator::watcher::ConfigWatcher;
+        use apicentric::simulator::ConfigChange;
+
+        let path = std::path::Path::new(input);
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let (tx, mut changes) = tokio::sync::mpsc::channel(16);
+        let _watcher = ConfigWatcher::new(dir.to_path_buf(), tx).map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to watch {}: {}", input, e),
+                None::<String>,
+            )
+        })?;
+        println!("👀 Watching {} for changes... Press Ctrl+C to stop", input);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => break,
+                change = changes.recv() => match change {
+                    Some(ConfigChange::ServiceAdded(name) | ConfigChange::ServiceModified(name))
+                        if name == stem =>
+                    {
+                        // Editors emit several events per save; let them settle first
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                        while changes.try_recv().is_ok() {}
+                        match self.write(input, output) {
+                            Ok(()) => println!("♻️ Regenerated {} in {}", self.label(), output),
+                            Err(e) => eprintln!("❌ {}", e),
+                        }
+                    }
+                    Some(_) => continue,
+                    None => break,
+                },
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "file-watch"))]
+    async fn watch(self, _input: &str, _output: &str) -> ApicentricResult<()> {
+        Err(ApicentricError::config_error(
+            "Watching requires the 'file-watch' feature",
+            Some("Rebuild apicentric with --features file-watch"),
+        ))
+    }
+}
+
+pub async fn handle_export_types(
+    input: &str,
+    output: &str,
+    watch: bool,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    Generator::Types.run(input, output, watch, exec_ctx).await
+}
+
+pub async fn handle_export_query(
+    input: &str,
+    output: &str,
+    watch: bool,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    Generator::Query.run(input, output, watch, exec_ctx).await
 }
 
 pub async fn handle_export_view(
     input: &str,
     output: &str,
+    watch: bool,
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
-    if exec_ctx.dry_run {
-        println!(
-            "🏃 Dry run: Would export React view from '{}' to '{}'",
-            input, output
-        );
-        return Ok(());
-    }
-    let yaml = std::fs::read_to_string(input).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to read service: {}", e), None::<String>)
-    })?;
-    let service: apicentric::simulator::config::ServiceDefinition = serde_yaml::from_str(&yaml)
-        .map_err(|e| {
-            ApicentricError::runtime_error(format!("Invalid service YAML: {}", e), None::<String>)
-        })?;
-    let tsx = apicentric::simulator::react_view::generate_react_view(&service).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to generate view: {}", e), None::<String>)
-    })?;
-    std::fs::write(output, tsx).map_err(|e| {
-        ApicentricError::runtime_error(format!("Failed to write view file: {}", e), None::<String>)
-    })?;
-    println!("✅ Exported React view to {}", output);
-    Ok(())
+    Generator::View.run(input, output, watch, exec_ctx).await
 }
@@ -5,22 +5,30 @@ use apicentric::simulator::log::RequestLogEntry;
 use apicentric::{ApicentricError, ApicentricResult, Context, ExecutionContext};
 use chrono::{DateTime, Utc};
 
-use crate::commands::shared::{find_yaml_files, validate_yaml_file};
+use crate::commands::shared::{
+    find_yaml_files, fix_yaml_file, render_yaml_file_templates, validate_yaml_file,
+};
 
 pub async fn handle_validate(
     path: &str,
     recursive: bool,
     verbose: bool,
+    render: bool,
+    fix: bool,
+    tag: Option<&str>,
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
     if exec_ctx.dry_run {
         println!(
-            "🏃 Dry run: Would validate service definitions (path={}, recursive={}, verbose={})",
-            path, recursive, verbose
+            "🏃 Dry run: Would validate service definitions (path={}, recursive={}, verbose={}, render={}, fix={}, tag={})",
+            path, recursive, verbose, render, fix, tag.unwrap_or("none")
         );
         return Ok(());
     }
     println!("🔍 Validating service definitions...\n📁 Path: {}", path);
+    if let Some(tag) = tag {
+        println!("🏷️ Only endpoints tagged '{}'", tag);
+    }
     let path_buf = PathBuf::from(path);
     if !path_buf.exists() {
         println!("⚠️ Path does not exist: {}", path);
@@ -36,7 +44,22 @@ pub async fn handle_validate(
         if verbose {
             println!("🔎 {}", f.display());
         }
-        match validate_yaml_file(f) {
+        if fix {
+            match fix_yaml_file(f) {
+                Ok(changes) => {
+                    for change in &changes {
+                        println!("   🔧 {}: {}", f.display(), change);
+                    }
+                }
+                Err(e) => println!("   ⚠️ Could not fix {}: {}", f.display(), e),
+            }
+        }
+        let result = if render {
+            render_yaml_file_templates(f, tag)
+        } else {
+            validate_yaml_file(f)
+        };
+        match result {
             Ok(_) => {
                 valid += 1;
                 if verbose {
@@ -67,9 +90,11 @@ pub async fn handle_validate(
 use apicentric::adapters::{
     noop_telemetry::{NoOpMetrics, NoOpPublisher, NoOpTracer},
     simulator_manager_adapter::SimulatorManagerAdapter,
-    ReqwestHttpClientAdapter, YamlServiceSpecLoader,
+    HttpClientBuilder, YamlServiceSpecLoader,
 };
 #[cfg(feature = "contract-testing")]
+use apicentric::config::RetryPolicy;
+#[cfg(feature = "contract-testing")]
 use apicentric::{
     Contract, ContractId, ContractValidationResult, RealApiConfig, RetryAttempts,
     ScenarioExecutionUseCase, SpecValidationUseCase,
@@ -81,8 +106,19 @@ pub async fn handle_contract_test(
     url: &str,
     env: &str,
     quiet: bool,
+    headers: &[String],
+    retry: Option<&RetryPolicy>,
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
+    let mut client_builder = HttpClientBuilder::new();
+    if let Some(policy) = retry {
+        client_builder = client_builder.retry_policy(policy.clone());
+    }
+    for header in headers {
+        let (name, value) = parse_header(header)?;
+        client_builder = client_builder.default_header(name, value);
+    }
+
     if exec_ctx.dry_run {
         println!(
             "🏃 Dry run: Would run contract tests (path={}, url={}, env={})",
@@ -123,7 +159,7 @@ pub async fn handle_contract_test(
         println!("\n🔬 Found {} test scenarios.", scenarios.len());
     }
 
-    let http_client = ReqwestHttpClientAdapter::new();
+    let http_client = client_builder.build();
     let mock_runner = SimulatorManagerAdapter::new();
     let metrics = Box::new(NoOpMetrics);
     let tracer = Box::new(NoOpTracer);
@@ -160,6 +196,82 @@ pub async fn handle_contract_test(
     Ok(())
 }
 
+/// Request every endpoint of a service definition from a running instance
+/// and fail when any answer drifts from what the definition declares
+#[cfg(feature = "contract-testing")]
+pub async fn handle_verify(
+    input: &str,
+    url: &str,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    if exec_ctx.dry_run {
+        println!(
+            "🏃 Dry run: Would verify '{}' against instance at {}",
+            input, url
+        );
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(input)?;
+    let definition =
+        apicentric::simulator::config::ConfigLoader::load_service_from_yaml(&content, input)?;
+    println!("🔎 Verifying '{}' against {}", definition.name, url);
+
+    let verifier = apicentric::contract::InstanceVerifier::new(HttpClientBuilder::new().build());
+    let results = verifier.verify(&definition, url).await?;
+    let drifted: Vec<_> = results
+        .iter()
+        .filter_map(|result| result.compliance_issue.as_ref())
+        .collect();
+
+    println!(
+        "\n📊 Verification Results: endpoints={} matching={} drifted={}",
+        results.len(),
+        results.len() - drifted.len(),
+        drifted.len()
+    );
+    if drifted.is_empty() {
+        println!("✅ Instance matches the definition");
+        return Ok(());
+    }
+    for issue in &drifted {
+        println!("   ❌ {}: {}", issue.scenario_path, issue.description);
+        for line in apicentric::contract::render_differences(issue) {
+            println!("       {}", line);
+        }
+    }
+    Err(ApicentricError::validation_error(
+        format!(
+            "{} endpoint(s) of '{}' drifted from the definition",
+            drifted.len(),
+            definition.name
+        ),
+        None::<&str>,
+        Some("Restart the instance from the definition or update the YAML to match"),
+    ))
+}
+
+/// Split a `Name: value` header argument, rejecting names or values reqwest
+/// would not send
+#[cfg(feature = "contract-testing")]
+fn parse_header(header: &str) -> ApicentricResult<(String, String)> {
+    let invalid = || {
+        ApicentricError::validation_error(
+            format!("Invalid header '{}'", header),
+            Some("--header"),
+            Some("Use the form 'Name: value', e.g. 'Authorization: Bearer <token>'"),
+        )
+    };
+    let (name, value) = header.split_once(':').ok_or_else(invalid)?;
+    let (name, value) = (name.trim(), value.trim());
+    if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+        || reqwest::header::HeaderValue::from_str(value).is_err()
+    {
+        return Err(invalid());
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
 #[cfg(feature = "contract-testing")]
 fn print_result(result: &ContractValidationResult, quiet: bool) {
     let passed = result
@@ -216,11 +328,24 @@ fn print_result(result: &ContractValidationResult, quiet: bool) {
                 println!("   - Path:     {}", issue.scenario_path);
                 println!("     Severity: {:?}", issue.severity);
                 println!("     Issue:    {}", issue.description);
+                for line in apicentric::contract::render_differences(issue) {
+                    println!("       {}", line);
+                }
             }
         }
     }
 }
 
+/// GET an internal `/__apicentric/*` endpoint, authenticating with
+/// `APICENTRIC_ADMIN_TOKEN` when it is set
+async fn fetch_internal(url: &str) -> reqwest::Result<reqwest::Response> {
+    let mut request = reqwest::Client::new().get(url);
+    if let Ok(token) = std::env::var("APICENTRIC_ADMIN_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+    request.send().await
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_logs(
     context: &Context,
@@ -264,7 +389,7 @@ pub async fn handle_logs(
                 url.push_str("&status=");
                 url.push_str(&s.to_string());
             }
-            let resp = reqwest::get(&url).await.map_err(|e| {
+            let resp = fetch_internal(&url).await.map_err(|e| {
                 ApicentricError::runtime_error(
                     format!("Failed to fetch logs: {}", e),
                     None::<String>,
@@ -324,6 +449,62 @@ pub async fn handle_logs(
     }
 }
 
+pub async fn handle_stats(
+    context: &Context,
+    json: bool,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    if exec_ctx.dry_run {
+        println!(
+            "🏃 Dry run: Would summarize simulator traffic (json={})",
+            json
+        );
+        return Ok(());
+    }
+    let simulator = context.api_simulator().ok_or_else(|| {
+        ApicentricError::config_error(
+            "API simulator is not enabled or configured",
+            Some("Enable simulator in apicentric.json"),
+        )
+    })?;
+    let stats = simulator.endpoint_stats().await?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&stats).map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to serialize stats: {}", e),
+                None::<String>,
+            )
+        })?;
+        println!("{}", output);
+    } else if stats.is_empty() {
+        println!("No traffic recorded yet.");
+    } else {
+        println!("📊 Traffic per endpoint");
+        for endpoint in &stats {
+            let classes: Vec<String> = endpoint
+                .status_classes
+                .iter()
+                .map(|(class, count)| format!("{}={}", class, count))
+                .collect();
+            let latency = endpoint
+                .avg_latency_ms
+                .map(|ms| format!("{:.1}ms", ms))
+                .unwrap_or_else(|| "-".to_string());
+            println!(
+                " - {} {} {}: {} requests ({}) avg {}",
+                endpoint.service,
+                endpoint.method,
+                endpoint.path,
+                endpoint.count,
+                classes.join(", "),
+                latency
+            );
+        }
+    }
+    Ok(())
+}
+
 pub async fn handle_monitor(
     context: &Context,
     service: Option<&str>,
@@ -371,7 +552,7 @@ pub async fn handle_monitor(
                 url.push('/');
             }
             url.push_str("__apicentric/logs?limit=100");
-            let resp = reqwest::get(&url).await.map_err(|e| {
+            let resp = fetch_internal(&url).await.map_err(|e| {
                 ApicentricError::runtime_error(
                     format!("Failed to fetch logs: {}", e),
                     None::<String>,
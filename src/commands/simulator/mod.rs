@@ -19,9 +19,25 @@ pub async fn simulator_command(
             services_dir,
             force,
             template,
+            stdin,
+            json,
+            set,
+            ..
         } => {
-            control::handle_start(context, services_dir, *force, template.as_deref(), exec_ctx)
-                .await
+            control::handle_start(
+                context,
+                services_dir,
+                *force,
+                template.as_deref(),
+                *stdin,
+                *json,
+                set,
+                exec_ctx,
+            )
+            .await
+        }
+        SimulatorAction::Watch { services_dir } => {
+            control::handle_watch(context, services_dir, exec_ctx).await
         }
         SimulatorAction::Stop { force } => control::handle_stop(context, *force, exec_ctx).await,
         SimulatorAction::Status { detailed } => {
@@ -31,7 +47,21 @@ pub async fn simulator_command(
             file,
             recursive,
             verbose,
-        } => inspect::handle_validate(file, *recursive, *verbose, exec_ctx).await,
+            render,
+            fix,
+            tag,
+        } => {
+            inspect::handle_validate(
+                file,
+                *recursive,
+                *verbose,
+                *render,
+                *fix,
+                tag.as_deref(),
+                exec_ctx,
+            )
+            .await
+        }
         SimulatorAction::Logs {
             service,
             limit,
@@ -57,6 +87,7 @@ pub async fn simulator_command(
             json,
             interval,
         } => inspect::handle_monitor(context, service.as_deref(), *json, *interval, exec_ctx).await,
+        SimulatorAction::Stats { json } => inspect::handle_stats(context, *json, exec_ctx).await,
         SimulatorAction::SetScenario { scenario } => {
             control::handle_set_scenario(context, scenario, exec_ctx).await
         }
@@ -68,15 +99,24 @@ pub async fn simulator_command(
             output,
             format,
         } => export::handle_export(file, output, format, exec_ctx).await,
-        SimulatorAction::GenerateTypes { file, output } => {
-            export::handle_export_types(file, output, exec_ctx).await
-        }
-        SimulatorAction::GenerateQuery { file, output } => {
-            export::handle_export_query(file, output, exec_ctx).await
-        }
-        SimulatorAction::GenerateView { file, output } => {
-            export::handle_export_view(file, output, exec_ctx).await
+        SimulatorAction::Graph { input_dir, format } => {
+            export::handle_graph(input_dir, format, exec_ctx).await
         }
+        SimulatorAction::GenerateTypes {
+            file,
+            output,
+            watch,
+        } => export::handle_export_types(file, output, *watch, exec_ctx).await,
+        SimulatorAction::GenerateQuery {
+            file,
+            output,
+            watch,
+        } => export::handle_export_query(file, output, *watch, exec_ctx).await,
+        SimulatorAction::GenerateView {
+            file,
+            output,
+            watch,
+        } => export::handle_export_view(file, output, *watch, exec_ctx).await,
         #[cfg(feature = "tui")]
         SimulatorAction::New { output } => service::handle_new(output, exec_ctx).await,
         SimulatorAction::NewGraphql { name, output } => {
@@ -84,19 +124,43 @@ pub async fn simulator_command(
         }
         #[cfg(feature = "tui")]
         SimulatorAction::Edit { file } => service::handle_edit(file, exec_ctx).await,
-        SimulatorAction::Record { output, url } => {
-            service::handle_record(context, output, url, exec_ctx).await
-        }
+        SimulatorAction::Record {
+            output,
+            url,
+            infer_schemas,
+        } => service::handle_record(context, output, url, *infer_schemas, exec_ctx).await,
+        SimulatorAction::Proxy {
+            target,
+            port,
+            record,
+            infer_schemas,
+        } => service::handle_proxy(target, *port, record, *infer_schemas, exec_ctx).await,
         SimulatorAction::Dockerize { file, output } => {
             dockerize::handle_dockerize(file, output, exec_ctx).await
         }
         #[cfg(feature = "contract-testing")]
+        SimulatorAction::Verify { input, url } => {
+            inspect::handle_verify(input, url, exec_ctx).await
+        }
+        #[cfg(feature = "contract-testing")]
         SimulatorAction::Test {
             path,
             url,
             env,
             quiet,
-        } => inspect::handle_contract_test(path, url, env, *quiet, exec_ctx).await,
+            headers,
+        } => {
+            inspect::handle_contract_test(
+                path,
+                url,
+                env,
+                *quiet,
+                headers,
+                context.config().retry.as_ref(),
+                exec_ctx,
+            )
+            .await
+        }
     }
 }
 #[cfg(test)]
@@ -1,11 +1,13 @@
 #[cfg(feature = "tui")]
 use crate::commands::shared::{scaffold_endpoint_definition, scaffold_service_definition};
+use apicentric::simulator::recording_proxy::{ProxyRecorder, RecordingProxy};
 use apicentric::{ApicentricError, ApicentricResult, Context, ExecutionContext};
 
 pub async fn handle_record(
     context: &Context,
     output: &str,
     url: &Option<String>,
+    infer_schemas: bool,
     exec_ctx: &ExecutionContext,
 ) -> ApicentricResult<()> {
     let target = url
@@ -20,7 +22,7 @@ pub async fn handle_record(
     }
     if let Some(simulator) = context.api_simulator() {
         simulator
-            .record(&target, std::path::PathBuf::from(output))
+            .record(&target, std::path::PathBuf::from(output), infer_schemas)
             .await?;
         Ok(())
     } else {
@@ -31,6 +33,30 @@ pub async fn handle_record(
     }
 }
 
+pub async fn handle_proxy(
+    target: &str,
+    port: u16,
+    record: &str,
+    infer_schemas: bool,
+    exec_ctx: &ExecutionContext,
+) -> ApicentricResult<()> {
+    if exec_ctx.dry_run {
+        println!(
+            "🏃 Dry run: Would proxy port {} to '{}' and record into '{}'",
+            port, target, record
+        );
+        return Ok(());
+    }
+    ProxyRecorder
+        .record(
+            target,
+            std::path::PathBuf::from(record),
+            port,
+            infer_schemas,
+        )
+        .await
+}
+
 #[cfg(feature = "tui")]
 pub async fn handle_new(output: &str, exec_ctx: &ExecutionContext) -> ApicentricResult<()> {
     if exec_ctx.dry_run {
@@ -134,16 +160,17 @@ pub async fn handle_new_graphql(
         server: Some(apicentric::simulator::config::ServerConfig {
             port: Some(9001),
             base_path: "/".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(Vec::new()),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     };
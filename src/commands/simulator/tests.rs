@@ -27,6 +27,10 @@ async fn start_runs() {
             services_dir: "services".into(),
             force: false,
             template: None,
+            stdin: false,
+            json: false,
+            set: Vec::new(),
+            env: None,
         },
         &ctx,
         &exec,
@@ -82,6 +86,9 @@ async fn validate_runs() {
             file: "services".into(),
             recursive: false,
             verbose: false,
+            render: false,
+            fix: false,
+            tag: None,
         },
         &ctx,
         &exec,
@@ -135,3 +142,99 @@ async fn new_runs() {
     .await
     .unwrap();
 }
+
+#[tokio::test]
+async fn start_skips_malformed_service_files() {
+    let temp = TempDir::new().unwrap();
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    fs::write(
+        temp.path().join("good.yaml"),
+        format!(
+            "name: good\nserver:\n  port: {}\n  base_path: /\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: text/plain\n        body: pong\n",
+            port
+        ),
+    )
+    .unwrap();
+    fs::write(temp.path().join("broken.yaml"), "name: [unclosed\n").unwrap();
+
+    let mut config = apicentric::SimulatorConfig::default_config();
+    config.enabled = true;
+    config.services_dir = temp.path().to_path_buf();
+    let simulator = apicentric::ApiSimulatorManager::new(config);
+
+    let loaded = simulator.load_services_with_summary().unwrap();
+    assert_eq!(loaded.services.len(), 1);
+    let warnings = control::skipped_file_warnings(&loaded.summary.errors);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("broken.yaml"));
+
+    simulator.start().await.unwrap();
+    let status = simulator.get_status().await;
+    assert!(status.active_services.iter().any(|s| s.name == "good"));
+    simulator.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn validate_fix_adds_missing_content_type() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("svc.yaml");
+    fs::write(
+        &file,
+        "name: svc\nserver:\n  base_path: /\nendpoints:\n  - method: get\n    path: /ping\n    responses:\n      200:\n        body: '{\"ok\": true}'\n",
+    )
+    .unwrap();
+    assert!(crate::commands::shared::validate_yaml_file(&file).is_err());
+
+    let exec = ExecutionContext::new();
+    inspect::handle_validate(
+        file.to_str().unwrap(),
+        false,
+        false,
+        false,
+        true,
+        None,
+        &exec,
+    )
+    .await
+    .unwrap();
+
+    let fixed: serde_yaml::Value =
+        serde_yaml::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(fixed["endpoints"][0]["method"], "GET");
+    assert_eq!(
+        fixed["endpoints"][0]["responses"][200]["content_type"],
+        "application/json"
+    );
+    assert!(crate::commands::shared::validate_yaml_file(&file).is_ok());
+}
+
+#[tokio::test]
+async fn validate_tag_only_renders_tagged_endpoints() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("svc.yaml");
+    fs::write(
+        &file,
+        "name: svc\nserver:\n  base_path: /\nendpoints:\n  - method: GET\n    path: /stable\n    tags: [public]\n    responses:\n      200:\n        content_type: application/json\n        body: '{\"ok\": true}'\n  - method: GET\n    path: /broken\n    tags: [beta]\n    responses:\n      200:\n        content_type: application/json\n        body: '{{#if}}'\n",
+    )
+    .unwrap();
+
+    let exec = ExecutionContext::new();
+    let path = file.to_str().unwrap();
+    inspect::handle_validate(path, false, false, true, false, Some("public"), &exec)
+        .await
+        .unwrap();
+    assert!(
+        inspect::handle_validate(path, false, false, true, false, Some("beta"), &exec)
+            .await
+            .is_err()
+    );
+    assert!(
+        inspect::handle_validate(path, false, false, true, false, None, &exec)
+            .await
+            .is_err()
+    );
+}
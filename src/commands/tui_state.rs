@@ -629,6 +629,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(filter.matches(&entry));
@@ -641,6 +642,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(!filter.matches(&entry_post));
@@ -659,6 +661,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(filter.matches(&entry));
@@ -671,6 +674,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 404,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(!filter.matches(&entry_404));
@@ -689,6 +693,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(filter.matches(&entry));
@@ -701,6 +706,7 @@ mod tests {
             path: "/api/test".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(!filter.matches(&entry_other));
@@ -721,6 +727,7 @@ mod tests {
             path: "/api/users".to_string(),
             status: 201,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(filter.matches(&entry_match));
@@ -733,6 +740,7 @@ mod tests {
             path: "/api/users".to_string(),
             status: 201,
             payload: None,
+            duration_ms: None,
         };
 
         assert!(!filter.matches(&entry_wrong_method));
@@ -787,6 +795,7 @@ mod tests {
             path: "/users".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         });
 
         log_view.add_entry(RequestLogEntry {
@@ -797,6 +806,7 @@ mod tests {
             path: "/users".to_string(),
             status: 201,
             payload: None,
+            duration_ms: None,
         });
 
         log_view.add_entry(RequestLogEntry {
@@ -807,6 +817,7 @@ mod tests {
             path: "/login".to_string(),
             status: 200,
             payload: None,
+            duration_ms: None,
         });
 
         // No filter - should return all entries
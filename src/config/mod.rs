@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{ApicentricError, ApicentricResult};
+
 pub mod repository;
 pub mod validation;
 
@@ -17,6 +19,10 @@ pub struct ApicentricConfig {
     /// API Simulator configuration (the main feature)
     #[serde(default)]
     pub simulator: Option<crate::simulator::config::SimulatorConfig>,
+
+    /// Retry policy for outgoing HTTP requests made during contract tests
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
 }
 
 impl ApicentricConfig {
@@ -40,6 +46,21 @@ impl ApicentricConfig {
             }
         }
     }
+
+    /// Renders the fully-resolved configuration as pretty JSON, with secrets redacted.
+    pub fn to_effective_json(&self) -> ApicentricResult<String> {
+        let mut effective = self.clone();
+        effective.redact_sensitive_fields();
+        effective
+            .simulator
+            .get_or_insert_with(crate::simulator::config::SimulatorConfig::default_config);
+        serde_json::to_string_pretty(&effective).map_err(|e| {
+            ApicentricError::config_error(
+                format!("Failed to serialize configuration: {}", e),
+                None::<String>,
+            )
+        })
+    }
 }
 
 // ============================================================================
@@ -74,6 +95,69 @@ pub enum AiProviderKind {
     Gemini,
 }
 
+// ============================================================================
+// HTTP Client Configuration
+// ============================================================================
+
+/// Retry with exponential backoff for idempotent HTTP requests.
+///
+/// Transport errors and the configured status codes are retried; the delay
+/// doubles after each attempt up to `max_delay_ms`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub initial_delay_ms: u64,
+    /// Upper bound for a single backoff delay, in milliseconds.
+    pub max_delay_ms: u64,
+    /// Randomize each delay between half and all of its backoff value.
+    pub jitter: bool,
+    /// Response status codes that are retried.
+    pub retry_on_status: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 1000,
+            max_delay_ms: 10_000,
+            jitter: true,
+            retry_on_status: vec![502, 503, 504],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait before retry number `attempt` (starting at 1).
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        let delay = self
+            .initial_delay_ms
+            .saturating_mul(factor)
+            .min(self.max_delay_ms);
+        let delay = if self.jitter && delay > 1 {
+            use std::hash::{BuildHasher, Hasher};
+            let random = std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish();
+            delay / 2 + random % (delay - delay / 2 + 1)
+        } else {
+            delay
+        };
+        std::time::Duration::from_millis(delay)
+    }
+
+    /// Whether a response with this status should be retried.
+    pub fn retries_status(&self, status: u16) -> bool {
+        self.retry_on_status.contains(&status)
+    }
+}
+
 // ============================================================================
 // Legacy Types (for backward compatibility only)
 // ============================================================================
@@ -102,6 +186,7 @@ pub fn generate_default_config() -> ApicentricConfig {
             model: Some("gemini-2.5-flash".to_string()),
         }),
         simulator: Some(crate::simulator::config::SimulatorConfig::default()),
+        retry: None,
     }
 }
 
@@ -116,6 +201,35 @@ mod tests {
         assert!(config.simulator.is_none());
     }
 
+    #[test]
+    fn effective_json_redacts_secrets_and_fills_simulator() {
+        let json = generate_default_config().to_effective_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["ai"]["api_key"], "********");
+        assert_eq!(value["simulator"]["enabled"], false);
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            initial_delay_ms: 100,
+            max_delay_ms: 300,
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.backoff(1).as_millis(), 100);
+        assert_eq!(policy.backoff(2).as_millis(), 200);
+        assert_eq!(policy.backoff(3).as_millis(), 300);
+        assert_eq!(policy.backoff(70).as_millis(), 300);
+
+        let jittered = RetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        let delay = jittered.backoff(2).as_millis();
+        assert!((100..=200).contains(&delay));
+    }
+
     #[test]
     fn generate_default_config_works() {
         let config = generate_default_config();
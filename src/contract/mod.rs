@@ -1,9 +1,12 @@
-//! Contract testing modules: scenario extraction, execution, and reporting.
+//! Contract testing modules: scenario extraction, execution, reporting and
+//! verification of running instances.
 
 pub mod executor;
 pub mod result_reporter;
 pub mod scenario_extractor;
+pub mod verifier;
 
 pub use executor::*;
 pub use result_reporter::*;
 pub use scenario_extractor::*;
+pub use verifier::*;
@@ -1,7 +1,10 @@
-use crate::domain::contract_testing::ContractValidationResult;
+use crate::domain::contract_testing::{ComplianceIssue, ContractValidationResult, FieldDiff};
 use crate::domain::ports::contract::{ContractReportSink, ReportError, ReportFormat};
 use std::sync::Arc;
 
+/// Longest value preview shown in a diff line before it is truncated
+const PREVIEW_LEN: usize = 60;
+
 /// Reports contract validation results using a `ContractReportSink` port.
 pub struct ResultReporter<S: ContractReportSink> {
     sink: Arc<S>,
@@ -23,6 +26,45 @@ impl<S: ContractReportSink> ResultReporter<S> {
     }
 }
 
+/// Render the field differences attached to a body mismatch issue, one line
+/// per field: `<pointer>: mock=<value> real=<value>`.
+pub fn render_differences(issue: &ComplianceIssue) -> Vec<String> {
+    let diffs: Vec<FieldDiff> = issue
+        .details
+        .as_ref()
+        .and_then(|details| details.get("differences"))
+        .and_then(|diffs| serde_json::from_value(diffs.clone()).ok())
+        .unwrap_or_default();
+    diffs
+        .iter()
+        .map(|diff| {
+            format!(
+                "{}: mock={} real={}",
+                if diff.pointer.is_empty() {
+                    "/"
+                } else {
+                    &diff.pointer
+                },
+                preview(diff.mock.as_ref()),
+                preview(diff.real.as_ref())
+            )
+        })
+        .collect()
+}
+
+fn preview(value: Option<&serde_json::Value>) -> String {
+    let Some(value) = value else {
+        return "<missing>".to_string();
+    };
+    let text = value.to_string();
+    if text.chars().count() > PREVIEW_LEN {
+        let cut: String = text.chars().take(PREVIEW_LEN).collect();
+        format!("{}…", cut)
+    } else {
+        text
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +110,31 @@ mod tests {
         assert_eq!(output, Some("ok".to_string()));
         assert!(*sink.called.lock().unwrap());
     }
+
+    #[test]
+    fn renders_mock_and_real_values_for_mismatches() {
+        use crate::domain::contract_testing::{ComplianceIssueType, ComplianceSeverity};
+        use serde_json::json;
+
+        let mock = json!({"users": [{"id": 1, "name": "Ada"}, {"id": 2, "name": "Bob"}]});
+        let real = json!({"users": [{"id": 2, "name": "Bob"}, {"id": 1, "name": "Grace"}]});
+        let issue = ComplianceIssue {
+            issue_type: ComplianceIssueType::ResponseSchemaMismatch,
+            severity: ComplianceSeverity::Medium,
+            description: "Response JSON structure differs".into(),
+            scenario_path: "/users".into(),
+            details: Some(json!({
+                "differences": FieldDiff::between(&mock, &real, Some("id")),
+            })),
+        };
+
+        assert_eq!(
+            render_differences(&issue),
+            vec![r#"/users/0/name: mock="Ada" real="Grace""#.to_string()]
+        );
+
+        let positional = FieldDiff::between(&mock, &real, None);
+        assert_eq!(positional.len(), 4);
+        assert_eq!(positional[0].pointer, "/users/0/id");
+    }
 }
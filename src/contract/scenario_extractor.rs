@@ -1,5 +1,7 @@
-use crate::domain::contract_testing::ValidationScenario;
+use crate::domain::contract_testing::{HttpMethod, ResponseBody, ValidationScenario};
 use crate::domain::ports::contract::{ServiceSpec, ServiceSpecLoader, SpecLoaderError};
+use crate::simulator::config::{EndpointKind, ServiceDefinition};
+use std::collections::HashMap;
 
 /// Extracts validation scenarios from a service definition using a `ServiceSpecLoader` port.
 pub struct ScenarioExtractor<L: ServiceSpecLoader> {
@@ -22,6 +24,73 @@ impl<L: ServiceSpecLoader> ScenarioExtractor<L> {
     }
 }
 
+/// Extract one validation scenario per declared response of each HTTP endpoint
+/// in a simulator service definition.
+///
+/// Endpoints with methods the contract runner does not support are skipped.
+pub fn extract_scenarios(definition: &ServiceDefinition) -> Vec<ValidationScenario> {
+    let mut scenarios = Vec::new();
+    for (index, endpoint) in definition
+        .endpoints
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        if endpoint.kind != EndpointKind::Http {
+            continue;
+        }
+        let Some(method) = parse_method(&endpoint.method) else {
+            continue;
+        };
+
+        let mut statuses: Vec<&u16> = endpoint.responses.keys().collect();
+        statuses.sort();
+        for status in statuses {
+            let response = &endpoint.responses[status];
+            let id = format!(
+                "{}_{}_endpoint_{}_{}",
+                definition.name,
+                endpoint.method.to_lowercase(),
+                index,
+                status
+            );
+            let mut headers = HashMap::new();
+            headers.insert("Accept".to_string(), response.content_type.clone());
+            let expected_headers = response
+                .headers
+                .iter()
+                .flatten()
+                .map(|(key, value)| (key.to_lowercase(), value.clone()))
+                .collect();
+            let expected_body = match serde_json::from_str(response.body.trim()) {
+                Ok(json) => ResponseBody::Json(json),
+                Err(_) => ResponseBody::Text(response.body.clone()),
+            };
+
+            scenarios.push(
+                ValidationScenario::new(id, endpoint.path.clone(), method.clone())
+                    .with_headers(headers)
+                    .with_expected_response(*status, expected_headers, expected_body),
+            );
+        }
+    }
+    scenarios
+}
+
+fn parse_method(method: &str) -> Option<HttpMethod> {
+    match method.to_uppercase().as_str() {
+        "GET" => Some(HttpMethod::GET),
+        "POST" => Some(HttpMethod::POST),
+        "PUT" => Some(HttpMethod::PUT),
+        "DELETE" => Some(HttpMethod::DELETE),
+        "PATCH" => Some(HttpMethod::PATCH),
+        "HEAD" => Some(HttpMethod::HEAD),
+        "OPTIONS" => Some(HttpMethod::OPTIONS),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +146,50 @@ mod tests {
         assert_eq!(scenarios.len(), 1);
         assert_eq!(scenarios[0].path(), "/ping");
     }
+
+    #[test]
+    fn extracts_one_scenario_per_response_from_definition() {
+        let definition: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: users
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users/{id}
+    responses:
+      200:
+        content_type: application/json
+        body: '{"id": 1}'
+      404:
+        content_type: application/json
+        body: '{"error": "not found"}'
+  - method: POST
+    path: /users
+    responses:
+      201:
+        content_type: text/plain
+        body: created
+        headers:
+          Location: /users/1
+"#,
+        )
+        .unwrap();
+
+        let scenarios = extract_scenarios(&definition);
+        assert_eq!(scenarios.len(), 3);
+        assert_eq!(scenarios[0].path(), "/users/{id}");
+        assert_eq!(scenarios[0].expected_status(), Some(200));
+        assert!(matches!(
+            scenarios[0].expected_body(),
+            Some(ResponseBody::Json(body)) if body["id"] == 1
+        ));
+        assert_eq!(scenarios[1].expected_status(), Some(404));
+        assert_eq!(scenarios[2].method().to_string(), "POST");
+        assert_eq!(scenarios[2].expected_headers()["location"], "/users/1");
+        assert!(matches!(
+            scenarios[2].expected_body(),
+            Some(ResponseBody::Text(body)) if body == "created"
+        ));
+    }
 }
@@ -0,0 +1,233 @@
+use crate::domain::contract_testing::{
+    ApiResponse, ApiUrl, CompatibilityPolicy, ComplianceIssue, ComplianceIssueType,
+    ComplianceSeverity, Contract, ContractId, RealApiConfig, ResponseBody, RetryAttempts,
+    ScenarioValidationResult, ValidationScenario,
+};
+use crate::domain::ports::contract::ContractHttpClient;
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::{ParameterDefinition, ParameterLocation, ServiceDefinition};
+use std::time::Instant;
+
+use super::extract_scenarios;
+
+/// Value substituted for `{param}` segments when requesting an endpoint,
+/// unless the parameter's type calls for another
+const PATH_PARAM_PLACEHOLDER: &str = "1";
+
+/// Sample substituted for `{param:uuid}` segments
+const UUID_PLACEHOLDER: &str = "00000000-0000-4000-8000-000000000001";
+
+/// Checks that a running instance still answers the way its service
+/// definition says it does.
+pub struct InstanceVerifier<H: ContractHttpClient> {
+    http_client: H,
+}
+
+impl<H: ContractHttpClient> InstanceVerifier<H> {
+    /// Create a new verifier sending requests through the given client.
+    pub fn new(http_client: H) -> Self {
+        Self { http_client }
+    }
+
+    /// Request every HTTP endpoint of `definition` once from `base_url` and
+    /// compare the answer with the declared response for the returned status.
+    ///
+    /// Statuses the endpoint does not declare are reported as drift. Bodies
+    /// are compared by shape; templated bodies are only checked for status.
+    pub async fn verify(
+        &self,
+        definition: &ServiceDefinition,
+        base_url: &str,
+    ) -> ApicentricResult<Vec<ScenarioValidationResult>> {
+        let base_path = definition
+            .server
+            .as_ref()
+            .map(|server| server.base_path.trim_end_matches('/'))
+            .unwrap_or_default();
+        let url = ApiUrl::new(format!("{}{}", base_url.trim_end_matches('/'), base_path)).map_err(
+            |e| ApicentricError::validation_error(e.to_string(), Some("--url"), None::<&str>),
+        )?;
+        let config = RealApiConfig::new(
+            "verify".to_string(),
+            url.clone(),
+            None,
+            RetryAttempts::new(0).expect("zero retries are valid"),
+        );
+        let contract = Contract::new(
+            ContractId::new(definition.name.clone()).map_err(verify_error)?,
+            definition.name.clone(),
+            definition.name.clone(),
+            None,
+        )
+        .map_err(verify_error)?;
+        let policy = CompatibilityPolicy {
+            validate_headers: false,
+            ..CompatibilityPolicy::strict()
+        };
+
+        let mut endpoints: Vec<Vec<ValidationScenario>> = Vec::new();
+        for scenario in extract_scenarios(definition) {
+            match endpoints.last_mut() {
+                Some(group)
+                    if group[0].path == scenario.path && group[0].method == scenario.method =>
+                {
+                    group.push(scenario)
+                }
+                _ => endpoints.push(vec![scenario]),
+            }
+        }
+
+        let mut results = Vec::with_capacity(endpoints.len());
+        for declared in endpoints {
+            let mut request = declared[0].clone();
+            let parameters = definition
+                .endpoints
+                .iter()
+                .flatten()
+                .find(|endpoint| endpoint.path == request.path)
+                .and_then(|endpoint| endpoint.parameters.as_deref())
+                .unwrap_or_default();
+            request.path = resolve_path_params(&request.path, parameters);
+            let label = format!("{} {}", request.method, declared[0].path);
+            let start = Instant::now();
+
+            let (real_response, compliance_issue, expected_response) = match self
+                .http_client
+                .execute_request(&url, &config, &request)
+                .await
+            {
+                Ok(real) => match declared
+                    .iter()
+                    .find(|s| s.expected_status == Some(real.status()))
+                {
+                    Some(scenario) => {
+                        let expected = expected_response(scenario, &real);
+                        let issue = contract
+                            .validate_response_compatibility(&expected, &real, &policy)
+                            .map(|mut issue| {
+                                issue.scenario_path = label.clone();
+                                issue
+                            });
+                        (Some(real), issue, Some(expected))
+                    }
+                    None => {
+                        let declared_statuses: Vec<u16> =
+                            declared.iter().filter_map(|s| s.expected_status).collect();
+                        let issue = ComplianceIssue {
+                            issue_type: ComplianceIssueType::StatusCodeMismatch,
+                            severity: ComplianceSeverity::High,
+                            description: format!(
+                                "Status code mismatch: expected one of {:?} but instance returned {}",
+                                declared_statuses,
+                                real.status()
+                            ),
+                            scenario_path: label,
+                            details: None,
+                        };
+                        (Some(real), Some(issue), None)
+                    }
+                },
+                Err(e) => {
+                    let issue = ComplianceIssue {
+                        issue_type: ComplianceIssueType::UnexpectedError,
+                        severity: ComplianceSeverity::Critical,
+                        description: format!("Request failed: {}", e),
+                        scenario_path: label,
+                        details: None,
+                    };
+                    (None, Some(issue), None)
+                }
+            };
+
+            results.push(ScenarioValidationResult {
+                scenario_id: request.id,
+                mock_response: None,
+                real_response,
+                expected_response,
+                compliance_issue,
+                duration_ms: start.elapsed().as_millis() as u64,
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// The declared response to compare against. A templated body cannot be
+/// compared before rendering, so the instance's own body stands in for it.
+fn expected_response(scenario: &ValidationScenario, real: &ApiResponse) -> ApiResponse {
+    let body = match &scenario.expected_body {
+        Some(ResponseBody::Text(text)) if text.contains("{{") => real.body().clone(),
+        Some(body) => body.clone(),
+        None => real.body().clone(),
+    };
+    ApiResponse::new(real.status(), scenario.expected_headers.clone(), body, 0)
+}
+
+/// Fill each `{param}` segment with a value its type accepts: the inline
+/// `{name:type}` type first, then the type declared in `parameters`
+fn resolve_path_params(path: &str, parameters: &[ParameterDefinition]) -> String {
+    path.split('/')
+        .map(|segment| {
+            let Some(param) = segment
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+            else {
+                return segment;
+            };
+            let (name, inline_type) = match param.trim_start_matches('*').split_once(':') {
+                Some((name, param_type)) => (name, Some(param_type)),
+                None => (param.trim_start_matches('*'), None),
+            };
+            let declared_type = || {
+                parameters
+                    .iter()
+                    .find(|p| p.name == name && matches!(p.location, ParameterLocation::Path))
+                    .map(|p| p.param_type.as_str())
+            };
+            match inline_type
+                .or_else(declared_type)
+                .map(str::to_ascii_lowercase)
+                .as_deref()
+            {
+                Some("uuid") => UUID_PLACEHOLDER,
+                Some("boolean" | "bool") => "true",
+                _ => PATH_PARAM_PLACEHOLDER,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn verify_error(e: impl std::fmt::Display) -> ApicentricError {
+    ApicentricError::validation_error(e.to_string(), None::<&str>, None::<&str>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_path_parameters() {
+        assert_eq!(
+            resolve_path_params("/users/{id}/posts/{post_id}", &[]),
+            "/users/1/posts/1"
+        );
+        assert_eq!(resolve_path_params("/health", &[]), "/health");
+    }
+
+    #[test]
+    fn path_parameter_samples_fit_their_types() {
+        assert_eq!(
+            resolve_path_params("/orders/{id:uuid}/items/{n:int}", &[]),
+            format!("/orders/{}/items/1", UUID_PLACEHOLDER)
+        );
+
+        let declared: Vec<ParameterDefinition> =
+            serde_yaml::from_str("- name: active\n  in: path\n  type: boolean\n  required: true\n")
+                .unwrap();
+        assert_eq!(
+            resolve_path_params("/flags/{active}", &declared),
+            "/flags/true"
+        );
+    }
+}
@@ -61,6 +61,9 @@ pub struct CompatibilityPolicy {
     pub ignore_additional_headers: bool,
     pub compare_body: bool,
     pub tolerance_threshold: f64,
+    /// Field used to pair array elements when diffing bodies instead of position
+    #[serde(default)]
+    pub array_key: Option<String>,
 }
 
 impl CompatibilityPolicy {
@@ -73,6 +76,7 @@ impl CompatibilityPolicy {
             ignore_additional_headers: false,
             compare_body: true,
             tolerance_threshold: 0.95,
+            array_key: None,
         }
     }
 
@@ -85,6 +89,7 @@ impl CompatibilityPolicy {
             ignore_additional_headers: true,
             compare_body: true,
             tolerance_threshold: 0.80,
+            array_key: None,
         }
     }
 
@@ -97,9 +102,15 @@ impl CompatibilityPolicy {
             ignore_additional_headers: true,
             compare_body: false,
             tolerance_threshold: 0.60,
+            array_key: None,
         }
     }
 
+    pub fn with_array_key(mut self, key: String) -> Self {
+        self.array_key = Some(key);
+        self
+    }
+
     pub fn is_compatible(&self, issues: &[ComplianceIssue]) -> bool {
         let critical_issues = issues
             .iter()
@@ -5,8 +5,8 @@ use std::time::SystemTime;
 
 use super::config::CompatibilityPolicy;
 use super::value_objects::{
-    ComplianceIssueType, ComplianceSeverity, ContractId, ContractValidationError, HttpMethod,
-    RequestBody, ResponseBody,
+    ComplianceIssueType, ComplianceSeverity, ContractId, ContractValidationError, FieldDiff,
+    HttpMethod, RequestBody, ResponseBody,
 };
 
 /// Validation scenario for testing specific API endpoints
@@ -344,7 +344,12 @@ impl Contract {
                             scenario_path: String::new(),
                             details: Some(serde_json::json!({
                                 "expected_structure": self.json_structure_summary(expected_json),
-                                "real_structure": self.json_structure_summary(real_json)
+                                "real_structure": self.json_structure_summary(real_json),
+                                "differences": FieldDiff::between(
+                                    expected_json,
+                                    real_json,
+                                    policy.array_key.as_deref(),
+                                ),
                             })),
                         });
                     }
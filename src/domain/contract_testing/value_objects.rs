@@ -143,6 +143,113 @@ pub enum ResponseBody {
     Text(String),
 }
 
+/// A field whose value differs between the mock and the real response body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    /// JSON Pointer (RFC 6901) to the field
+    pub pointer: String,
+    /// Value in the mock response; `None` when only the real API returns it
+    pub mock: Option<serde_json::Value>,
+    /// Value in the real response; `None` when the real API omits it
+    pub real: Option<serde_json::Value>,
+}
+
+impl FieldDiff {
+    /// Collect every differing leaf between two JSON documents.
+    ///
+    /// Arrays are compared by position unless `array_key` is set, in which case
+    /// object elements are paired by that field and pointers use the mock index.
+    pub fn between(
+        mock: &serde_json::Value,
+        real: &serde_json::Value,
+        array_key: Option<&str>,
+    ) -> Vec<FieldDiff> {
+        let mut diffs = Vec::new();
+        Self::collect(String::new(), Some(mock), Some(real), array_key, &mut diffs);
+        diffs
+    }
+
+    fn collect(
+        pointer: String,
+        mock: Option<&serde_json::Value>,
+        real: Option<&serde_json::Value>,
+        array_key: Option<&str>,
+        diffs: &mut Vec<FieldDiff>,
+    ) {
+        use serde_json::Value;
+
+        let child = |segment: &str| {
+            format!(
+                "{}/{}",
+                pointer,
+                segment.replace('~', "~0").replace('/', "~1")
+            )
+        };
+        match (mock, real) {
+            (Some(Value::Object(mock_obj)), Some(Value::Object(real_obj))) => {
+                for (key, mock_value) in mock_obj {
+                    Self::collect(
+                        child(key),
+                        Some(mock_value),
+                        real_obj.get(key),
+                        array_key,
+                        diffs,
+                    );
+                }
+                for (key, real_value) in real_obj {
+                    if !mock_obj.contains_key(key) {
+                        Self::collect(child(key), None, Some(real_value), array_key, diffs);
+                    }
+                }
+            }
+            (Some(Value::Array(mock_arr)), Some(Value::Array(real_arr))) => {
+                let key_of = |item: &Value| array_key.and_then(|k| item.get(k)).cloned();
+                if array_key.is_some()
+                    && mock_arr.iter().chain(real_arr).all(|i| key_of(i).is_some())
+                {
+                    for (index, mock_item) in mock_arr.iter().enumerate() {
+                        let real_item = real_arr.iter().find(|r| key_of(r) == key_of(mock_item));
+                        Self::collect(
+                            child(&index.to_string()),
+                            Some(mock_item),
+                            real_item,
+                            array_key,
+                            diffs,
+                        );
+                    }
+                    for (index, real_item) in real_arr.iter().enumerate() {
+                        if !mock_arr.iter().any(|m| key_of(m) == key_of(real_item)) {
+                            Self::collect(
+                                child(&index.to_string()),
+                                None,
+                                Some(real_item),
+                                array_key,
+                                diffs,
+                            );
+                        }
+                    }
+                } else {
+                    for index in 0..mock_arr.len().max(real_arr.len()) {
+                        Self::collect(
+                            child(&index.to_string()),
+                            mock_arr.get(index),
+                            real_arr.get(index),
+                            array_key,
+                            diffs,
+                        );
+                    }
+                }
+            }
+            (mock, real) if mock != real => diffs.push(FieldDiff {
+                pointer,
+                mock: mock.cloned(),
+                real: real.cloned(),
+            }),
+            _ => {}
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RequestBody {
     Json(serde_json::Value),
@@ -5,6 +5,7 @@
 //! with contextual information and actionable suggestions for common issues.
 
 use std::fmt;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// The main error type for all Apicentric operations.
@@ -138,6 +139,10 @@ pub enum ApicentricError {
         suggestion: Option<String>,
     },
 
+    /// A simulator start/stop/load failure that callers can match on.
+    #[error(transparent)]
+    Simulator(#[from] SimulatorError),
+
     /// An I/O error.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -318,6 +323,7 @@ impl ApicentricError {
             | Self::Scripting { suggestion, .. }
             | Self::Csv { suggestion, .. }
             | Self::Data { suggestion, .. } => suggestion.as_deref(),
+            Self::Simulator(err) => Some(err.suggestion()),
             #[cfg(feature = "iot")]
             Self::Mqtt { suggestion, .. } | Self::Modbus { suggestion, .. } => {
                 suggestion.as_deref()
@@ -338,6 +344,7 @@ impl ApicentricError {
     pub fn service_name(&self) -> Option<&str> {
         match self {
             Self::Service { service_name, .. } => service_name.as_deref(),
+            Self::Simulator(SimulatorError::ServiceNotFound(name)) => Some(name),
             _ => None,
         }
     }
@@ -487,6 +494,36 @@ impl From<csv::Error> for ApicentricError {
     }
 }
 
+/// Failures of the simulator's start, stop and load operations, carried by
+/// `ApicentricError::Simulator` so embedders can match instead of parsing messages.
+#[derive(Debug, Error)]
+pub enum SimulatorError {
+    /// A service could not listen because its port is taken.
+    #[error("Port {0} is already in use")]
+    PortInUse(u16),
+
+    /// No registered service has this name.
+    #[error("Service '{0}' not found")]
+    ServiceNotFound(String),
+
+    /// A service definition file could not be parsed.
+    #[error("Invalid service definition in {}: {reason}", file.display())]
+    InvalidDefinition { file: PathBuf, reason: String },
+}
+
+impl SimulatorError {
+    /// A suggestion for resolving the error.
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::PortInUse(_) => "Stop the process using the port or choose another one",
+            Self::ServiceNotFound(_) => "Check that the service is registered",
+            Self::InvalidDefinition { .. } => {
+                "Check YAML syntax and ensure all required fields are present"
+            }
+        }
+    }
+}
+
 /// A `Result` type alias for Apicentric operations.
 pub type ApicentricResult<T> = Result<T, ApicentricError>;
 
@@ -96,7 +96,7 @@ pub use config::ApicentricConfig;
 #[cfg(not(target_arch = "wasm32"))]
 pub use context::{Context, ContextBuilder, ExecutionContext};
 #[cfg(not(target_arch = "wasm32"))]
-pub use errors::{ApicentricError, ApicentricResult};
+pub use errors::{ApicentricError, ApicentricResult, SimulatorError};
 #[cfg(not(target_arch = "wasm32"))]
 pub use simulator::{ApiSimulatorManager, ServiceDefinition, SimulatorConfig};
 
@@ -120,6 +120,9 @@ pub mod mock {
     pub use crate::adapters::mock_server::{load_spec, run_mock_server, MockApiSpec};
 }
 
+// Browser-embeddable mock handler (wasm-safe subset of the simulator)
+pub mod wasm;
+
 /// A simple example function exposed to WebAssembly consumers.
 ///
 /// # Arguments
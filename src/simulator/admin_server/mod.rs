@@ -1,4 +1,5 @@
 //! Admin server for the API simulator.
+use crate::errors::ApicentricError;
 use crate::simulator::registry::ServiceRegistry;
 use bytes::Bytes;
 use http_body_util::Full;
@@ -145,6 +146,35 @@ async fn handle_admin_request(
                 }
             }
         }
+        (&hyper::Method::POST, "/apicentric-admin/reset") => {
+            service_registry.read().await.reset_all().await;
+            let mut no_content = Response::new(Full::new(Bytes::new()));
+            *no_content.status_mut() = StatusCode::NO_CONTENT;
+            no_content
+        }
+        (&hyper::Method::GET, path) if history_service_name(path).is_some() => {
+            let name = history_service_name(path).unwrap_or_default();
+            let service = {
+                let registry = service_registry.read().await;
+                registry.get_service(name).cloned()
+            };
+            let Some(service) = service else {
+                let mut not_found = Response::new(Full::new(Bytes::from("Service not found")));
+                *not_found.status_mut() = StatusCode::NOT_FOUND;
+                return not_found;
+            };
+            let history = service.read().await.history();
+            match history.and_then(|versions| {
+                serde_json::to_string(&versions).map_err(ApicentricError::from)
+            }) {
+                Ok(body) => Response::new(Full::new(Bytes::from(body))),
+                Err(_) => {
+                    let mut error = Response::new(Full::new(Bytes::from("Internal Server Error")));
+                    *error.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                    error
+                }
+            }
+        }
         _ => {
             let mut not_found = Response::new(Full::new(Bytes::from("Not Found")));
             *not_found.status_mut() = StatusCode::NOT_FOUND;
@@ -152,3 +182,10 @@ async fn handle_admin_request(
         }
     }
 }
+
+/// Service named by a `/apicentric-admin/services/{name}/history` path
+fn history_service_name(path: &str) -> Option<&str> {
+    path.strip_prefix("/apicentric-admin/services/")?
+        .strip_suffix("/history")
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+}
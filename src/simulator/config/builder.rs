@@ -0,0 +1,230 @@
+use super::{
+    BehaviorConfig, EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
+};
+use std::collections::HashMap;
+
+impl ServiceDefinition {
+    /// Start a fluent [`ServiceDefinitionBuilder`] for a service with the given name.
+    pub fn builder(name: impl Into<String>) -> ServiceDefinitionBuilder {
+        ServiceDefinitionBuilder::new(name)
+    }
+}
+
+/// A builder for creating `ServiceDefinition` instances without YAML.
+pub struct ServiceDefinitionBuilder {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    port: Option<u16>,
+    base_path: String,
+    fixtures: HashMap<String, serde_json::Value>,
+    endpoints: Vec<EndpointDefinition>,
+    behavior: Option<BehaviorConfig>,
+}
+
+impl ServiceDefinitionBuilder {
+    /// Creates a new builder for a service served under `/`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            description: None,
+            port: None,
+            base_path: "/".to_string(),
+            fixtures: HashMap::new(),
+            endpoints: Vec::new(),
+            behavior: None,
+        }
+    }
+
+    /// Sets the service version.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the service description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the port the service listens on.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Sets the path prefix every endpoint is served under.
+    pub fn base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = base_path.into();
+        self
+    }
+
+    /// Adds a fixture available to templates as `fixtures.<key>`.
+    pub fn fixture(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.fixtures.insert(key.into(), value);
+        self
+    }
+
+    /// Adds an endpoint.
+    pub fn endpoint(mut self, endpoint: impl Into<EndpointDefinition>) -> Self {
+        self.endpoints.push(endpoint.into());
+        self
+    }
+
+    /// Sets the service-wide behavior (latency, error simulation, ...).
+    pub fn behavior(mut self, behavior: BehaviorConfig) -> Self {
+        self.behavior = Some(behavior);
+        self
+    }
+
+    /// Builds the `ServiceDefinition`.
+    pub fn build(self) -> ServiceDefinition {
+        ServiceDefinition {
+            name: self.name,
+            version: self.version,
+            description: self.description,
+            server: Some(ServerConfig {
+                port: self.port,
+                base_path: self.base_path,
+                ..Default::default()
+            }),
+            models: None,
+            fixtures: (!self.fixtures.is_empty()).then_some(self.fixtures),
+            fixture_sort: None,
+            bucket: None,
+            partials: None,
+            endpoints: Some(self.endpoints),
+            graphql: None,
+            behavior: self.behavior,
+            state_machine: None,
+            #[cfg(feature = "iot")]
+            twin: None,
+        }
+    }
+}
+
+/// A builder for creating HTTP `EndpointDefinition` instances.
+pub struct EndpointBuilder {
+    method: String,
+    path: String,
+    description: Option<String>,
+    responses: HashMap<u16, ResponseDefinition>,
+    requires: Option<Vec<String>>,
+    tags: Vec<String>,
+}
+
+impl EndpointBuilder {
+    /// Creates a new builder for `method` requests to `path`.
+    pub fn new(method: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            method: method.into().to_uppercase(),
+            path: path.into(),
+            description: None,
+            responses: HashMap::new(),
+            requires: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates a builder for a `GET` endpoint.
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new("GET", path)
+    }
+
+    /// Creates a builder for a `POST` endpoint.
+    pub fn post(path: impl Into<String>) -> Self {
+        Self::new("POST", path)
+    }
+
+    /// Creates a builder for a `PUT` endpoint.
+    pub fn put(path: impl Into<String>) -> Self {
+        Self::new("PUT", path)
+    }
+
+    /// Creates a builder for a `PATCH` endpoint.
+    pub fn patch(path: impl Into<String>) -> Self {
+        Self::new("PATCH", path)
+    }
+
+    /// Creates a builder for a `DELETE` endpoint.
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::new("DELETE", path)
+    }
+
+    /// Sets the endpoint description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds a templated response with the given content type.
+    pub fn response(
+        mut self,
+        status: u16,
+        content_type: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        let response = ResponseDefinition {
+            condition: None,
+            content_type: content_type.into(),
+            body: body.into(),
+            schema: None,
+            script: None,
+            headers: None,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
+            side_effects: None,
+            body_base64: None,
+            body_file: None,
+        };
+        self.responses.insert(status, response);
+        self
+    }
+
+    /// Adds a templated `application/json` response.
+    pub fn json_response(self, status: u16, body: impl Into<String>) -> Self {
+        self.response(status, "application/json", body)
+    }
+
+    /// Requires bearer tokens carrying these scopes (see `server.auth`).
+    pub fn requires<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.requires = Some(scopes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds a tag used to group endpoints in commands.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Builds the `EndpointDefinition`.
+    pub fn build(self) -> EndpointDefinition {
+        EndpointDefinition {
+            method: self.method,
+            path: self.path,
+            description: self.description,
+            responses: self.responses,
+            requires: self.requires,
+            tags: (!self.tags.is_empty()).then_some(self.tags),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<EndpointBuilder> for EndpointDefinition {
+    fn from(builder: EndpointBuilder) -> Self {
+        builder.build()
+    }
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Types a `{name:type}` path segment may constrain itself to
+pub const PATH_PARAM_TYPES: &[&str] = &["int", "uuid", "string"];
+
 /// Type of endpoint supported by the simulator
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -29,6 +32,10 @@ pub struct StreamConfig {
     /// Periodic message configuration
     #[serde(default)]
     pub periodic: Option<PeriodicMessage>,
+    /// Reply rendered for each message a WebSocket client sends; the message
+    /// is available as `request.body`, parsed as JSON when possible
+    #[serde(default)]
+    pub on_message: Option<String>,
 }
 
 /// Configuration for a periodic message
@@ -41,7 +48,7 @@ pub struct PeriodicMessage {
 }
 
 /// Endpoint definition
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct EndpointDefinition {
     /// Type of endpoint (HTTP/WebSocket/SSE)
     #[serde(default)]
@@ -51,11 +58,17 @@ pub struct EndpointDefinition {
     /// Optional headers that must match for this endpoint to trigger
     #[serde(default)]
     pub header_match: Option<HashMap<String, String>>,
+    /// Optional query parameters that must match; endpoints declaring them
+    /// win over endpoints with the same path that do not
+    #[serde(default)]
+    pub query_match: Option<HashMap<String, String>>,
     pub description: Option<String>,
     #[serde(default)]
     pub parameters: Option<Vec<ParameterDefinition>>,
     #[serde(default)]
     pub request_body: Option<RequestBodyDefinition>,
+    /// Responses by status code; event stream endpoints may leave this empty
+    #[serde(default)]
     pub responses: HashMap<u16, ResponseDefinition>,
     /// Optional scenario-based responses with matching conditions
     #[serde(default)]
@@ -63,6 +76,105 @@ pub struct EndpointDefinition {
     /// Streaming configuration for WebSocket/SSE endpoints
     #[serde(default)]
     pub stream: Option<StreamConfig>,
+    /// Behavior overriding the service and global settings for this endpoint
+    #[serde(default)]
+    pub behavior: Option<super::BehaviorConfig>,
+    /// Template rendering the current resource; its hash is sent as `ETag`
+    /// and requests with a stale `If-Match` get 412 Precondition Failed
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// Where to send requests when the mock has no data to serve
+    #[serde(default)]
+    pub fallback: Option<EndpointFallback>,
+    /// JMESPath expression applied to proxied JSON responses; overrides the
+    /// service `proxy_response_transform`
+    #[serde(default)]
+    pub proxy_response_transform: Option<String>,
+    /// Scopes a bearer token must carry; missing or invalid tokens get 401
+    /// and tokens lacking a scope get 403
+    #[serde(default)]
+    pub requires: Option<Vec<String>>,
+    /// Labels for grouping endpoints (e.g. `public`, `beta`) in commands
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// State machine transition applied when the endpoint is hit
+    #[serde(default)]
+    pub transition: Option<StateTransition>,
+}
+
+/// Move the service state machine; hits from any other state get 409 Conflict
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StateTransition {
+    /// States the transition may start from; empty allows any state
+    #[serde(default)]
+    pub from: Vec<String>,
+    pub to: String,
+}
+
+impl EndpointDefinition {
+    /// Whether the endpoint is labelled with `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().flatten().any(|t| t == tag)
+    }
+
+    /// Whether the endpoint is served as Server-Sent Events, either declared
+    /// with `kind: sse` or by giving a plain endpoint a `stream` block
+    pub fn is_event_stream(&self) -> bool {
+        match self.kind {
+            EndpointKind::Sse => true,
+            EndpointKind::Http => self.stream.is_some(),
+            EndpointKind::WebSocket => false,
+        }
+    }
+
+    /// Problems with the `{...}` segments of the path
+    pub fn path_errors(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        // A catch-all swallows everything after it, so it must come last
+        let segments: Vec<&str> = self.path.trim_end_matches('/').split('/').collect();
+        if let Some(position) = segments.iter().position(|s| s.starts_with("{*")) {
+            if position + 1 != segments.len() {
+                errors.push(ValidationError {
+                    field: "path".to_string(),
+                    message: format!(
+                        "Wildcard segment '{}' must be the last segment",
+                        segments[position]
+                    ),
+                    suggestion: Some("Move the {*name} segment to the end of the path".to_string()),
+                });
+            }
+        }
+        for segment in &segments {
+            let Some((_, param_type)) = segment
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .and_then(|param| param.split_once(':'))
+            else {
+                continue;
+            };
+            if !PATH_PARAM_TYPES.contains(&param_type) {
+                errors.push(ValidationError {
+                    field: "path".to_string(),
+                    message: format!(
+                        "Unknown type '{}' in path segment '{}'. Must be one of: {}",
+                        param_type,
+                        segment,
+                        PATH_PARAM_TYPES.join(", ")
+                    ),
+                    suggestion: Some("Use a supported type or drop the ':type' suffix".to_string()),
+                });
+            }
+        }
+        errors
+    }
+}
+
+/// Fallback used by an endpoint whose rendered body is empty
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EndpointFallback {
+    /// Forward to the service's `proxy_base_url` until the mock is populated
+    Proxy,
 }
 
 /// Parameter definition for endpoints
@@ -77,6 +189,18 @@ pub struct ParameterDefinition {
     pub description: Option<String>,
 }
 
+impl ParameterDefinition {
+    /// Check whether a raw string value can be read as the declared type
+    pub fn accepts(&self, value: &str) -> bool {
+        match self.param_type.to_ascii_lowercase().as_str() {
+            "integer" | "int" => value.parse::<i64>().is_ok(),
+            "number" => value.parse::<f64>().is_ok(),
+            "boolean" | "bool" => value.parse::<bool>().is_ok(),
+            _ => true,
+        }
+    }
+}
+
 /// Parameter location (path, query, header)
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -93,6 +217,9 @@ pub struct RequestBodyDefinition {
     pub required: bool,
     pub schema: Option<String>, // Reference to model name
     pub content_type: Option<String>,
+    /// Reject bodies that don't conform to the `schema` model with a 422
+    #[serde(default)]
+    pub validate: bool,
 }
 
 /// Response definition with templating support
@@ -100,23 +227,140 @@ pub struct RequestBodyDefinition {
 pub struct ResponseDefinition {
     pub condition: Option<String>, // Template condition for conditional responses
     pub content_type: String,
-    pub body: String, // Template string
+    /// Template string; a structured YAML value is stored as its JSON text
+    #[serde(default, deserialize_with = "deserialize_body")]
+    pub body: String,
+    /// Raw bytes sent verbatim instead of `body`, e.g. images or protobuf
+    #[serde(default)]
+    pub body_base64: Option<String>,
+    /// Read the body from a file at load time; `.gz` files are decompressed
+    #[serde(default)]
+    pub body_file: Option<PathBuf>,
     #[serde(default)]
     pub schema: Option<String>, // Reference to model name
+    /// Rhai script run per request; returning `#{ status, headers, body }`
+    /// answers with that response instead of this definition
     #[serde(default)]
     pub script: Option<PathBuf>,
-    #[serde(default)]
+    /// Response headers; a list value (e.g. several `Set-Cookie`) is stored
+    /// one value per line and sent as repeated headers
+    #[serde(
+        default,
+        deserialize_with = "deserialize_headers",
+        serialize_with = "serialize_headers"
+    )]
     pub headers: Option<HashMap<String, String>>,
+    /// HTTP trailers sent after a chunked body (templated like headers)
+    #[serde(default)]
+    pub trailers: Option<HashMap<String, String>>,
+    /// Named alternative bodies, selectable with the `__example` query parameter
+    #[serde(default)]
+    pub examples: Option<HashMap<String, String>>,
+    /// Delay between records when an array body is streamed as NDJSON,
+    /// json-seq or multipart/mixed
+    #[serde(default)]
+    pub record_delay_ms: Option<u64>,
+    /// Re-indent a JSON body; overrides the service `pretty_json` setting
+    #[serde(default)]
+    pub pretty_json: Option<bool>,
+    /// Prune a JSON body to the fieldsets requested with `fields[<type>]=a,b`
+    #[serde(default)]
+    pub sparse_fields: bool,
+    /// Serve this response only for the first N matching requests, ahead of
+    /// other unconditioned responses; afterwards the endpoint behaves as if
+    /// it were absent
+    #[serde(default)]
+    pub serve_times: Option<usize>,
+    /// Program and arguments run with the request as JSON on stdin; stdout
+    /// is sent verbatim as the body. Needs the service's `allow_exec` and
+    /// the program listed in `exec_allowlist`
+    #[serde(default)]
+    pub exec: Option<Vec<String>>,
     #[serde(default)]
     pub side_effects: Option<Vec<SideEffect>>,
 }
 
+/// A response body written either as a (template) string or as structured YAML.
+/// Structured bodies are kept as YAML values so mappings keep their key order.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BodyValue {
+    Text(String),
+    Structured(serde_yaml::Value),
+}
+
+fn deserialize_body<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match BodyValue::deserialize(deserializer)? {
+        BodyValue::Text(text) => Ok(text),
+        BodyValue::Structured(value) => {
+            serde_json::to_string(&value).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// A response header written either as one value or as a list of values
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HeaderValues {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn deserialize_headers<'de, D>(deserializer: D) -> Result<Option<HashMap<String, String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let headers = Option::<HashMap<String, HeaderValues>>::deserialize(deserializer)?;
+    Ok(headers.map(|headers| {
+        headers
+            .into_iter()
+            .map(|(name, values)| match values {
+                HeaderValues::One(value) => (name, value),
+                HeaderValues::Many(values) => (name, values.join("\n")),
+            })
+            .collect()
+    }))
+}
+
+fn serialize_headers<S>(
+    headers: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeMap;
+
+    let Some(headers) = headers else {
+        return serializer.serialize_none();
+    };
+    let mut map = serializer.serialize_map(Some(headers.len()))?;
+    for (name, value) in headers {
+        if value.contains('\n') {
+            map.serialize_entry(name, &value.lines().collect::<Vec<_>>())?;
+        } else {
+            map.serialize_entry(name, value)?;
+        }
+    }
+    map.end()
+}
+
 /// Side effects that can be triggered by responses
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SideEffect {
     pub action: String,
     pub target: String,
+    #[serde(default)]
     pub value: String, // Template string
+    /// Path parameter identifying the fixture array item for the `*_by_param` actions
+    #[serde(default)]
+    pub param: Option<String>,
+    /// Item field compared with the path parameter; defaults to the parameter name
+    #[serde(default)]
+    pub field: Option<String>,
 }
 
 /// Scenario definition for conditional responses
@@ -132,6 +376,12 @@ pub struct ScenarioDefinition {
     /// Strategy for selecting this scenario when multiple are available
     #[serde(default)]
     pub strategy: Option<ScenarioStrategy>,
+    /// Relative share of requests served by this scenario under the `weighted` strategy
+    #[serde(default)]
+    pub weight: Option<u32>,
+    /// Request header or cookie identifying a client so it keeps its weighted bucket
+    #[serde(default)]
+    pub sticky: Option<StickyKey>,
 }
 
 /// Strategy for auto-selecting scenarios
@@ -140,6 +390,35 @@ pub struct ScenarioDefinition {
 pub enum ScenarioStrategy {
     Sequential,
     Random,
+    Weighted,
+}
+
+/// Source of the client id used for sticky weighted bucketing
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+pub struct StickyKey {
+    /// Request header carrying the client id
+    #[serde(default)]
+    pub header: Option<String>,
+    /// Cookie carrying the client id
+    #[serde(default)]
+    pub cookie: Option<String>,
+}
+
+impl StickyKey {
+    /// Extract the client id from request headers (keys are lowercase)
+    pub fn client_id(&self, headers: &HashMap<String, String>) -> Option<String> {
+        let from_header = self
+            .header
+            .as_ref()
+            .and_then(|name| headers.get(&name.to_ascii_lowercase()).cloned());
+        from_header.or_else(|| {
+            let name = self.cookie.as_ref()?;
+            headers.get("cookie")?.split(';').find_map(|pair| {
+                let (key, value) = pair.trim().split_once('=')?;
+                (key == name).then(|| value.to_string())
+            })
+        })
+    }
 }
 
 /// Conditions evaluated against incoming requests
@@ -149,8 +428,18 @@ pub struct ScenarioConditions {
     pub query: Option<HashMap<String, String>>,
     #[serde(default)]
     pub headers: Option<HashMap<String, String>>,
+    /// Cookie values that must be present in the `Cookie` header
+    #[serde(default)]
+    pub cookies: Option<HashMap<String, String>>,
     #[serde(default)]
     pub body: Option<HashMap<String, serde_json::Value>>,
+    /// Regex matched against the raw request body, e.g. for XML/SOAP payloads
+    #[serde(default)]
+    pub body_regex: Option<String>,
+    /// Media type the body was parsed as, e.g. `application/json` or
+    /// `application/x-www-form-urlencoded`
+    #[serde(default)]
+    pub content_type: Option<String>,
 }
 
 /// Response associated with a scenario
@@ -194,9 +483,23 @@ impl ConfigValidator for EndpointDefinition {
                 suggestion: Some("Ensure path starts with '/', e.g., '/users'".to_string()),
             });
         }
+        errors.extend(self.path_errors());
+
+        if let Some(expression) = &self.proxy_response_transform {
+            if let Err(e) = jmespath::compile(expression) {
+                errors.push(ValidationError {
+                    field: "proxy_response_transform".to_string(),
+                    message: format!("Invalid JMESPath expression: {}", e),
+                    suggestion: Some("Use a path such as 'data.items'".to_string()),
+                });
+            }
+        }
 
         // Validate responses
-        if self.responses.is_empty() {
+        if self.responses.is_empty()
+            && !self.is_event_stream()
+            && self.kind != EndpointKind::WebSocket
+        {
             errors.push(ValidationError {
                 field: "responses".to_string(),
                 message: "Endpoint must have at least one response definition".to_string(),
@@ -221,6 +524,27 @@ impl ConfigValidator for EndpointDefinition {
             }
         }
 
+        if let Some(ref scenarios) = self.scenarios {
+            let weighted: Vec<(usize, &ScenarioDefinition)> = scenarios
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| matches!(s.strategy, Some(ScenarioStrategy::Weighted)))
+                .collect();
+            if !weighted.is_empty() && weighted.iter().all(|(_, s)| s.weight == Some(0)) {
+                errors.push(ValidationError {
+                    field: format!("scenarios[{}].weight", weighted[0].0),
+                    message: "Weighted scenarios must not all have a zero weight".to_string(),
+                    suggestion: Some("Give at least one scenario a positive weight".to_string()),
+                });
+            }
+        }
+
+        if let Some(ref behavior) = self.behavior {
+            if let Err(mut behavior_errors) = behavior.validate() {
+                errors.append(&mut behavior_errors);
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -257,6 +581,41 @@ impl ResponseDefinition {
             }
         }
 
+        if let Some(ref encoded) = self.body_base64 {
+            use base64::Engine;
+            if let Err(e) = base64::engine::general_purpose::STANDARD.decode(encoded.trim()) {
+                errors.push(ValidationError {
+                    field: "body_base64".to_string(),
+                    message: format!("Invalid base64 body: {}", e),
+                    suggestion: Some("Encode the raw bytes with standard base64".to_string()),
+                });
+            }
+        }
+
+        match (self.serve_times, &self.condition) {
+            (Some(0), _) => errors.push(ValidationError {
+                field: "serve_times".to_string(),
+                message: "serve_times must be at least 1".to_string(),
+                suggestion: Some(
+                    "Remove the response instead of serving it zero times".to_string(),
+                ),
+            }),
+            (Some(_), Some(_)) => errors.push(ValidationError {
+                field: "serve_times".to_string(),
+                message: "serve_times cannot be combined with a condition".to_string(),
+                suggestion: Some("Use a scenario for conditional responses".to_string()),
+            }),
+            _ => {}
+        }
+
+        if self.body_file.is_some() && (self.body_base64.is_some() || !body_trimmed.is_empty()) {
+            errors.push(ValidationError {
+                field: "body_file".to_string(),
+                message: "body_file cannot be combined with body or body_base64".to_string(),
+                suggestion: Some("Keep the body in one place".to_string()),
+            });
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
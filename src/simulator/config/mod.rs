@@ -2,16 +2,22 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
+pub mod builder;
 pub mod endpoint;
 pub mod server;
 pub mod validation;
 
+pub use builder::{EndpointBuilder, ServiceDefinitionBuilder};
 pub use endpoint::{
-    EndpointDefinition, EndpointKind, ParameterDefinition, ParameterLocation, PeriodicMessage,
-    RequestBodyDefinition, ResponseDefinition, ScenarioConditions, ScenarioDefinition,
-    ScenarioResponse, ScenarioStrategy, SideEffect, StreamConfig,
+    EndpointDefinition, EndpointFallback, EndpointKind, ParameterDefinition, ParameterLocation,
+    PeriodicMessage, RequestBodyDefinition, ResponseDefinition, ScenarioConditions,
+    ScenarioDefinition, ScenarioResponse, ScenarioStrategy, SideEffect, StateTransition, StickyKey,
+    StreamConfig,
+};
+pub use server::{
+    AuthConfig, CompressionConfig, CorsConfig, ProxyTlsConfig, ServerConfig, TlsConfig,
+    TrailingSlashConfig, TrailingSlashMode,
 };
-pub use server::{CorsConfig, ServerConfig};
 pub use validation::{ConfigLoader, LoadError, LoadErrorType, LoadResult, ValidationSummary};
 
 // Import IoT types
@@ -39,6 +45,23 @@ pub struct SimulatorConfig {
     /// Global behavior settings
     #[serde(default)]
     pub global_behavior: Option<BehaviorConfig>,
+    /// Guardrails applied when loading service definitions
+    #[serde(default)]
+    pub limits: Option<LoadLimits>,
+    /// Environment whose `<service>.<env>.yaml` overlays are merged onto the base files
+    #[serde(default)]
+    pub env: Option<String>,
+    /// `Retry-After` seconds on the 503s answered while a reload swaps services (default 1)
+    #[serde(default)]
+    pub reload_retry_after_secs: Option<u64>,
+    /// Fail every service's templates on undefined variables, as if each set
+    /// `server.strict_templates`
+    #[serde(default)]
+    pub strict_templates: bool,
+    /// Versions of each service definition kept in the database for rollback
+    /// (default 10)
+    #[serde(default)]
+    pub service_history: Option<usize>,
 }
 
 impl SimulatorConfig {
@@ -51,6 +74,11 @@ impl SimulatorConfig {
             db_path: default_db_path(),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         }
     }
 
@@ -66,6 +94,11 @@ impl SimulatorConfig {
             db_path: default_db_path(),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         }
     }
 
@@ -87,10 +120,29 @@ impl Default for SimulatorConfig {
             db_path: default_db_path(),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         }
     }
 }
 
+/// Upper bounds enforced when loading service definitions; unset means unlimited
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LoadLimits {
+    /// Maximum number of services in the services directory
+    #[serde(default)]
+    pub max_services: Option<usize>,
+    /// Maximum number of endpoints declared by a single service
+    #[serde(default)]
+    pub max_endpoints_per_service: Option<usize>,
+    /// Maximum size in bytes of any response body template
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+}
+
 /// Port range configuration for service assignment
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PortRange {
@@ -107,20 +159,94 @@ pub struct ServiceDefinition {
     pub server: Option<ServerConfig>,
     pub models: Option<BTreeMap<String, serde_json::Value>>, // JSON Schema definitions
     pub fixtures: Option<HashMap<String, serde_json::Value>>,
+    /// Field to sort each named array fixture by (`-field` for descending);
+    /// arrays not listed keep their file order
+    #[serde(default)]
+    pub fixture_sort: Option<HashMap<String, String>>,
     #[serde(default)]
     pub bucket: Option<HashMap<String, serde_json::Value>>,
+    /// Named Handlebars partials shared by all response templates (`{{> name}}`)
+    #[serde(default)]
+    pub partials: Option<HashMap<String, String>>,
     #[serde(default)]
     pub endpoints: Option<Vec<EndpointDefinition>>,
     #[serde(default)]
     pub graphql: Option<GraphQLConfig>,
     #[serde(default)]
     pub behavior: Option<BehaviorConfig>,
+    /// Finite state machine moved by endpoint `transition`s; templates read
+    /// the current state as `state.current`
+    #[serde(default)]
+    pub state_machine: Option<StateMachineConfig>,
     // Digital Twin support
     #[serde(default)]
     #[cfg(feature = "iot")]
     pub twin: Option<TwinDefinition>,
 }
 
+impl ServiceDefinition {
+    /// Drop every endpoint not labelled with `tag`
+    pub fn retain_tagged(&mut self, tag: &str) {
+        if let Some(endpoints) = self.endpoints.as_mut() {
+            endpoints.retain(|endpoint| endpoint.has_tag(tag));
+        }
+    }
+
+    /// Apply `fixture_sort` to the array fixtures it names.
+    ///
+    /// The sort is stable, so elements with equal (or missing) keys keep their
+    /// file order; missing keys sort last.
+    pub fn sort_fixtures(&mut self) {
+        let (Some(fixtures), Some(sort)) = (self.fixtures.as_mut(), self.fixture_sort.as_ref())
+        else {
+            return;
+        };
+        for (name, field) in sort {
+            let Some(serde_json::Value::Array(items)) = fixtures.get_mut(name) else {
+                continue;
+            };
+            let (field, descending) = match field.strip_prefix('-') {
+                Some(field) => (field, true),
+                None => (field.as_str(), false),
+            };
+            items.sort_by(|a, b| match (a.get(field), b.get(field)) {
+                (Some(a), Some(b)) => {
+                    let ordering = compare_json(a, b);
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                }
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            });
+        }
+    }
+}
+
+/// Order numbers numerically and everything else by its text
+fn compare_json(a: &serde_json::Value, b: &serde_json::Value) -> std::cmp::Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => match (a.as_str(), b.as_str()) {
+            (Some(a), Some(b)) => a.cmp(b),
+            _ => a.to_string().cmp(&b.to_string()),
+        },
+    }
+}
+
+/// Declarative state machine for multi-step flows (e.g. cart → checkout → paid)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StateMachineConfig {
+    /// State the service (and each tenant partition) starts in
+    pub initial: String,
+    /// Every valid state; transitions are validated against it when set
+    #[serde(default)]
+    pub states: Option<Vec<String>>,
+}
+
 /// Helper for untagged deserialization of service files
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -144,10 +270,13 @@ impl From<UnifiedConfig> for ServiceDefinition {
                 server: None,
                 models: None,
                 fixtures: None,
+                fixture_sort: None,
                 bucket: None,
+                partials: None,
                 endpoints: None,
                 graphql: None,
                 behavior: None,
+                state_machine: None,
                 twin: Some(twin),
             },
         }
@@ -172,13 +301,95 @@ pub struct BehaviorConfig {
     pub error_simulation: Option<ErrorSimulationConfig>,
     #[serde(default)]
     pub rate_limiting: Option<RateLimitingConfig>,
+    /// Regexes whose matches are masked in logged request bodies and paths
+    #[serde(default)]
+    pub redact: Option<Vec<String>>,
+    /// Upper bound for the `X-Mock-Delay` request header; `0` ignores the header
+    #[serde(default)]
+    pub max_mock_delay_ms: Option<u64>,
+    /// Compress large responses; overrides `server.compression`
+    #[serde(default)]
+    pub compression: Option<CompressionConfig>,
 }
 
+impl BehaviorConfig {
+    /// Effective behavior for an endpoint. Each setting is taken from the
+    /// endpoint first, then the service, then the global configuration.
+    pub fn resolve(
+        endpoint: Option<&BehaviorConfig>,
+        service: Option<&BehaviorConfig>,
+        global: Option<&BehaviorConfig>,
+    ) -> BehaviorConfig {
+        let levels: Vec<&BehaviorConfig> =
+            [endpoint, service, global].into_iter().flatten().collect();
+        BehaviorConfig {
+            latency: levels.iter().find_map(|b| b.latency.clone()),
+            error_simulation: levels.iter().find_map(|b| b.error_simulation.clone()),
+            rate_limiting: levels.iter().find_map(|b| b.rate_limiting.clone()),
+            redact: levels.iter().find_map(|b| b.redact.clone()),
+            max_mock_delay_ms: levels.iter().find_map(|b| b.max_mock_delay_ms),
+            compression: levels.iter().find_map(|b| b.compression.clone()),
+        }
+    }
+
+    /// Delay requested through an `X-Mock-Delay` header value, capped by
+    /// `max_mock_delay_ms` (10 seconds when unset)
+    pub fn mock_delay_ms(&self, header: &str) -> Option<u64> {
+        let requested = header.trim().parse::<u64>().ok()?;
+        Some(requested.min(self.max_mock_delay_ms.unwrap_or(DEFAULT_MAX_MOCK_DELAY_MS)))
+            .filter(|delay| *delay > 0)
+    }
+}
+
+/// Cap applied to `X-Mock-Delay` when no `max_mock_delay_ms` is configured
+pub const DEFAULT_MAX_MOCK_DELAY_MS: u64 = 10_000;
+
 /// Latency simulation configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LatencyConfig {
     pub min_ms: u64,
     pub max_ms: u64,
+    /// Shape of the delay distribution; samples are clamped to `min_ms..=max_ms`
+    #[serde(default)]
+    pub distribution: Option<LatencyDistribution>,
+}
+
+/// Distribution simulated latencies are drawn from
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LatencyDistribution {
+    /// Every delay between `min_ms` and `max_ms` is equally likely
+    Uniform,
+    /// Bell curve around `mean_ms`
+    Normal { mean_ms: f64, stddev_ms: f64 },
+    /// Long right tail around a typical `median_ms`; larger `sigma` means
+    /// heavier tail spikes
+    Lognormal { median_ms: f64, sigma: f64 },
+}
+
+impl LatencyConfig {
+    /// Draw a delay in milliseconds from the configured distribution
+    pub fn sample_ms<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let (min, max) = (self.min_ms.min(self.max_ms), self.max_ms.max(self.min_ms));
+        let sampled = match self.distribution {
+            None | Some(LatencyDistribution::Uniform) => return rng.gen_range(min..=max),
+            Some(LatencyDistribution::Normal { mean_ms, stddev_ms }) => {
+                mean_ms + stddev_ms * standard_normal(rng)
+            }
+            Some(LatencyDistribution::Lognormal { median_ms, sigma }) => {
+                median_ms * (sigma * standard_normal(rng)).exp()
+            }
+        };
+        (sampled.max(0.0).round() as u64).clamp(min, max)
+    }
+}
+
+/// Standard normal sample via the Box-Muller transform
+fn standard_normal<R: rand::Rng + ?Sized>(rng: &mut R) -> f64 {
+    // `gen::<f64>()` is in [0, 1); shift to (0, 1] so the logarithm stays finite
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
 }
 
 /// Error simulation configuration
@@ -189,9 +400,157 @@ pub struct ErrorSimulationConfig {
     pub status_codes: Option<Vec<u16>>,
 }
 
+impl ErrorSimulationConfig {
+    /// Status to inject for a request when the roll against `rate` hits,
+    /// drawn from `status_codes` (500 when none are listed)
+    pub fn roll<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<u16> {
+        if !self.enabled || rng.gen::<f64>() >= self.rate {
+            return None;
+        }
+        match self.status_codes.as_deref() {
+            Some(codes) if !codes.is_empty() => Some(codes[rng.gen_range(0..codes.len())]),
+            _ => Some(500),
+        }
+    }
+}
+
 /// Rate limiting configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RateLimitingConfig {
     pub enabled: bool,
     pub requests_per_minute: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn behavior(yaml: &str) -> BehaviorConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn behavior_resolves_endpoint_over_service_over_global() {
+        let global = behavior(
+            "latency: {min_ms: 1, max_ms: 1}\nrate_limiting: {enabled: true, requests_per_minute: 60}\nredact: ['secret']\n",
+        );
+        let service = behavior(
+            "latency: {min_ms: 2, max_ms: 2}\nerror_simulation: {enabled: true, rate: 0.5}\n",
+        );
+        let endpoint = behavior("latency: {min_ms: 3, max_ms: 3}\n");
+
+        // Global only
+        let resolved = BehaviorConfig::resolve(None, None, Some(&global));
+        assert_eq!(resolved.latency.unwrap().min_ms, 1);
+        assert!(resolved.error_simulation.is_none());
+
+        // Service overrides global, unset settings still inherit
+        let resolved = BehaviorConfig::resolve(None, Some(&service), Some(&global));
+        assert_eq!(resolved.latency.unwrap().min_ms, 2);
+        assert_eq!(resolved.error_simulation.unwrap().rate, 0.5);
+        assert_eq!(resolved.rate_limiting.unwrap().requests_per_minute, 60);
+
+        // Endpoint overrides both
+        let resolved = BehaviorConfig::resolve(Some(&endpoint), Some(&service), Some(&global));
+        assert_eq!(resolved.latency.unwrap().min_ms, 3);
+        assert_eq!(resolved.error_simulation.unwrap().rate, 0.5);
+        assert_eq!(resolved.redact.unwrap(), vec!["secret".to_string()]);
+
+        let resolved = BehaviorConfig::resolve(None, None, None);
+        assert!(resolved.latency.is_none() && resolved.rate_limiting.is_none());
+    }
+
+    #[test]
+    fn fixture_sort_orders_named_arrays_and_keeps_others() {
+        let mut service: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: shop
+fixtures:
+  users: [{name: Cy}, {name: Ana}, {id: 9}, {name: Bo}]
+  orders: [{total: 5}, {total: 40}, {total: 12}]
+  tags: [b, a]
+fixture_sort:
+  users: name
+  orders: -total
+"#,
+        )
+        .unwrap();
+        service.sort_fixtures();
+
+        let fixtures = service.fixtures.unwrap();
+        assert_eq!(
+            fixtures["users"],
+            serde_json::json!([{"name": "Ana"}, {"name": "Bo"}, {"name": "Cy"}, {"id": 9}])
+        );
+        assert_eq!(
+            fixtures["orders"],
+            serde_json::json!([{"total": 40}, {"total": 12}, {"total": 5}])
+        );
+        assert_eq!(fixtures["tags"], serde_json::json!(["b", "a"]));
+    }
+
+    #[test]
+    fn response_body_accepts_string_or_structured_value() {
+        let quoted: ResponseDefinition = serde_yaml::from_str(
+            r#"
+content_type: application/json
+body: '{"id": 1, "tags": ["a", "b"], "name": "{{params.name}}"}'
+"#,
+        )
+        .unwrap();
+        let structured: ResponseDefinition = serde_yaml::from_str(
+            r#"
+content_type: application/json
+body:
+  id: 1
+  tags: [a, b]
+  name: "{{params.name}}"
+"#,
+        )
+        .unwrap();
+
+        let parse = |body: &str| serde_json::from_str::<serde_json::Value>(body).unwrap();
+        assert_eq!(parse(&quoted.body), parse(&structured.body));
+        assert_eq!(
+            structured.body,
+            r#"{"id":1,"tags":["a","b"],"name":"{{params.name}}"}"#
+        );
+    }
+
+    #[test]
+    fn normal_latency_sample_mean_matches_configured_mean() {
+        let latency: LatencyConfig = serde_yaml::from_str(
+            "min_ms: 0\nmax_ms: 1000\ndistribution:\n  type: normal\n  mean_ms: 200\n  stddev_ms: 30\n",
+        )
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let samples = 20_000;
+        let total: u64 = (0..samples).map(|_| latency.sample_ms(&mut rng)).sum();
+        let mean = total as f64 / samples as f64;
+        assert!(
+            (mean - 200.0).abs() < 2.0,
+            "sample mean {} too far from 200",
+            mean
+        );
+    }
+
+    #[test]
+    fn latency_samples_stay_within_bounds() {
+        let latency = LatencyConfig {
+            min_ms: 50,
+            max_ms: 500,
+            distribution: Some(LatencyDistribution::Lognormal {
+                median_ms: 80.0,
+                sigma: 1.5,
+            }),
+        };
+        let mut rng = StdRng::seed_from_u64(11);
+        let samples: Vec<u64> = (0..5_000).map(|_| latency.sample_ms(&mut rng)).collect();
+        assert!(samples.iter().all(|s| (50..=500).contains(s)));
+        // The long tail reaches the cap while the bulk sits near the median
+        assert!(samples.contains(&500));
+        assert!(samples.iter().filter(|s| **s < 150).count() > samples.len() / 2);
+    }
+}
@@ -1,20 +1,144 @@
 use crate::errors::ValidationError;
 use crate::validation::{ConfigValidator, ValidationUtils};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use url::Url;
 
 /// Server configuration for a service
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct ServerConfig {
     pub port: Option<u16>,
     pub base_path: String,
     /// Optional base URL to proxy requests when no local endpoint matches
     #[serde(default)]
     pub proxy_base_url: Option<String>,
+    /// TLS options for the client used to reach `proxy_base_url`
+    #[serde(default)]
+    pub proxy_tls: Option<ProxyTlsConfig>,
+    /// JMESPath expression applied to proxied JSON responses, e.g. `data.items`
     #[serde(default)]
+    pub proxy_response_transform: Option<String>,
+    /// CORS settings; `cors: none` suppresses every CORS header, while leaving
+    /// it unset answers with a wildcard origin
+    #[serde(default, deserialize_with = "deserialize_cors")]
     pub cors: Option<CorsConfig>,
     #[serde(default)]
     pub record_unknown: bool,
+    /// Re-indent JSON response bodies for readability
+    #[serde(default)]
+    pub pretty_json: bool,
+    /// JWT validation for endpoints that declare `requires`
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    /// Compress responses with an encoding the client accepts
+    #[serde(default)]
+    pub compression: Option<CompressionConfig>,
+    /// Header whose value selects an isolated fixtures/runtime-data partition
+    /// (e.g. `X-Tenant-Id`); requests without it share one partition
+    #[serde(default)]
+    pub tenant_header: Option<String>,
+    /// Serve the internal `/__apicentric/*` endpoints (on unless set to `false`)
+    #[serde(default)]
+    pub internal_endpoints: Option<bool>,
+    /// Fail responses whose templates reference variables missing from the
+    /// context instead of rendering them empty
+    #[serde(default)]
+    pub strict_templates: bool,
+    /// How `/users/` is treated when only `/users` is declared (and vice versa)
+    #[serde(default)]
+    pub trailing_slash: Option<TrailingSlashConfig>,
+    /// Abort response scripts that run longer than this many milliseconds
+    /// (default 2000)
+    #[serde(default)]
+    pub script_timeout_ms: Option<u64>,
+    /// Let response scripts call remote services with `fetch`
+    #[serde(default)]
+    pub allow_script_network: bool,
+    /// Let responses run external commands with `exec` (off by default)
+    #[serde(default)]
+    pub allow_exec: bool,
+    /// Programs `exec` responses may run once `allow_exec` is set
+    #[serde(default)]
+    pub exec_allowlist: Vec<String>,
+    /// Prefixes under `base_path` that every endpoint is also served under,
+    /// e.g. `["/v1", "/v2"]` answers `/v1/users` and `/v2/users` from `/users`
+    #[serde(default)]
+    pub path_prefixes: Vec<String>,
+    /// Serve HTTPS, optionally checking client certificates
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// TLS termination for the service's own listener
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TlsConfig {
+    /// PEM certificate chain presented to clients
+    pub cert: PathBuf,
+    /// PEM private key for `cert`
+    pub key: PathBuf,
+    /// PEM roots that client certificates must chain to
+    #[serde(default)]
+    pub client_ca: Option<PathBuf>,
+    /// Answer 401 to requests made without a client certificate
+    #[serde(default)]
+    pub require_client_cert: bool,
+}
+
+/// Trailing slash policy for request paths
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TrailingSlashConfig {
+    #[serde(default)]
+    pub mode: TrailingSlashMode,
+}
+
+/// What to do with a request whose path only matches once a trailing slash
+/// is added or removed
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlashMode {
+    /// `/users` and `/users/` are different routes
+    #[default]
+    Strict,
+    /// Answer with a 308 pointing at the declared form
+    Redirect,
+    /// Serve the declared endpoint for either form
+    Ignore,
+}
+
+/// Response compression negotiated from `Accept-Encoding`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompressionConfig {
+    /// Encodings to offer, most preferred first; the client's weights win
+    #[serde(default = "default_encodings")]
+    pub encodings: Vec<String>,
+    /// Bodies smaller than this many bytes are sent uncompressed
+    #[serde(default)]
+    pub min_size: usize,
+}
+
+fn default_encodings() -> Vec<String> {
+    vec!["br".to_string(), "gzip".to_string()]
+}
+
+/// Bearer token settings checked against an endpoint's required scopes
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuthConfig {
+    /// HMAC secret used to verify tokens
+    pub jwt_secret: String,
+    /// Claim holding the granted scopes or roles (defaults to `scope`)
+    #[serde(default)]
+    pub scope_claim: Option<String>,
+}
+
+/// TLS options for proxying to HTTPS backends
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProxyTlsConfig {
+    /// Skip certificate verification, e.g. for self-signed dev backends
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    /// PEM file with an extra root certificate to trust
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
 }
 
 /// CORS configuration
@@ -26,6 +150,55 @@ pub struct CorsConfig {
     pub methods: Option<Vec<String>>,
     #[serde(default)]
     pub headers: Option<Vec<String>>,
+    /// Response headers browser scripts may read (`Access-Control-Expose-Headers`)
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    /// How long browsers may cache a preflight answer
+    #[serde(default = "default_cors_max_age")]
+    pub max_age_secs: u64,
+}
+
+fn default_cors_max_age() -> u64 {
+    86400
+}
+
+impl CorsConfig {
+    /// The `cors: none` mode: no CORS headers on any response
+    pub fn none() -> Self {
+        Self {
+            enabled: false,
+            origins: Vec::new(),
+            methods: None,
+            headers: None,
+            expose_headers: Vec::new(),
+            max_age_secs: default_cors_max_age(),
+        }
+    }
+}
+
+/// Accept either a CORS mapping or the string `none`
+fn deserialize_cors<'de, D>(deserializer: D) -> Result<Option<CorsConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CorsSetting {
+        Mode(String),
+        Config(CorsConfig),
+    }
+
+    match Option::<CorsSetting>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(CorsSetting::Config(cfg)) => Ok(Some(cfg)),
+        Some(CorsSetting::Mode(mode)) if mode.eq_ignore_ascii_case("none") => {
+            Ok(Some(CorsConfig::none()))
+        }
+        Some(CorsSetting::Mode(mode)) => Err(serde::de::Error::custom(format!(
+            "unknown cors mode '{}', expected 'none' or a CORS mapping",
+            mode
+        ))),
+    }
 }
 
 impl ConfigValidator for ServerConfig {
@@ -71,6 +244,30 @@ impl ConfigValidator for ServerConfig {
             }
         }
 
+        if let Some(tls) = &self.tls {
+            if tls.require_client_cert && tls.client_ca.is_none() {
+                errors.push(ValidationError {
+                    field: "server.tls.client_ca".to_string(),
+                    message: "require_client_cert needs a client_ca to verify certificates"
+                        .to_string(),
+                    suggestion: Some(
+                        "Point client_ca at the PEM file of the CA that issues client certificates"
+                            .to_string(),
+                    ),
+                });
+            }
+        }
+
+        if let Some(expression) = &self.proxy_response_transform {
+            if let Err(e) = jmespath::compile(expression) {
+                errors.push(ValidationError {
+                    field: "server.proxy_response_transform".to_string(),
+                    message: format!("Invalid JMESPath expression: {}", e),
+                    suggestion: Some("Use a path such as 'data.items'".to_string()),
+                });
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -0,0 +1,126 @@
+use serde_yaml::{Mapping, Value};
+
+/// Repair simple, unambiguous problems in a raw service document.
+///
+/// Endpoint methods are uppercased, endpoints without any response get a
+/// default `200`, and responses missing `content_type` get `application/json`.
+/// Every entry of a combined `services:` manifest is repaired the same way.
+/// Nothing else in the document is touched. Returns one line per change.
+pub fn fix_service_yaml(doc: &mut Value) -> Vec<String> {
+    let mut changes = Vec::new();
+    if let Some(services) = doc.get_mut("services").and_then(Value::as_sequence_mut) {
+        for (i, service) in services.iter_mut().enumerate() {
+            fix_endpoints(service, &format!("services[{}].", i), &mut changes);
+        }
+    } else {
+        fix_endpoints(doc, "", &mut changes);
+    }
+    changes
+}
+
+/// Repair the `endpoints` of one service, prefixing each change with `prefix`
+fn fix_endpoints(service: &mut Value, prefix: &str, changes: &mut Vec<String>) {
+    let Some(endpoints) = service
+        .get_mut("endpoints")
+        .and_then(Value::as_sequence_mut)
+    else {
+        return;
+    };
+
+    for (i, endpoint) in endpoints.iter_mut().enumerate() {
+        let Some(endpoint) = endpoint.as_mapping_mut() else {
+            continue;
+        };
+
+        if let Some(Value::String(method)) = endpoint.get_mut("method") {
+            let upper = method.to_uppercase();
+            if *method != upper {
+                changes.push(format!(
+                    "{}endpoints[{}].method: '{}' -> '{}'",
+                    prefix, i, method, upper
+                ));
+                *method = upper;
+            }
+        }
+
+        let has_responses = endpoint
+            .get("responses")
+            .and_then(Value::as_mapping)
+            .is_some_and(|r| !r.is_empty());
+        if !has_responses {
+            let mut ok = Mapping::new();
+            ok.insert("content_type".into(), "application/json".into());
+            ok.insert("body".into(), "{}".into());
+            let mut responses = Mapping::new();
+            responses.insert(200.into(), Value::Mapping(ok));
+            endpoint.insert("responses".into(), Value::Mapping(responses));
+            changes.push(format!(
+                "{}endpoints[{}].responses: added default 200",
+                prefix, i
+            ));
+            continue;
+        }
+
+        if let Some(responses) = endpoint
+            .get_mut("responses")
+            .and_then(Value::as_mapping_mut)
+        {
+            for (status, response) in responses.iter_mut() {
+                let Some(response) = response.as_mapping_mut() else {
+                    continue;
+                };
+                if !response.contains_key("content_type") {
+                    response.insert("content_type".into(), "application/json".into());
+                    let status = serde_yaml::to_string(status).unwrap_or_default();
+                    changes.push(format!(
+                        "{}endpoints[{}].responses.{}.content_type: added 'application/json'",
+                        prefix,
+                        i,
+                        status.trim()
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixes_method_case_and_missing_responses() {
+        let mut doc: Value = serde_yaml::from_str(
+            "name: svc\nendpoints:\n  - method: get\n    path: /a\n  - method: POST\n    path: /b\n    responses:\n      201:\n        body: ok\n        content_type: text/plain\n",
+        )
+        .unwrap();
+        let changes = fix_service_yaml(&mut doc);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(doc["endpoints"][0]["method"], "GET");
+        assert_eq!(
+            doc["endpoints"][0]["responses"][200]["content_type"],
+            "application/json"
+        );
+        assert_eq!(
+            doc["endpoints"][1]["responses"][201]["content_type"],
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn fixes_every_service_of_a_manifest() {
+        let mut doc: Value = serde_yaml::from_str(
+            "services:\n  - name: a\n    endpoints:\n      - method: get\n        path: /a\n        responses:\n          200:\n            body: ok\n            content_type: text/plain\n  - name: b\n    endpoints:\n      - method: delete\n        path: /b\n        responses:\n          204:\n            body: ''\n            content_type: text/plain\n",
+        )
+        .unwrap();
+        let changes = fix_service_yaml(&mut doc);
+        assert_eq!(
+            changes,
+            vec![
+                "services[0].endpoints[0].method: 'get' -> 'GET'",
+                "services[1].endpoints[0].method: 'delete' -> 'DELETE'",
+            ]
+        );
+        assert_eq!(doc["services"][1]["endpoints"][0]["method"], "DELETE");
+    }
+}
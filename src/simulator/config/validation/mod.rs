@@ -1,19 +1,26 @@
+mod fixer;
 mod repository;
 mod summarizer;
 mod validators;
 
+pub use fixer::fix_service_yaml;
 pub use repository::{ConfigFileLoader, ConfigRepository};
 pub use summarizer::{summarize, LoadError, LoadErrorType, ValidationSummary};
-pub use validators::{validate_service_schema, validate_unique_name};
+pub use validators::{
+    trailing_slash_duplicates, validate_service_limits, validate_service_schema,
+    validate_unique_name, validate_unique_port,
+};
 
-use super::ServiceDefinition;
+use super::{LoadLimits, ServiceDefinition};
 use crate::errors::{ApicentricError, ApicentricResult};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 #[derive(Clone)]
 pub struct ConfigLoader<R: ConfigRepository + Clone = repository::ConfigFileLoader> {
     repository: R,
+    limits: LoadLimits,
+    env: Option<String>,
 }
 
 impl ConfigLoader<repository::ConfigFileLoader> {
@@ -21,17 +28,54 @@ impl ConfigLoader<repository::ConfigFileLoader> {
         let canonical_root = root.canonicalize().unwrap_or(root);
         Self {
             repository: repository::ConfigFileLoader::new(canonical_root),
+            limits: LoadLimits::default(),
+            env: None,
         }
     }
+
+    /// Load and validate a single service from an in-memory YAML document (e.g. stdin)
+    pub fn load_service_from_yaml(
+        content: &str,
+        origin: &str,
+    ) -> ApicentricResult<ServiceDefinition> {
+        let service = repository::parse_service_yaml(content, origin)?;
+        validators::validate_service_schema(&service)?;
+        Ok(service)
+    }
 }
 
 impl<R: ConfigRepository + Clone> ConfigLoader<R> {
     pub fn with_repository(repository: R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            limits: LoadLimits::default(),
+            env: None,
+        }
+    }
+
+    /// Enforce the given limits on every subsequent load
+    pub fn with_limits(mut self, limits: LoadLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Merge `<service>.<env>.yaml` overlays onto their base files when loading
+    pub fn with_env(mut self, env: Option<String>) -> Self {
+        self.env = env;
+        self
     }
 
     pub fn load_all_services(&self) -> ApicentricResult<Vec<ServiceDefinition>> {
         let result = self.load_all_services_with_summary()?;
+        if result.summary.total_files == 0 {
+            return Err(ApicentricError::config_error(
+                format!(
+                    "No service YAMLs found in '{}'",
+                    self.repository.get_services_dir().display()
+                ),
+                Some("Add .yaml or .yml service definition files to the services directory"),
+            ));
+        }
         if result.services.is_empty() {
             return Err(ApicentricError::config_error(
                 "No valid service definitions found in services directory",
@@ -62,45 +106,126 @@ impl<R: ConfigRepository + Clone> ConfigLoader<R> {
     }
 
     pub fn load_all_services_with_summary(&self) -> ApicentricResult<LoadResult> {
-        let files = self.repository.list_service_files()?;
+        let all_files = self.repository.list_service_files()?;
+        let env = self.env.as_deref();
+        let is_overlay = |file: &PathBuf| {
+            env.is_some() && overlay_env(file) == env && overlay_base(file, &all_files).is_some()
+        };
+        // Overlays for the selected env only apply to their base
+        let files: Vec<PathBuf> = all_files
+            .iter()
+            .filter(|file| !is_overlay(file))
+            .cloned()
+            .collect();
         let mut services = Vec::new();
         let mut errors = Vec::new();
         let mut names = HashSet::new();
+        let mut ports = HashMap::new();
+        // A combined manifest counts once per service it lists
+        let mut total = 0;
 
         for file in files.iter() {
-            match self.repository.load_service(file) {
-                Ok(service) => {
-                    if let Err(e) = validators::validate_unique_name(&service, &mut names) {
-                        errors.push(LoadError {
-                            file_path: file.clone(),
-                            error_type: LoadErrorType::DuplicateName,
-                            message: e.to_string(),
-                        });
-                    } else if let Err(e) = validators::validate_service_schema(&service) {
-                        errors.push(LoadError {
-                            file_path: file.clone(),
-                            error_type: LoadErrorType::Validation,
-                            message: e.to_string(),
-                        });
-                    } else {
-                        services.push(service);
-                    }
+            let overlay = all_files.iter().find(|candidate| {
+                is_overlay(candidate) && overlay_base(candidate, &all_files).as_ref() == Some(file)
+            });
+            let loaded = match overlay {
+                Some(overlay) => self
+                    .repository
+                    .load_service_with_overlay(file, overlay)
+                    .map(|service| vec![service]),
+                None => self.repository.load_services(file),
+            };
+            let loaded = match loaded {
+                Ok(loaded) => loaded,
+                // Most likely the overlay of another environment, e.g. `users.prod.yaml`
+                // while running `--env staging`
+                Err(e) if overlay_base(file, &all_files).is_some() => {
+                    log::warn!(
+                        "Skipping '{}': not a service definition ({}); it only applies as an overlay with --env {}",
+                        file.display(),
+                        e,
+                        overlay_env(file).unwrap_or_default()
+                    );
+                    continue;
                 }
                 Err(e) => {
+                    total += 1;
                     errors.push(LoadError {
                         file_path: file.clone(),
                         error_type: LoadErrorType::Parsing,
                         message: e.to_string(),
                     });
+                    continue;
+                }
+            };
+            total += loaded.len();
+            for service in loaded {
+                // Limits guard against runaway configs, so they abort the whole load
+                validators::validate_service_limits(&service, &self.limits)?;
+                if let Err(e) = validators::validate_unique_name(&service, &mut names) {
+                    errors.push(LoadError {
+                        file_path: file.clone(),
+                        error_type: LoadErrorType::DuplicateName,
+                        message: e.to_string(),
+                    });
+                } else if let Err(e) = validators::validate_service_schema(&service) {
+                    errors.push(LoadError {
+                        file_path: file.clone(),
+                        error_type: LoadErrorType::Validation,
+                        message: e.to_string(),
+                    });
+                } else if let Err(e) = validators::validate_unique_port(&service, file, &mut ports)
+                {
+                    errors.push(LoadError {
+                        file_path: file.clone(),
+                        error_type: LoadErrorType::PortConflict,
+                        message: e.to_string(),
+                    });
+                } else {
+                    for warning in validators::trailing_slash_duplicates(&service) {
+                        log::warn!("{}: {}", file.display(), warning);
+                    }
+                    services.push(service);
                 }
             }
         }
 
-        let summary = summarize(files.len(), errors);
+        if let Some(max) = self.limits.max_services {
+            if services.len() > max {
+                return Err(ApicentricError::config_error(
+                    format!(
+                        "Found {} services in '{}', exceeding the limit of {}",
+                        services.len(),
+                        self.repository.get_services_dir().display(),
+                        max
+                    ),
+                    Some("Remove services or raise simulator.limits.max_services"),
+                ));
+            }
+        }
+
+        let summary = summarize(total, errors);
         Ok(LoadResult { services, summary })
     }
 }
 
+/// Base file an overlay such as `users.staging.yaml` applies to (`users.yaml`
+/// or `users.yml`), when that base file is among `files`.
+fn overlay_base(file: &Path, files: &[PathBuf]) -> Option<PathBuf> {
+    let stem = file.file_stem()?.to_str()?;
+    let (base_stem, _) = stem.rsplit_once('.')?;
+    ["yaml", "yml"]
+        .iter()
+        .map(|ext| file.with_file_name(format!("{}.{}", base_stem, ext)))
+        .find(|base| files.contains(base))
+}
+
+/// Environment named by an overlay file, e.g. `staging` for `users.staging.yaml`
+fn overlay_env(file: &Path) -> Option<&str> {
+    let stem = file.file_stem()?.to_str()?;
+    stem.rsplit_once('.').map(|(_, env)| env)
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadResult {
     pub services: Vec<ServiceDefinition>,
@@ -135,4 +260,166 @@ mod tests {
         assert_eq!(result.summary.invalid_count, 1);
         assert_eq!(result.summary.total_files, 2);
     }
+
+    #[test]
+    fn env_overlay_merges_onto_base_service() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("users.yaml"),
+            "name: users\nserver:\n  base_path: /api\n  proxy_base_url: http://localhost:9000\n  cors:\n    enabled: true\n    origins: ['*']\nendpoints:\n  - method: GET\n    path: /users\n    responses:\n      200:\n        content_type: application/json\n        body: '[]'\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("users.staging.yaml"),
+            "server:\n  proxy_base_url: https://staging.example.com\n",
+        )
+        .unwrap();
+
+        let base = ConfigLoader::new(dir.path().to_path_buf())
+            .load_all_services_with_summary()
+            .unwrap();
+        assert_eq!(base.summary.total_files, 1);
+        assert_eq!(base.services.len(), 1);
+        let server = base.services[0].server.as_ref().unwrap();
+        assert_eq!(
+            server.proxy_base_url.as_deref(),
+            Some("http://localhost:9000")
+        );
+
+        let staging = ConfigLoader::new(dir.path().to_path_buf())
+            .with_env(Some("staging".to_string()))
+            .load_all_services()
+            .unwrap();
+        assert_eq!(staging.len(), 1);
+        let server = staging[0].server.as_ref().unwrap();
+        assert_eq!(
+            server.proxy_base_url.as_deref(),
+            Some("https://staging.example.com")
+        );
+        assert_eq!(server.base_path, "/api");
+        assert!(server.cors.as_ref().unwrap().enabled);
+        assert_eq!(staging[0].endpoints.as_ref().unwrap()[0].path, "/users");
+    }
+
+    #[test]
+    fn dotted_service_next_to_its_namesake_is_not_an_overlay() {
+        let dir = tempdir().unwrap();
+        write_valid_service(&dir.path().join("users.yaml"), "users");
+        write_valid_service(&dir.path().join("users.v2.yaml"), "users-v2");
+        fs::write(
+            dir.path().join("users.staging.yaml"),
+            "server:\n  proxy_base_url: https://staging.example.com\n",
+        )
+        .unwrap();
+
+        for env in [None, Some("staging".to_string())] {
+            let result = ConfigLoader::new(dir.path().to_path_buf())
+                .with_env(env)
+                .load_all_services_with_summary()
+                .unwrap();
+            let mut names: Vec<_> = result.services.iter().map(|s| s.name.clone()).collect();
+            names.sort();
+            assert_eq!(names, vec!["users", "users-v2"]);
+            assert_eq!(result.summary.invalid_count, 0);
+        }
+    }
+
+    #[test]
+    fn combined_manifest_registers_every_service() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("services.yaml"),
+            "services:\n  - name: users\n    server:\n      port: 9001\n      base_path: /users\n    endpoints:\n      - method: GET\n        path: /\n        responses:\n          200:\n            content_type: application/json\n            body: '[]'\n  - name: orders\n    server:\n      port: 9002\n      base_path: /orders\n    endpoints:\n      - method: GET\n        path: /\n        responses:\n          200:\n            content_type: application/json\n            body: '[]'\n",
+        )
+        .unwrap();
+        write_valid_service(&dir.path().join("health.yaml"), "health");
+
+        let result = ConfigLoader::new(dir.path().to_path_buf())
+            .load_all_services_with_summary()
+            .unwrap();
+        let mut names: Vec<&str> = result.services.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["health", "orders", "users"]);
+        assert_eq!(result.summary.total_files, 3);
+        assert!(result.summary.is_all_valid());
+    }
+
+    #[test]
+    fn load_service_from_yaml_validates_document() {
+        let yaml = "name: piped\nserver:\n  base_path: /api\nendpoints:\n  - method: GET\n    path: /health\n    responses:\n      200:\n        content_type: application/json\n        body: '{}'\n";
+        let service = ConfigLoader::load_service_from_yaml(yaml, "<stdin>").unwrap();
+        assert_eq!(service.name, "piped");
+
+        let err = ConfigLoader::load_service_from_yaml("name: [", "<stdin>").unwrap_err();
+        assert!(err.to_string().contains("<stdin>"));
+    }
+
+    #[test]
+    fn load_all_services_reports_missing_directory() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("nope");
+        let err = ConfigLoader::new(missing.clone())
+            .load_all_services()
+            .unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "Services directory '{}' does not exist",
+            missing.display()
+        )));
+    }
+
+    #[test]
+    fn load_all_services_reports_empty_directory() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("README.md"), "not a service").unwrap();
+        let loader = ConfigLoader::new(dir.path().to_path_buf());
+        let err = loader.load_all_services().unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "No service YAMLs found in '{}'",
+            loader.get_services_dir().display()
+        )));
+    }
+
+    #[test]
+    fn load_all_services_reports_port_conflicts() {
+        let dir = tempdir().unwrap();
+        let file1 = dir.path().join("a.yaml");
+        let file2 = dir.path().join("b.yaml");
+        for (path, name) in [(&file1, "svc-a"), (&file2, "svc-b")] {
+            let content = format!(
+                "name: {name}\nserver:\n  port: 8080\n  base_path: /api\nendpoints:\n  - method: GET\n    path: /health\n    responses:\n      200:\n        content_type: application/json\n        body: '{{}}'\n"
+            );
+            fs::write(path, content).unwrap();
+        }
+
+        let loader = ConfigLoader::new(dir.path().to_path_buf());
+        let result = loader.load_all_services_with_summary().unwrap();
+        assert_eq!(result.services.len(), 1);
+        assert_eq!(result.summary.errors.len(), 1);
+
+        let error = &result.summary.errors[0];
+        assert_eq!(error.error_type, LoadErrorType::PortConflict);
+        assert!(error.message.contains("Port conflict"));
+        assert!(error.message.contains("8080"));
+        assert!(error.message.contains("a.yaml"));
+        assert!(error.message.contains("b.yaml"));
+    }
+
+    #[test]
+    fn load_all_services_enforces_endpoint_limit() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("big.yaml"),
+            "name: big\nendpoints:\n  - method: GET\n    path: /a\n    responses:\n      200:\n        content_type: application/json\n        body: '{}'\n  - method: GET\n    path: /b\n    responses:\n      200:\n        content_type: application/json\n        body: '{}'\n",
+        )
+        .unwrap();
+
+        let loader = ConfigLoader::new(dir.path().to_path_buf()).with_limits(LoadLimits {
+            max_endpoints_per_service: Some(1),
+            ..LoadLimits::default()
+        });
+        let err = loader.load_all_services().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Service 'big' declares 2 endpoints, exceeding the limit of 1"));
+    }
 }
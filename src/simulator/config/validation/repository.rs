@@ -1,5 +1,5 @@
 use super::super::ServiceDefinition;
-use crate::errors::{ApicentricError, ApicentricResult};
+use crate::errors::{ApicentricError, ApicentricResult, SimulatorError};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -7,6 +7,15 @@ use std::path::{Path, PathBuf};
 pub trait ConfigRepository {
     fn list_service_files(&self) -> ApicentricResult<Vec<PathBuf>>;
     fn load_service(&self, path: &Path) -> ApicentricResult<ServiceDefinition>;
+    /// Load every service in a file: one, or several listed under a
+    /// top-level `services:` key in a combined manifest
+    fn load_services(&self, path: &Path) -> ApicentricResult<Vec<ServiceDefinition>>;
+    /// Load a service with an environment overlay deep-merged onto it
+    fn load_service_with_overlay(
+        &self,
+        path: &Path,
+        overlay: &Path,
+    ) -> ApicentricResult<ServiceDefinition>;
     fn save_service(&self, path: &Path, content: &str) -> ApicentricResult<()>;
     fn delete_service(&self, path: &Path) -> ApicentricResult<()>;
     fn service_exists(&self, path: &Path) -> bool;
@@ -14,6 +23,78 @@ pub trait ConfigRepository {
     fn resolve_path(&self, filename: &str) -> ApicentricResult<PathBuf>;
 }
 
+/// Parse a service definition document; `origin` names its source in errors
+pub(crate) fn parse_service_yaml(
+    content: &str,
+    origin: &str,
+) -> ApicentricResult<ServiceDefinition> {
+    // Use UnifiedConfig to support both standard services and digital twins
+    let unified: super::super::UnifiedConfig =
+        serde_yaml::from_str(content).map_err(|e| invalid_yaml(origin, e))?;
+
+    Ok(ServiceDefinition::from(unified))
+}
+
+/// A service file whose YAML does not parse into a definition
+fn invalid_yaml(origin: &str, error: serde_yaml::Error) -> ApicentricError {
+    SimulatorError::InvalidDefinition {
+        file: PathBuf::from(origin),
+        reason: format!("Invalid YAML: {}", error),
+    }
+    .into()
+}
+
+/// Parse a file that holds either one service or a combined manifest with a
+/// top-level `services:` list
+pub(crate) fn parse_services_yaml(
+    content: &str,
+    origin: &str,
+) -> ApicentricResult<Vec<ServiceDefinition>> {
+    let doc: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| invalid_yaml(origin, e))?;
+    let is_manifest =
+        doc.get("services").is_some_and(|s| s.is_sequence()) && doc.get("name").is_none();
+    if !is_manifest {
+        return Ok(vec![parse_service_yaml(content, origin)?]);
+    }
+
+    let manifest: ServiceManifest = serde_yaml::from_value(doc).map_err(|e| {
+        ApicentricError::from(SimulatorError::InvalidDefinition {
+            file: PathBuf::from(origin),
+            reason: format!("Invalid service in manifest: {}", e),
+        })
+    })?;
+    Ok(manifest
+        .services
+        .into_iter()
+        .map(ServiceDefinition::from)
+        .collect())
+}
+
+/// Several services kept in one file
+#[derive(serde::Deserialize)]
+struct ServiceManifest {
+    services: Vec<super::super::UnifiedConfig>,
+}
+
+/// Deep-merge `overlay` onto `base`: mappings merge key by key, any other
+/// value (including sequences) replaces the base value.
+pub(crate) fn merge_yaml(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 /// Filesystem based implementation of `ConfigRepository`
 #[derive(Clone)]
 pub struct ConfigFileLoader {
@@ -69,8 +150,11 @@ impl ConfigRepository for ConfigFileLoader {
     fn list_service_files(&self) -> ApicentricResult<Vec<PathBuf>> {
         if !self.root.exists() {
             return Err(ApicentricError::config_error(
-                format!("Services directory does not exist: {}", self.root.display()),
-                Some("Create the services directory and add YAML service definition files"),
+                format!(
+                    "Services directory '{}' does not exist",
+                    self.root.display()
+                ),
+                Some("Create the directory or point --services-dir at an existing one"),
             ));
         }
         let mut files = Vec::new();
@@ -86,15 +170,43 @@ impl ConfigRepository for ConfigFileLoader {
             )
         })?;
 
-        // Use UnifiedConfig to support both standard services and digital twins
-        let unified: super::super::UnifiedConfig = serde_yaml::from_str(&content).map_err(|e| {
-            ApicentricError::config_error(
-                format!("Invalid YAML in service file {}: {}", path.display(), e),
-                Some("Check YAML syntax and ensure all required fields are present"),
+        parse_service_yaml(&content, &path.display().to_string())
+    }
+
+    fn load_services(&self, path: &Path) -> ApicentricResult<Vec<ServiceDefinition>> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            ApicentricError::fs_error(
+                format!("Cannot read service file {}: {}", path.display(), e),
+                Some("Check file permissions and ensure the file exists"),
             )
         })?;
 
-        Ok(ServiceDefinition::from(unified))
+        parse_services_yaml(&content, &path.display().to_string())
+    }
+
+    fn load_service_with_overlay(
+        &self,
+        path: &Path,
+        overlay: &Path,
+    ) -> ApicentricResult<ServiceDefinition> {
+        let read_yaml = |file: &Path| -> ApicentricResult<serde_yaml::Value> {
+            let content = fs::read_to_string(file).map_err(|e| {
+                ApicentricError::fs_error(
+                    format!("Cannot read service file {}: {}", file.display(), e),
+                    Some("Check file permissions and ensure the file exists"),
+                )
+            })?;
+            serde_yaml::from_str(&content).map_err(|e| invalid_yaml(&file.display().to_string(), e))
+        };
+        let mut merged = read_yaml(path)?;
+        merge_yaml(&mut merged, read_yaml(overlay)?);
+        let content = serde_yaml::to_string(&merged).map_err(|e| {
+            ApicentricError::config_error(
+                format!("Failed to merge overlay {}: {}", overlay.display(), e),
+                None::<String>,
+            )
+        })?;
+        parse_service_yaml(&content, &overlay.display().to_string())
     }
 
     fn save_service(&self, path: &Path, content: &str) -> ApicentricResult<()> {
@@ -222,6 +334,36 @@ endpoints:
         assert_eq!(svc.name, "test-service");
     }
 
+    #[test]
+    fn array_fixtures_keep_file_order_across_loads_and_overlays() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("service.yaml");
+        let overlay = dir.path().join("service.staging.yaml");
+        fs::write(
+            &file,
+            "name: shop\nfixtures:\n  users: [{id: 3}, {id: 1}, {id: 2}]\n  plans: [gold, free]\n",
+        )
+        .unwrap();
+        fs::write(&overlay, "fixtures:\n  plans: [pro, basic, free]\n").unwrap();
+        let loader = ConfigFileLoader::new(dir.path().to_path_buf());
+
+        let first = loader.load_service(&file).unwrap().fixtures.unwrap();
+        let second = loader.load_service(&file).unwrap().fixtures.unwrap();
+        assert_eq!(
+            first["users"],
+            serde_json::json!([{"id": 3}, {"id": 1}, {"id": 2}])
+        );
+        assert_eq!(first, second);
+
+        let merged = loader
+            .load_service_with_overlay(&file, &overlay)
+            .unwrap()
+            .fixtures
+            .unwrap();
+        assert_eq!(merged["users"], first["users"]);
+        assert_eq!(merged["plans"], serde_json::json!(["pro", "basic", "free"]));
+    }
+
     #[test]
     fn list_service_files_missing_dir() {
         let dir = PathBuf::from("/nonexistent-dir");
@@ -7,6 +7,7 @@ pub enum LoadErrorType {
     Parsing,
     Validation,
     DuplicateName,
+    PortConflict,
 }
 
 /// Detailed error information for service loading
@@ -1,7 +1,11 @@
-use super::super::{BehaviorConfig, ServiceDefinition, SimulatorConfig};
+use super::super::{
+    BehaviorConfig, LatencyDistribution, LoadLimits, ServiceDefinition, SimulatorConfig,
+    TrailingSlashMode,
+};
 use crate::errors::{ApicentricError, ApicentricResult, ValidationError};
 use crate::validation::{ConfigValidator, ValidationUtils};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Validate the basic structure of a service definition
 pub fn validate_service_schema(service: &ServiceDefinition) -> ApicentricResult<()> {
@@ -33,6 +37,109 @@ pub fn validate_unique_name(
     Ok(())
 }
 
+/// Ensure a service stays within the configured endpoint and response size limits
+pub fn validate_service_limits(
+    service: &ServiceDefinition,
+    limits: &LoadLimits,
+) -> ApicentricResult<()> {
+    let endpoints = service.endpoints.as_deref().unwrap_or_default();
+    if let Some(max) = limits.max_endpoints_per_service {
+        if endpoints.len() > max {
+            return Err(ApicentricError::config_error(
+                format!(
+                    "Service '{}' declares {} endpoints, exceeding the limit of {}",
+                    service.name,
+                    endpoints.len(),
+                    max
+                ),
+                Some("Split the service or raise simulator.limits.max_endpoints_per_service"),
+            ));
+        }
+    }
+    if let Some(max) = limits.max_response_bytes {
+        for endpoint in endpoints {
+            for (status, response) in &endpoint.responses {
+                if response.body.len() > max {
+                    return Err(ApicentricError::config_error(
+                        format!(
+                            "Response {} of {} {} in service '{}' is {} bytes, exceeding the limit of {}",
+                            status,
+                            endpoint.method,
+                            endpoint.path,
+                            service.name,
+                            response.body.len(),
+                            max
+                        ),
+                        Some("Shrink the response body or raise simulator.limits.max_response_bytes"),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ensure explicitly configured ports are not claimed by more than one service file
+pub fn validate_unique_port(
+    service: &ServiceDefinition,
+    file: &Path,
+    ports: &mut HashMap<u16, PathBuf>,
+) -> ApicentricResult<()> {
+    let Some(port) = service.server.as_ref().and_then(|s| s.port) else {
+        return Ok(());
+    };
+    if let Some(existing) = ports.get(&port) {
+        return Err(ApicentricError::config_error(
+            format!(
+                "Port conflict: service '{}' in {} and the service in {} both use port {}",
+                service.name,
+                file.display(),
+                existing.display(),
+                port
+            ),
+            Some("Assign a different server.port or omit it to use automatic assignment"),
+        ));
+    }
+    ports.insert(port, file.to_path_buf());
+    Ok(())
+}
+
+/// Endpoints declared twice with paths that differ only by a trailing slash.
+///
+/// Usually an accident, so these are reported as warnings rather than errors;
+/// services that opt into `trailing_slash: { mode: strict }` are not reported.
+pub fn trailing_slash_duplicates(service: &ServiceDefinition) -> Vec<String> {
+    let strict = service
+        .server
+        .as_ref()
+        .and_then(|s| s.trailing_slash.as_ref())
+        .is_some_and(|t| t.mode == TrailingSlashMode::Strict);
+    if strict {
+        return Vec::new();
+    }
+
+    let mut seen: HashMap<(String, &str), &str> = HashMap::new();
+    let mut warnings = Vec::new();
+    for endpoint in service.endpoints.iter().flatten() {
+        let trimmed = endpoint.path.trim_end_matches('/');
+        if trimmed.is_empty() {
+            continue;
+        }
+        let key = (endpoint.method.to_uppercase(), trimmed);
+        match seen.get(&key) {
+            Some(other) if *other != endpoint.path => warnings.push(format!(
+                "Service '{}': {} {} and {} {} differ only by a trailing slash",
+                service.name, key.0, other, key.0, endpoint.path
+            )),
+            Some(_) => {}
+            None => {
+                seen.insert(key, &endpoint.path);
+            }
+        }
+    }
+    warnings
+}
+
 impl ConfigValidator for ServiceDefinition {
     fn validate(&self) -> Result<(), Vec<ValidationError>> {
         let mut errors = Vec::new();
@@ -83,7 +190,51 @@ impl ConfigValidator for ServiceDefinition {
                     });
                 }
 
+                let has_auth = self.server.as_ref().is_some_and(|s| s.auth.is_some());
                 for (i, endpoint) in endpoints.iter().enumerate() {
+                    if endpoint.requires.as_ref().is_some_and(|r| !r.is_empty()) && !has_auth {
+                        errors.push(ValidationError {
+                            field: format!("endpoints[{}].requires", i),
+                            message:
+                                "Endpoint requires scopes but the service has no auth configuration"
+                                    .to_string(),
+                            suggestion: Some(
+                                "Add 'server.auth.jwt_secret' to validate bearer tokens"
+                                    .to_string(),
+                            ),
+                        });
+                    }
+                    if let Some(transition) = &endpoint.transition {
+                        match &self.state_machine {
+                            None => errors.push(ValidationError {
+                                field: format!("endpoints[{}].transition", i),
+                                message: "Endpoint declares a transition but the service has no state machine"
+                                    .to_string(),
+                                suggestion: Some(
+                                    "Add 'state_machine.initial' to the service".to_string(),
+                                ),
+                            }),
+                            Some(machine) => {
+                                let known = machine.states.as_ref();
+                                let unknown: Vec<&String> = transition
+                                    .from
+                                    .iter()
+                                    .chain(std::iter::once(&transition.to))
+                                    .filter(|state| known.is_some_and(|k| !k.contains(state)))
+                                    .collect();
+                                for state in unknown {
+                                    errors.push(ValidationError {
+                                        field: format!("endpoints[{}].transition", i),
+                                        message: format!("Unknown state '{}'", state),
+                                        suggestion: Some(
+                                            "List the state under 'state_machine.states'"
+                                                .to_string(),
+                                        ),
+                                    });
+                                }
+                            }
+                        }
+                    }
                     if let Err(mut endpoint_errors) = endpoint.validate() {
                         for error in &mut endpoint_errors {
                             error.field = format!("endpoints[{}].{}", i, error.field);
@@ -127,6 +278,22 @@ impl ConfigValidator for BehaviorConfig {
                     suggestion: Some("Ensure min_ms <= max_ms".to_string()),
                 });
             }
+            let invalid = match latency.distribution {
+                Some(LatencyDistribution::Normal { stddev_ms, .. }) => stddev_ms < 0.0,
+                Some(LatencyDistribution::Lognormal { median_ms, sigma }) => {
+                    median_ms <= 0.0 || sigma < 0.0
+                }
+                _ => false,
+            };
+            if invalid {
+                errors.push(ValidationError {
+                    field: "behavior.latency.distribution".to_string(),
+                    message: "Latency distribution parameters are out of range".to_string(),
+                    suggestion: Some(
+                        "Use a non-negative stddev_ms/sigma and a positive median_ms".to_string(),
+                    ),
+                });
+            }
         }
 
         if let Some(ref error_sim) = self.error_simulation {
@@ -151,6 +318,16 @@ impl ConfigValidator for BehaviorConfig {
             }
         }
 
+        for pattern in self.redact.iter().flatten() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                errors.push(ValidationError {
+                    field: "behavior.redact".to_string(),
+                    message: format!("Invalid redaction pattern '{}': {}", pattern, e),
+                    suggestion: Some("Use a valid regular expression".to_string()),
+                });
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -207,23 +384,13 @@ mod tests {
     #[test]
     fn duplicate_name_validator_fails() {
         let server = ServerConfig {
-            port: None,
             base_path: "/api".into(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         };
         let endpoint = EndpointDefinition {
-            kind: Default::default(),
             method: "GET".into(),
             path: "/health".into(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
-            responses: std::collections::HashMap::new(),
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         };
         let service = ServiceDefinition {
             name: "svc".into(),
@@ -232,10 +399,13 @@ mod tests {
             server: Some(server),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![endpoint]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
@@ -244,4 +414,79 @@ mod tests {
         let err = validate_unique_name(&service, &mut names).unwrap_err();
         assert!(format!("{}", err).contains("Duplicate"));
     }
+
+    #[test]
+    fn trailing_slash_duplicates_are_reported_unless_strict() {
+        let yaml = r#"
+name: users
+endpoints:
+  - method: GET
+    path: /users
+    responses: {}
+  - method: GET
+    path: /users/
+    responses: {}
+  - method: POST
+    path: /users/
+    responses: {}
+"#;
+        let mut service: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+        let warnings = trailing_slash_duplicates(&service);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains("GET /users and GET /users/"),
+            "{}",
+            warnings[0]
+        );
+
+        service.server =
+            Some(serde_yaml::from_str("base_path: /\ntrailing_slash:\n  mode: strict").unwrap());
+        assert!(trailing_slash_duplicates(&service).is_empty());
+    }
+
+    #[test]
+    fn wildcard_in_the_middle_of_a_path_fails_at_load() {
+        let yaml = r#"
+name: files
+server:
+  base_path: /
+endpoints:
+  - method: GET
+    path: /files/{*path}/meta
+    responses:
+      200:
+        content_type: application/json
+        body: "{}"
+"#;
+        let service: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+        let err = validate_service_schema(&service).unwrap_err().to_string();
+        assert!(
+            err.contains("Wildcard segment '{*path}' must be the last segment"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn unknown_path_parameter_type_fails_at_load() {
+        let yaml = r#"
+name: orders
+server:
+  base_path: /
+endpoints:
+  - method: GET
+    path: /orders/{id:integer}
+    responses:
+      200:
+        content_type: application/json
+        body: "{}"
+"#;
+        let service: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+        let err = validate_service_schema(&service).unwrap_err().to_string();
+        assert!(
+            err.contains("Unknown type 'integer' in path segment '{id:integer}'. Must be one of: int, uuid, string"),
+            "{}",
+            err
+        );
+    }
 }
@@ -0,0 +1,123 @@
+use crate::simulator::config::ServiceDefinition;
+
+/// Render services, their endpoints and proxy dependencies as a Graphviz DOT graph.
+///
+/// A service whose `proxy_base_url` points at another service's local port gets
+/// a dashed `proxy` edge to it; any other proxy target becomes an external node.
+pub fn to_dot(services: &[ServiceDefinition]) -> String {
+    let mut out = String::from("digraph apicentric {\n  rankdir=LR;\n");
+
+    for service in services {
+        let server = service.server.as_ref();
+        let base_path = server.map(|s| s.base_path.as_str()).unwrap_or("/");
+        let label = match server.and_then(|s| s.port) {
+            Some(port) => format!("{}\\n:{}{}", service.name, port, base_path),
+            None => format!("{}\\n{}", service.name, base_path),
+        };
+        out.push_str(&format!(
+            "  {} [shape=box, label={}];\n",
+            quote(&service.name),
+            quote(&label)
+        ));
+
+        for ep in service.endpoints.as_deref().unwrap_or_default() {
+            let endpoint = format!("{} {}", ep.method.to_uppercase(), ep.path);
+            let id = format!("{}:{}", service.name, endpoint);
+            out.push_str(&format!(
+                "  {} [shape=ellipse, label={}];\n  {} -> {};\n",
+                quote(&id),
+                quote(&endpoint),
+                quote(&service.name),
+                quote(&id)
+            ));
+        }
+    }
+
+    for service in services {
+        let Some(target) = service
+            .server
+            .as_ref()
+            .and_then(|s| s.proxy_base_url.as_deref())
+        else {
+            continue;
+        };
+        let node = match proxied_service(target, services) {
+            Some(name) => name.to_string(),
+            None => {
+                out.push_str(&format!("  {} [shape=note];\n", quote(target)));
+                target.to_string()
+            }
+        };
+        out.push_str(&format!(
+            "  {} -> {} [label=\"proxy\", style=dashed];\n",
+            quote(&service.name),
+            quote(&node)
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Name of the service listening on the local port a proxy URL points at.
+fn proxied_service<'a>(target: &str, services: &'a [ServiceDefinition]) -> Option<&'a str> {
+    let url = url::Url::parse(target).ok()?;
+    if !matches!(
+        url.host_str()?,
+        "localhost" | "127.0.0.1" | "0.0.0.0" | "[::1]"
+    ) {
+        return None;
+    }
+    let port = url.port_or_known_default()?;
+    services
+        .iter()
+        .find(|s| s.server.as_ref().and_then(|srv| srv.port) == Some(port))
+        .map(|s| s.name.as_str())
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_has_node_per_service_and_proxy_edge() {
+        let users: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: users
+server:
+  port: 9001
+  base_path: /api
+endpoints:
+  - method: get
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[]'
+"#,
+        )
+        .unwrap();
+        let gateway: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: gateway
+server:
+  port: 9000
+  base_path: /
+  proxy_base_url: http://localhost:9001
+endpoints: []
+"#,
+        )
+        .unwrap();
+
+        let dot = to_dot(&[users, gateway]);
+        assert!(dot.starts_with("digraph apicentric {"));
+        assert!(dot.contains("\"users\" [shape=box, label=\"users\\n:9001/api\"];"));
+        assert!(dot.contains("\"gateway\" [shape=box"));
+        assert!(dot.contains("\"users\" -> \"users:GET /users\";"));
+        assert!(dot.contains("\"gateway\" -> \"users\" [label=\"proxy\", style=dashed];"));
+    }
+}
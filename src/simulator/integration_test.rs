@@ -2,9 +2,7 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::config::{
-        EndpointDefinition, EndpointKind, ResponseDefinition, ServiceDefinition,
-    };
+    use super::super::config::{EndpointDefinition, ResponseDefinition, ServiceDefinition};
     use super::super::config::{PortRange, SimulatorConfig};
     use super::super::log::RequestLogEntry;
     use super::super::manager::ApiSimulatorManager;
@@ -43,6 +41,18 @@ mod tests {
             Ok(None)
         }
 
+        fn save_scenario(
+            &self,
+            _service: &str,
+            _scenario: Option<&str>,
+        ) -> crate::errors::ApicentricResult<()> {
+            Ok(())
+        }
+
+        fn load_scenario(&self, _service: &str) -> crate::errors::ApicentricResult<Option<String>> {
+            Ok(None)
+        }
+
         fn append_log(&self, _entry: &RequestLogEntry) -> crate::errors::ApicentricResult<()> {
             Ok(())
         }
@@ -75,9 +85,7 @@ mod tests {
             server: Some(super::super::config::ServerConfig {
                 port: Some(8100),
                 base_path: "/api/v1".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: {
@@ -91,16 +99,14 @@ mod tests {
                 );
                 Some(fixtures)
             },
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users".to_string(),
-                    header_match: None,
                     description: Some("Get all users".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -111,23 +117,27 @@ mod tests {
                                 body: "{{ fixtures.users }}".to_string(),
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                                 schema: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users/{id}".to_string(),
-                    header_match: None,
                     description: Some("Get user by ID".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(200, ResponseDefinition {
@@ -136,22 +146,26 @@ mod tests {
                             body: r#"{"id": "{{ params.id }}", "message": "User {{ params.id }} found"}"#.to_string(),
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                             schema: None,
                         });
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users/{userId}/orders/{orderId}".to_string(),
-                    header_match: None,
                     description: Some("Get user order".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(200, ResponseDefinition {
@@ -160,17 +174,26 @@ mod tests {
                             body: r#"{"userId": "{{ params.userId }}", "orderId": "{{ params.orderId }}", "status": "found"}"#.to_string(),
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                             schema: None,
                         });
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -184,16 +207,17 @@ mod tests {
             server: Some(super::super::config::ServerConfig {
                 port: Some(8200),
                 base_path: "/api".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(Vec::new()),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -527,6 +551,11 @@ mod tests {
             db_path: temp_dir.path().join("test.db"),
             admin_port: None,
             global_behavior: None,
+            limits: None,
+            env: None,
+            reload_retry_after_secs: None,
+            strict_templates: false,
+            service_history: None,
         };
 
         let manager = ApiSimulatorManager::new(config);
@@ -1,4 +1,6 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 #[cfg(feature = "file-watch")]
@@ -9,9 +11,9 @@ use crate::errors::{ApicentricError, ApicentricResult};
 use crate::simulator::{
     config::{ConfigLoader, ServiceDefinition, SimulatorConfig},
     log::RequestLogEntry,
-    registry::ServiceRegistry,
+    registry::{ServiceRegistry, ServiceTiming},
     route_registry::RouteRegistry,
-    ConfigChange,
+    ConfigChange, SimulatorEvent,
 };
 
 #[cfg(feature = "file-watch")]
@@ -35,6 +37,9 @@ pub struct SimulatorLifecycle<R: RouteRegistry + Send + Sync> {
     #[cfg(feature = "file-watch")]
     pub(crate) config_watcher: Arc<RwLock<Option<ConfigWatcher>>>,
     pub(crate) log_sender: broadcast::Sender<RequestLogEntry>,
+    pub(crate) change_sender: broadcast::Sender<ConfigChange>,
+    pub(crate) event_sender: broadcast::Sender<SimulatorEvent>,
+    forwarding_requests: Arc<AtomicBool>,
 }
 
 impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
@@ -47,6 +52,8 @@ impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
         is_active: Arc<RwLock<bool>>,
         #[cfg(feature = "file-watch")] config_watcher: Arc<RwLock<Option<ConfigWatcher>>>,
         log_sender: broadcast::Sender<RequestLogEntry>,
+        change_sender: broadcast::Sender<ConfigChange>,
+        event_sender: broadcast::Sender<SimulatorEvent>,
     ) -> Self {
         Self {
             config,
@@ -57,6 +64,9 @@ impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
             #[cfg(feature = "file-watch")]
             config_watcher,
             log_sender,
+            change_sender,
+            event_sender,
+            forwarding_requests: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -65,60 +75,52 @@ impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
 impl<R: RouteRegistry + Send + Sync + 'static> Lifecycle for SimulatorLifecycle<R> {
     #[tracing::instrument(skip(self), fields(simulator_enabled = self.config.enabled))]
     async fn start(&self) -> ApicentricResult<()> {
-        if !self.config.enabled {
-            return Err(ApicentricError::config_error(
-                "API simulator is not enabled",
-                Some("Enable in configuration"),
-            ));
-        }
-
-        let mut is_active = self.is_active.write().await;
-        if *is_active {
-            return Err(ApicentricError::runtime_error(
-                "API simulator is already running",
-                None::<String>,
-            ));
-        }
-
         // Load service definitions
         let services = self.config_loader.load_all_services()?;
+        self.start_watching(services).await
+    }
 
-        if services.is_empty() {
-            return Err(ApicentricError::config_error(
-                "No service definitions found",
-                Some("Add YAML service definition files to the services directory"),
-            ));
+    #[tracing::instrument(skip(self))]
+    async fn stop(&self) -> ApicentricResult<()> {
+        let mut is_active = self.is_active.write().await;
+        if !*is_active {
+            return Ok(()); // Already stopped
         }
 
-        // Register and start services
+        // Stop all services
+        let stopping = Instant::now();
         let mut registry = self.service_registry.write().await;
-        let mut router = self.route_registry.write().await;
-
-        for service_def in services {
-            let service_name = service_def.name.clone();
-            let base_path = service_def
-                .server
-                .as_ref()
-                .map(|s| s.base_path.clone())
-                .unwrap_or_else(|| "/".to_string());
-
-            registry.register_service(service_def.clone()).await?;
-            router.register_service(&service_name, &base_path);
+        let running = running_service_names(&registry).await;
+        let timings = registry.stop_all_services().await?;
+        log_timings(&timings, "Service stopped");
+        for name in running {
+            let _ = self
+                .event_sender
+                .send(SimulatorEvent::ServiceStopped { name });
         }
 
-        registry.start_all_services().await?;
-        let service_count = registry.services_count();
-        drop(registry);
-        drop(router);
+        // Clear router mappings
+        let mut router = self.route_registry.write().await;
+        router.clear_all();
 
-        *is_active = true;
+        *is_active = false;
 
         info!(
             target: "simulator",
-            service_count = service_count,
-            "API Simulator started"
+            duration_ms = duration_ms(stopping),
+            "API Simulator stopped"
         );
 
+        Ok(())
+    }
+}
+
+impl<R: RouteRegistry + Send + Sync + 'static> SimulatorLifecycle<R> {
+    /// Start the simulator with definitions already loaded from the services
+    /// directory, then watch that directory for changes
+    pub async fn start_watching(&self, services: Vec<ServiceDefinition>) -> ApicentricResult<()> {
+        self.start_services(services).await?;
+
         #[cfg(feature = "file-watch")]
         {
             // Spawn configuration watcher for automatic reloads
@@ -148,28 +150,6 @@ impl<R: RouteRegistry + Send + Sync + 'static> Lifecycle for SimulatorLifecycle<
 
         Ok(())
     }
-
-    #[tracing::instrument(skip(self))]
-    async fn stop(&self) -> ApicentricResult<()> {
-        let mut is_active = self.is_active.write().await;
-        if !*is_active {
-            return Ok(()); // Already stopped
-        }
-
-        // Stop all services
-        let mut registry = self.service_registry.write().await;
-        registry.stop_all_services().await?;
-
-        // Clear router mappings
-        let mut router = self.route_registry.write().await;
-        router.clear_all();
-
-        *is_active = false;
-
-        info!(target: "simulator", "API Simulator stopped");
-
-        Ok(())
-    }
 }
 
 impl<R: RouteRegistry + Send + Sync> Clone for SimulatorLifecycle<R> {
@@ -183,11 +163,78 @@ impl<R: RouteRegistry + Send + Sync> Clone for SimulatorLifecycle<R> {
             #[cfg(feature = "file-watch")]
             config_watcher: self.config_watcher.clone(),
             log_sender: self.log_sender.clone(),
+            change_sender: self.change_sender.clone(),
+            event_sender: self.event_sender.clone(),
+            forwarding_requests: self.forwarding_requests.clone(),
         }
     }
 }
 
 impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
+    /// Start the simulator with already-loaded service definitions.
+    ///
+    /// Used for definitions that do not come from the services directory
+    /// (e.g. piped on stdin); no directory watcher is installed.
+    pub async fn start_services(&self, services: Vec<ServiceDefinition>) -> ApicentricResult<()> {
+        if !self.config.enabled {
+            return Err(ApicentricError::config_error(
+                "API simulator is not enabled",
+                Some("Enable in configuration"),
+            ));
+        }
+
+        let mut is_active = self.is_active.write().await;
+        if *is_active {
+            return Err(ApicentricError::runtime_error(
+                "API simulator is already running",
+                None::<String>,
+            ));
+        }
+
+        if services.is_empty() {
+            return Err(ApicentricError::config_error(
+                "No service definitions found",
+                Some("Add YAML service definition files to the services directory"),
+            ));
+        }
+
+        // Register and start services
+        let starting = Instant::now();
+        let mut registry = self.service_registry.write().await;
+        let mut router = self.route_registry.write().await;
+
+        for service_def in services {
+            let service_name = service_def.name.clone();
+            let base_path = service_def
+                .server
+                .as_ref()
+                .map(|s| s.base_path.clone())
+                .unwrap_or_else(|| "/".to_string());
+
+            registry.register_service(service_def.clone()).await?;
+            router.register_service(&service_name, &base_path);
+        }
+
+        let timings = registry.start_all_services().await?;
+        log_timings(&timings, "Service started");
+        self.emit_started(&registry).await;
+        let service_count = registry.services_count();
+        drop(registry);
+        drop(router);
+
+        *is_active = true;
+        self.forward_requests();
+
+        info!(
+            target: "simulator",
+            service_count = service_count,
+            duration_ms = duration_ms(starting),
+            "API Simulator started"
+        );
+
+        Ok(())
+    }
+
     pub async fn apply_remote_service(&self, service: ServiceDefinition) -> ApicentricResult<()> {
         let mut registry = self.service_registry.write().await;
         registry.register_service(service.clone()).await?;
@@ -198,37 +245,39 @@ impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
             .map(|s| s.base_path.clone())
             .unwrap_or_else(|| "/".to_string());
         router.register_service(&service.name, &base_path);
-        registry.start_all_services().await?;
+        let timings = registry.start_all_services().await?;
+        log_timings(&timings, "Service started");
         Ok(())
     }
 
     pub async fn handle_config_change(&self, change: ConfigChange) -> ApicentricResult<()> {
-        match change {
-            ConfigChange::ServiceAdded(service_name) => {
-                println!("📁 Service added: {}", service_name);
-            }
-            ConfigChange::ServiceModified(service_name) => {
-                println!("📝 Service modified: {}", service_name);
-            }
-            ConfigChange::ServiceRemoved(service_name) => {
-                println!("🗑️ Service removed: {}", service_name);
-            }
-        }
-
+        info!(target: "simulator", change = ?change, "Services directory changed");
         if *self.is_active.read().await {
             self.reload_services_internal().await?;
         }
 
+        // Only a change the simulator is now serving is reported to subscribers
+        let _ = self.change_sender.send(change);
         Ok(())
     }
 
     pub async fn reload_services_internal(&self) -> ApicentricResult<()> {
+        let reloading = Instant::now();
         let services = self.config_loader.load_all_services()?;
         let mut registry = self.service_registry.write().await;
         let mut router = self.route_registry.write().await;
 
+        let running = running_service_names(&registry).await;
+        registry
+            .drain_all_services(self.config.reload_retry_after_secs.unwrap_or(1))
+            .await;
         registry.clear_all_services().await?;
         router.clear_all();
+        for name in running {
+            let _ = self
+                .event_sender
+                .send(SimulatorEvent::ServiceStopped { name });
+        }
 
         for service_def in services {
             let service_name = service_def.name.clone();
@@ -241,7 +290,81 @@ impl<R: RouteRegistry + Send + Sync> SimulatorLifecycle<R> {
             router.register_service(&service_name, &base_path);
         }
 
-        registry.start_all_services().await?;
+        let timings = registry.start_all_services().await?;
+        log_timings(&timings, "Service started");
+        self.emit_started(&registry).await;
+        let service_count = registry.services_count();
+        let _ = self.event_sender.send(SimulatorEvent::ConfigReloaded {
+            services: service_count,
+        });
+        info!(
+            target: "simulator",
+            service_count = service_count,
+            duration_ms = duration_ms(reloading),
+            "API Simulator reloaded"
+        );
         Ok(())
     }
+
+    /// Publish a `ServiceStarted` event for every running service
+    async fn emit_started(&self, registry: &ServiceRegistry) {
+        for info in registry.list_services().await {
+            if info.is_running {
+                let _ = self.event_sender.send(SimulatorEvent::ServiceStarted {
+                    name: info.name,
+                    port: info.port,
+                });
+            }
+        }
+    }
+
+    /// Relay request log entries as `RequestHandled` events; spawned once per simulator
+    fn forward_requests(&self) {
+        if self.forwarding_requests.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let mut logs = self.log_sender.subscribe();
+        let events = self.event_sender.clone();
+        tokio::spawn(async move {
+            loop {
+                match logs.recv().await {
+                    Ok(entry) => {
+                        let _ = events.send(SimulatorEvent::RequestHandled(entry));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Names of the services currently running in the registry
+async fn running_service_names(registry: &ServiceRegistry) -> Vec<String> {
+    registry
+        .list_services()
+        .await
+        .into_iter()
+        .filter(|info| info.is_running)
+        .map(|info| info.name)
+        .collect()
+}
+
+/// Emit one structured event per service with the time it took
+fn log_timings(timings: &[ServiceTiming], message: &str) {
+    for timing in timings {
+        info!(
+            target: "simulator",
+            service = %timing.name,
+            port = timing.port,
+            duration_ms = timing.duration.as_secs_f64() * 1000.0,
+            "{}",
+            message
+        );
+    }
+}
+
+/// Milliseconds elapsed since `since`, with sub-millisecond precision
+fn duration_ms(since: Instant) -> f64 {
+    since.elapsed().as_secs_f64() * 1000.0
 }
@@ -1,9 +1,12 @@
 //! Request logging utilities for the simulator
 
 use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use crate::errors::{ApicentricError, ApicentricResult};
 use crate::storage::Storage;
 
 /// Individual request log entry
@@ -23,6 +26,9 @@ pub struct RequestLogEntry {
     pub status: u16,
     /// Optional payload (e.g. JSON for telemetry or request body)
     pub payload: Option<String>,
+    /// Time spent handling the request, when measured
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
 }
 
 impl RequestLogEntry {
@@ -43,6 +49,154 @@ impl RequestLogEntry {
             path,
             status,
             payload,
+            duration_ms: None,
+        }
+    }
+}
+
+/// Traffic summary of one endpoint, aggregated from request log entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    pub service: String,
+    pub method: String,
+    /// Endpoint path template, or the request path when no endpoint matched
+    pub path: String,
+    pub count: usize,
+    /// Responses per status class (`2xx`, `4xx`, ...)
+    pub status_classes: BTreeMap<String, usize>,
+    /// Mean handling time over the entries that recorded one
+    pub avg_latency_ms: Option<f64>,
+}
+
+/// Group request log entries per endpoint.
+///
+/// `endpoint_path` resolves a service's endpoint index to its path template;
+/// unmatched requests are grouped by their request path. Internal `SYSTEM`,
+/// `DEBUG` and twin `TICK` entries are skipped.
+pub fn summarize_endpoints(
+    entries: &[RequestLogEntry],
+    endpoint_path: impl Fn(&str, usize) -> Option<String>,
+) -> Vec<EndpointStats> {
+    let mut grouped: BTreeMap<(String, String, String), (EndpointStats, u64, usize)> =
+        BTreeMap::new();
+    for entry in entries {
+        if matches!(entry.method.as_str(), "SYSTEM" | "DEBUG" | "TICK") {
+            continue;
+        }
+        let path = entry
+            .endpoint
+            .and_then(|index| endpoint_path(&entry.service, index))
+            .unwrap_or_else(|| entry.path.clone());
+        let key = (entry.service.clone(), path.clone(), entry.method.clone());
+        let (stats, total_ms, timed) = grouped.entry(key).or_insert_with(|| {
+            (
+                EndpointStats {
+                    service: entry.service.clone(),
+                    method: entry.method.clone(),
+                    path,
+                    count: 0,
+                    status_classes: BTreeMap::new(),
+                    avg_latency_ms: None,
+                },
+                0,
+                0,
+            )
+        });
+        stats.count += 1;
+        *stats
+            .status_classes
+            .entry(format!("{}xx", entry.status / 100))
+            .or_insert(0) += 1;
+        if let Some(ms) = entry.duration_ms {
+            *total_ms += ms;
+            *timed += 1;
+        }
+    }
+    grouped
+        .into_values()
+        .map(|(mut stats, total_ms, timed)| {
+            stats.avg_latency_ms = (timed > 0).then(|| total_ms as f64 / timed as f64);
+            stats
+        })
+        .collect()
+}
+
+/// Render a body for logging without lossy conversion.
+///
+/// UTF-8 bodies are returned as text; anything else becomes `<binary N bytes>`.
+pub fn describe_body(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => format!("<binary {} bytes>", bytes.len()),
+    }
+}
+
+/// Masks sensitive data in log entries before they are stored or broadcast
+#[derive(Debug, Clone, Default)]
+pub struct LogRedactor {
+    patterns: Vec<Regex>,
+}
+
+impl LogRedactor {
+    /// Replacement written over every redacted match
+    pub const MASK: &'static str = "[REDACTED]";
+
+    /// Compile the configured redaction patterns
+    pub fn new(patterns: &[String]) -> ApicentricResult<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p).map_err(|e| {
+                    ApicentricError::config_error(
+                        format!("Invalid redaction pattern '{}': {}", p, e),
+                        Some("Check the regexes listed under behavior.redact"),
+                    )
+                })
+            })
+            .collect::<ApicentricResult<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Mask every match in the entry path and payload
+    pub fn redact(&self, mut entry: RequestLogEntry) -> RequestLogEntry {
+        if self.patterns.is_empty() {
+            return entry;
+        }
+        entry.path = self.mask(&entry.path);
+        entry.payload = entry.payload.map(|p| self.mask(&p));
+        entry
+    }
+
+    fn mask(&self, text: &str) -> String {
+        self.patterns.iter().fold(text.to_string(), |acc, re| {
+            re.replace_all(&acc, Self::MASK).into_owned()
+        })
+    }
+}
+
+/// Filters for querying request logs across all services
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    /// Only entries handled by this service
+    pub service: Option<String>,
+    /// Only entries whose path contains this fragment
+    pub route: Option<String>,
+    /// Only entries with this HTTP method (case-insensitive)
+    pub method: Option<String>,
+    /// Only entries with this response status
+    pub status: Option<u16>,
+    /// Maximum number of entries returned, most recent first
+    pub limit: usize,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            service: None,
+            route: None,
+            method: None,
+            status: None,
+            limit: 100,
         }
     }
 }
@@ -1,20 +1,21 @@
 //! API Simulator Manager - Central coordinator for the simulator functionality
 
-use crate::errors::{ApicentricError, ApicentricResult};
+use crate::errors::{ApicentricError, ApicentricResult, SimulatorError};
 use crate::simulator::{
     admin_server::AdminServer,
     config::{ConfigLoader, ServiceDefinition, SimulatorConfig},
     lifecycle::{Lifecycle, SimulatorLifecycle},
-    log::RequestLogEntry,
+    log::{summarize_endpoints, EndpointStats, LogFilter, RequestLogEntry},
     recording_proxy::{ProxyRecorder, RecordingProxy},
     registry::ServiceRegistry,
     router::RequestRouter,
-    ConfigChange, SimulatorStatus,
+    ConfigChange, SimulatorEvent, SimulatorStatus,
 };
 
 #[cfg(feature = "file-watch")]
 use crate::simulator::watcher::ConfigWatcher;
 use crate::storage::sqlite::SqliteStorage;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
@@ -37,6 +38,8 @@ pub struct ApiSimulatorManager {
     config_loader: ConfigLoader,
     is_active: Arc<RwLock<bool>>,
     log_sender: broadcast::Sender<RequestLogEntry>,
+    change_sender: broadcast::Sender<ConfigChange>,
+    event_sender: broadcast::Sender<SimulatorEvent>,
     lifecycle: SimulatorLifecycle<RequestRouter>,
     recorder: ProxyRecorder,
     admin_server: Arc<RwLock<AdminServer>>,
@@ -47,17 +50,23 @@ impl ApiSimulatorManager {
     /// Create a new API simulator manager
     pub fn new(config: SimulatorConfig) -> Self {
         let start_time = Instant::now();
-        let config_loader = ConfigLoader::new(config.services_dir.clone());
-        let storage = Arc::new(
-            SqliteStorage::init_db(config.db_path.clone())
-                .expect("failed to initialize sqlite storage"),
-        );
+        let config_loader = ConfigLoader::new(config.services_dir.clone())
+            .with_limits(config.limits.clone().unwrap_or_default())
+            .with_env(config.env.clone());
+        let mut storage = SqliteStorage::init_db(config.db_path.clone())
+            .expect("failed to initialize sqlite storage");
+        if let Some(limit) = config.service_history {
+            storage = storage.with_history_limit(limit);
+        }
+        let storage = Arc::new(storage);
         let (log_sender, _) = broadcast::channel(100);
-        let service_registry = Arc::new(RwLock::new(ServiceRegistry::new(
-            config.port_range.clone(),
-            storage,
-            log_sender.clone(),
-        )));
+        let (change_sender, _) = broadcast::channel(16);
+        let (event_sender, _) = broadcast::channel(100);
+        let mut registry =
+            ServiceRegistry::new(config.port_range.clone(), storage, log_sender.clone());
+        registry.set_global_behavior(config.global_behavior.clone());
+        registry.set_strict_templates(config.strict_templates);
+        let service_registry = Arc::new(RwLock::new(registry));
         let route_registry = Arc::new(RwLock::new(RequestRouter::new()));
         let is_active = Arc::new(RwLock::new(false));
 
@@ -73,6 +82,8 @@ impl ApiSimulatorManager {
             #[cfg(feature = "file-watch")]
             config_watcher.clone(),
             log_sender.clone(),
+            change_sender.clone(),
+            event_sender.clone(),
         );
         let recorder = ProxyRecorder;
         let admin_server = Arc::new(RwLock::new(AdminServer::new(service_registry.clone())));
@@ -84,6 +95,8 @@ impl ApiSimulatorManager {
             config_loader,
             is_active,
             log_sender,
+            change_sender,
+            event_sender,
             lifecycle,
             recorder,
             admin_server,
@@ -98,17 +111,86 @@ impl ApiSimulatorManager {
 
     /// Update database path for persistent storage
     pub async fn set_db_path<P: AsRef<std::path::Path>>(&self, path: P) -> ApicentricResult<()> {
-        let storage = Arc::new(SqliteStorage::init_db(path)?);
+        let mut storage = SqliteStorage::init_db(path)?;
+        if let Some(limit) = self.config.service_history {
+            storage = storage.with_history_limit(limit);
+        }
+        let storage = Arc::new(storage);
         let mut reg = self.service_registry.write().await;
         reg.set_storage(storage);
         Ok(())
     }
 
+    /// Inject fixtures into every service registered by subsequent starts and reloads
+    pub async fn set_fixture_overrides(&self, overrides: HashMap<String, serde_json::Value>) {
+        let mut registry = self.service_registry.write().await;
+        registry.set_fixture_overrides(overrides);
+    }
+
     /// Subscribe to log events
     pub fn subscribe_logs(&self) -> broadcast::Receiver<RequestLogEntry> {
         self.log_sender.subscribe()
     }
 
+    /// Subscribe to lifecycle and traffic events
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SimulatorEvent> {
+        self.event_sender.subscribe()
+    }
+
+    /// Subscribe to service configuration changes picked up by the watcher
+    pub fn subscribe_config_changes(&self) -> broadcast::Receiver<ConfigChange> {
+        self.change_sender.subscribe()
+    }
+
+    /// Query request logs across all services
+    pub async fn query_logs(&self, filter: &LogFilter) -> ApicentricResult<Vec<RequestLogEntry>> {
+        let registry = self.service_registry.read().await;
+        registry.storage().query_logs(
+            filter.service.as_deref(),
+            filter.route.as_deref(),
+            filter.method.as_deref(),
+            filter.status,
+            filter.limit,
+        )
+    }
+
+    /// Summarize every logged request per endpoint: counts, status classes and
+    /// average latency
+    pub async fn endpoint_stats(&self) -> ApicentricResult<Vec<EndpointStats>> {
+        let registry = self.service_registry.read().await;
+        let storage = registry.storage();
+        let entries = storage.query_logs(None, None, None, None, i64::MAX as usize)?;
+
+        // Endpoint paths of running services, else of the last stored definition
+        let mut paths: HashMap<String, Vec<String>> = registry
+            .list_services()
+            .await
+            .into_iter()
+            .map(|info| {
+                (
+                    info.name,
+                    info.endpoints.into_iter().map(|e| e.path).collect(),
+                )
+            })
+            .collect();
+        for entry in &entries {
+            if !paths.contains_key(&entry.service) {
+                let stored = storage.load_service(&entry.service).ok().flatten();
+                let endpoint_paths = stored
+                    .and_then(|def| def.endpoints)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| e.path)
+                    .collect();
+                paths.insert(entry.service.clone(), endpoint_paths);
+            }
+        }
+
+        Ok(summarize_endpoints(&entries, |service, index| {
+            paths.get(service)?.get(index).cloned()
+        }))
+    }
+
     /// Start the API simulator
     pub async fn start(&self) -> ApicentricResult<()> {
         if let Some(port) = self.config.admin_port {
@@ -118,6 +200,32 @@ impl ApiSimulatorManager {
         self.lifecycle.start().await
     }
 
+    /// Start the API simulator with in-memory service definitions instead of
+    /// loading the services directory
+    pub async fn start_with_services(
+        &self,
+        services: Vec<ServiceDefinition>,
+    ) -> ApicentricResult<()> {
+        if let Some(port) = self.config.admin_port {
+            let mut admin_server = self.admin_server.write().await;
+            admin_server.start(port).await;
+        }
+        self.lifecycle.start_services(services).await
+    }
+
+    /// Start the API simulator with services already loaded from the services
+    /// directory, which is watched for changes as with `start`
+    pub async fn start_loaded_services(
+        &self,
+        services: Vec<ServiceDefinition>,
+    ) -> ApicentricResult<()> {
+        if let Some(port) = self.config.admin_port {
+            let mut admin_server = self.admin_server.write().await;
+            admin_server.start(port).await;
+        }
+        self.lifecycle.start_watching(services).await
+    }
+
     /// Stop the API simulator
     pub async fn stop(&self) -> ApicentricResult<()> {
         if self.config.admin_port.is_some() {
@@ -168,10 +276,19 @@ impl ApiSimulatorManager {
     /// Set the active scenario for all services
     pub async fn set_scenario(&self, scenario: Option<String>) -> ApicentricResult<()> {
         let registry = self.service_registry.read().await;
-        registry.set_scenario_all(scenario).await;
+        registry.set_scenario_all(scenario.clone()).await;
+        let _ = self
+            .event_sender
+            .send(SimulatorEvent::ScenarioChanged(scenario));
         Ok(())
     }
 
+    /// Reset fixtures and clear runtime data for every service
+    pub async fn reset_all(&self) {
+        let registry = self.service_registry.read().await;
+        registry.reset_all().await;
+    }
+
     /// Get current simulator status
     pub async fn get_status(&self) -> SimulatorStatus {
         let is_active = *self.is_active.read().await;
@@ -203,9 +320,21 @@ impl ApiSimulatorManager {
     }
 
     /// Run a reverse proxy that records requests/responses.
-    pub async fn record(&self, target: &str, output_dir: PathBuf) -> ApicentricResult<()> {
+    ///
+    /// With `infer_schemas`, JSON bodies are summarised as schemas in `models`.
+    pub async fn record(
+        &self,
+        target: &str,
+        output_dir: PathBuf,
+        infer_schemas: bool,
+    ) -> ApicentricResult<()> {
         self.recorder
-            .record(target, output_dir, self.config.port_range.start)
+            .record(
+                target,
+                output_dir,
+                self.config.port_range.start,
+                infer_schemas,
+            )
             .await
     }
 
@@ -250,12 +379,13 @@ impl ApiSimulatorManager {
                 service = %service_name,
                 "Service started"
             );
+            let _ = self.event_sender.send(SimulatorEvent::ServiceStarted {
+                name: service_name.to_string(),
+                port: service.port(),
+            });
             Ok(())
         } else {
-            Err(ApicentricError::runtime_error(
-                format!("Service '{}' not found", service_name),
-                Some("Check that the service is registered"),
-            ))
+            Err(SimulatorError::ServiceNotFound(service_name.to_string()).into())
         }
     }
 
@@ -279,12 +409,12 @@ impl ApiSimulatorManager {
                 service = %service_name,
                 "Service stopped"
             );
+            let _ = self.event_sender.send(SimulatorEvent::ServiceStopped {
+                name: service_name.to_string(),
+            });
             Ok(())
         } else {
-            Err(ApicentricError::runtime_error(
-                format!("Service '{}' not found", service_name),
-                Some("Check that the service is registered"),
-            ))
+            Err(SimulatorError::ServiceNotFound(service_name.to_string()).into())
         }
     }
 
@@ -379,6 +509,14 @@ impl ApiSimulatorManager {
         Ok(())
     }
 
+    /// Load the configured directory, keeping the valid services and a summary
+    /// of the files that failed to load
+    pub fn load_services_with_summary(
+        &self,
+    ) -> ApicentricResult<crate::simulator::config::LoadResult> {
+        self.config_loader.load_all_services_with_summary()
+    }
+
     /// Save a service definition to a file
     pub fn save_service_file(&self, path: &std::path::Path, content: &str) -> ApicentricResult<()> {
         self.config_loader.save_service(path, content)
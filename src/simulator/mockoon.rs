@@ -5,7 +5,7 @@ use std::path::Path;
 use serde::Deserialize;
 
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig, ServiceDefinition,
+    EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
 };
 
 #[derive(Debug, Deserialize)]
@@ -55,9 +55,7 @@ fn convert(env: &MockoonEnvironment) -> ServiceDefinition {
     let server = ServerConfig {
         port: Some(env.port),
         base_path,
-        proxy_base_url: None,
-        cors: None,
-        record_unknown: false,
+        ..Default::default()
     };
 
     let endpoints = env
@@ -84,25 +82,28 @@ fn convert(env: &MockoonEnvironment) -> ServiceDefinition {
                     } else {
                         Some(headers)
                     },
+                    trailers: None,
+                    examples: None,
+                    record_delay_ms: None,
+                    pretty_json: None,
+                    sparse_fields: false,
+                    serve_times: None,
+                    exec: None,
                     side_effects: None,
+                    body_base64: None,
+                    body_file: None,
                 };
                 responses.insert(resp.status_code, response);
             }
             EndpointDefinition {
-                kind: EndpointKind::Http,
                 method: r.method.to_uppercase(),
                 path: if r.endpoint.starts_with('/') {
                     r.endpoint.clone()
                 } else {
                     format!("/{}", r.endpoint)
                 },
-                header_match: None,
-                description: None,
-                parameters: None,
-                request_body: None,
                 responses,
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             }
         })
         .collect();
@@ -114,10 +115,13 @@ fn convert(env: &MockoonEnvironment) -> ServiceDefinition {
         server: Some(server),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
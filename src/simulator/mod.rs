@@ -5,11 +5,13 @@
 pub mod admin_server;
 pub mod axios_client;
 pub mod config;
+pub mod graph;
 pub mod lifecycle;
 pub mod log;
 pub mod manager;
 pub mod marketplace;
 pub mod mockoon;
+pub mod msw;
 pub mod openapi;
 pub mod postman;
 pub mod react_query;
@@ -18,6 +20,7 @@ pub mod recording_proxy;
 pub mod registry;
 pub mod route_registry;
 pub mod router;
+pub mod schema_inference;
 pub mod scripting;
 pub mod service;
 pub mod soap;
@@ -31,7 +34,7 @@ pub mod wiremock;
 pub use config::{
     EndpointDefinition, ResponseDefinition, ServiceDefinition, SimulatorConfig, UnifiedConfig,
 };
-pub use log::RequestLogEntry;
+pub use log::{EndpointStats, LogFilter, RequestLogEntry};
 pub use manager::ApiSimulatorManager;
 pub use registry::ServiceRegistry;
 pub use router::RequestRouter;
@@ -43,6 +46,16 @@ pub enum ConfigChange {
     ServiceRemoved(String),
 }
 
+/// Lifecycle and traffic events published to embedders via `subscribe_events`
+#[derive(Debug, Clone)]
+pub enum SimulatorEvent {
+    ServiceStarted { name: String, port: u16 },
+    ServiceStopped { name: String },
+    RequestHandled(RequestLogEntry),
+    ScenarioChanged(Option<String>),
+    ConfigReloaded { services: usize },
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SimulatorStatus {
     pub is_active: bool,
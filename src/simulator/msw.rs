@@ -0,0 +1,130 @@
+use serde_json::Value;
+
+use crate::simulator::config::{EndpointKind, ResponseDefinition, ServiceDefinition};
+
+/// Generate a Mock Service Worker `handlers.ts` module for a service definition.
+///
+/// Each HTTP endpoint becomes an `http.<method>(...)` handler returning the body
+/// of its lowest 2xx response (or the lowest status declared).
+pub fn to_msw_handlers(service: &ServiceDefinition) -> String {
+    let base_path = service
+        .server
+        .as_ref()
+        .map(|s| s.base_path.clone())
+        .unwrap_or_else(|| "/".to_string());
+
+    let mut out = String::new();
+    out.push_str("import { http, HttpResponse } from 'msw';\n\n");
+    out.push_str("export const handlers = [\n");
+
+    for ep in service.endpoints.as_deref().unwrap_or_default() {
+        if ep.kind != EndpointKind::Http {
+            continue;
+        }
+        let method = match ep.method.to_lowercase().as_str() {
+            m @ ("get" | "post" | "put" | "patch" | "delete" | "head" | "options") => m.to_string(),
+            _ => "all".to_string(),
+        };
+        let path = msw_path(&base_path, &ep.path);
+        let response = match example_response(&ep.responses) {
+            Some((status, resp)) => render_response(status, resp),
+            None => "new HttpResponse(null, { status: 204 })".to_string(),
+        };
+        out.push_str(&format!(
+            "  http.{method}('{path}', () => {{\n    return {response};\n  }}),\n"
+        ));
+    }
+
+    out.push_str("];\n");
+    out
+}
+
+fn example_response(
+    responses: &std::collections::HashMap<u16, ResponseDefinition>,
+) -> Option<(u16, &ResponseDefinition)> {
+    responses
+        .iter()
+        .filter(|(status, _)| (200..300).contains(*status))
+        .min_by_key(|(status, _)| **status)
+        .or_else(|| responses.iter().min_by_key(|(status, _)| **status))
+        .map(|(status, resp)| (*status, resp))
+}
+
+fn render_response(status: u16, resp: &ResponseDefinition) -> String {
+    if resp.content_type.contains("json") {
+        if let Ok(json) = serde_json::from_str::<Value>(&resp.body) {
+            return format!("HttpResponse.json({json}, {{ status: {status} }})");
+        }
+    }
+    let body = serde_json::to_string(&resp.body).unwrap_or_else(|_| "''".to_string());
+    let content_type =
+        serde_json::to_string(&resp.content_type).unwrap_or_else(|_| "''".to_string());
+    format!(
+        "new HttpResponse({body}, {{ status: {status}, headers: {{ 'Content-Type': {content_type} }} }})"
+    )
+}
+
+/// Join the base path and endpoint path, converting `{param}` segments to `:param`.
+fn msw_path(base_path: &str, path: &str) -> String {
+    let full = format!(
+        "{}/{}",
+        base_path.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+    full.split('/')
+        .map(|seg| {
+            if seg.starts_with('{') && seg.ends_with('}') {
+                format!(":{}", &seg[1..seg.len() - 1])
+            } else {
+                seg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_handler_per_endpoint() {
+        let yaml = r#"
+name: users
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[{"id": 1}]'
+  - method: POST
+    path: /users
+    responses:
+      400:
+        content_type: application/json
+        body: '{"error": "invalid"}'
+      201:
+        content_type: application/json
+        body: '{"id": "{{faker "uuid"}}"}'
+  - method: DELETE
+    path: /users/{id}
+    responses:
+      204:
+        content_type: text/plain
+        body: ''
+"#;
+        let service: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+        let ts = to_msw_handlers(&service);
+
+        assert!(ts.starts_with("import { http, HttpResponse } from 'msw';"));
+        assert!(ts.contains("http.get('/api/users', () => {"));
+        assert!(ts.contains("HttpResponse.json([{\"id\":1}], { status: 200 })"));
+        assert!(ts.contains("http.post('/api/users', () => {"));
+        assert!(ts.contains("status: 201"));
+        assert!(ts.contains("http.delete('/api/users/:id', () => {"));
+        assert_eq!(ts.matches("  http.").count(), 3);
+    }
+}
@@ -12,8 +12,8 @@ use serde_yaml::Value;
 use url::Url;
 
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ParameterDefinition, ParameterLocation,
-    RequestBodyDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
+    EndpointDefinition, ParameterDefinition, ParameterLocation, RequestBodyDefinition,
+    ResponseDefinition, ServerConfig, ServiceDefinition,
 };
 
 /// Convert an OpenAPI/Swagger spec into a `ServiceDefinition` used by the simulator
@@ -77,11 +77,8 @@ fn convert_openapi3(doc: &OpenApi3Document) -> ServiceDefinition {
         .unwrap_or_else(|| "/".to_string());
 
     let server = ServerConfig {
-        port: None,
         base_path,
-        proxy_base_url: None,
-        cors: None,
-        record_unknown: false,
+        ..Default::default()
     };
 
     let mut endpoints = Vec::new();
@@ -123,6 +120,7 @@ fn convert_openapi3(doc: &OpenApi3Document) -> ServiceDefinition {
                             required: body.required,
                             schema: schema_ref,
                             content_type: Some(content_type.to_string()),
+                            validate: false,
                         }
                     })
                 });
@@ -163,7 +161,16 @@ fn convert_openapi3(doc: &OpenApi3Document) -> ServiceDefinition {
                                     schema,
                                     script: None,
                                     headers: None,
+                                    trailers: None,
+                                    examples: None,
+                                    record_delay_ms: None,
+                                    pretty_json: None,
+                                    sparse_fields: false,
+                                    serve_times: None,
+                                    exec: None,
                                     side_effects: None,
+                                    body_base64: None,
+                                    body_file: None,
                                 },
                             );
                         }
@@ -171,16 +178,13 @@ fn convert_openapi3(doc: &OpenApi3Document) -> ServiceDefinition {
                 }
 
                 endpoints.push(EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: method.to_uppercase(),
                     path: path.clone(),
-                    header_match: None,
                     description: op.summary.clone().or(op.description.clone()),
                     parameters,
                     request_body,
                     responses: responses_map,
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 });
             }
         }
@@ -217,10 +221,13 @@ fn convert_openapi3(doc: &OpenApi3Document) -> ServiceDefinition {
         server: Some(server),
         models,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -573,21 +580,26 @@ fn convert_swagger2(doc: &Swagger2Document) -> ServiceDefinition {
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
 
                 endpoints.push(EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: method.to_uppercase(),
                     path: path.clone(),
                     description: op.summary.clone().or(op.description.clone()),
-                    header_match: None,
-                    parameters: None,   // Simplification for now
-                    request_body: None, // Simplification for now
                     responses,
-                    scenarios: None,
-                    stream: None,
+                    // Parameters and request bodies are not converted for now
+                    ..Default::default()
                 });
             }
         }
@@ -598,18 +610,18 @@ fn convert_swagger2(doc: &Swagger2Document) -> ServiceDefinition {
         version: Some(doc.info.version.clone()),
         description: None,
         server: Some(ServerConfig {
-            port: None,
             base_path,
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None, // Simplification
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -5,7 +5,7 @@ use std::path::Path;
 use serde_json::{json, Value};
 
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig, ServiceDefinition,
+    EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition,
 };
 
 /// Parse a Postman or Insomnia collection from a `serde_json::Value` into a [`ServiceDefinition`]
@@ -49,18 +49,18 @@ fn convert_postman(v: &Value) -> ServiceDefinition {
         version: None,
         description: None,
         server: Some(ServerConfig {
-            port: None,
             base_path: "/".into(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         #[cfg(feature = "iot")]
         twin: None,
@@ -116,7 +116,16 @@ fn collect_postman_items(items: &[Value], endpoints: &mut Vec<EndpointDefinition
                             schema: None,
                             script: None,
                             headers,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     );
                 }
@@ -131,22 +140,26 @@ fn collect_postman_items(items: &[Value], endpoints: &mut Vec<EndpointDefinition
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
             }
 
             endpoints.push(EndpointDefinition {
-                kind: EndpointKind::Http,
                 method,
                 path,
-                header_match: None,
                 description: desc,
-                parameters: None,
-                request_body: None,
                 responses,
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             });
         } else if let Some(sub) = item.get("item").and_then(|i| i.as_array()) {
             collect_postman_items(sub, endpoints);
@@ -225,20 +238,24 @@ fn convert_insomnia(v: &Value) -> ServiceDefinition {
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
                 endpoints.push(EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method,
                     path,
-                    header_match: None,
                     description: desc,
-                    parameters: None,
-                    request_body: None,
                     responses,
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 });
             }
         }
@@ -249,18 +266,18 @@ fn convert_insomnia(v: &Value) -> ServiceDefinition {
         version: None,
         description: None,
         server: Some(ServerConfig {
-            port: None,
             base_path: "/".into(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(endpoints),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -4,10 +4,13 @@ use crate::simulator::config::{EndpointDefinition, EndpointKind, ServiceDefiniti
 /// Generate React Query hooks for a service definition.
 ///
 /// Each HTTP endpoint becomes either a `useQuery` (for GET) or `useMutation`
-/// (for other methods) hook.
+/// (for other methods) hook. `PATCH` mutations send a partial body and
+/// `DELETE` mutations optimistically remove the item from the list cache.
 pub fn generate_react_query_hooks(service: &ServiceDefinition) -> ApicentricResult<String> {
     let mut out = String::new();
-    out.push_str("import { useQuery, useMutation } from '@tanstack/react-query';\n\n");
+    out.push_str(
+        "import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';\n\n",
+    );
 
     let endpoints = service.endpoints.as_ref().cloned().unwrap_or_default();
 
@@ -24,10 +27,11 @@ pub fn generate_react_query_hooks(service: &ServiceDefinition) -> ApicentricResu
         if ep.kind != EndpointKind::Http {
             continue;
         }
-        if ep.method.eq_ignore_ascii_case("GET") {
-            out.push_str(&generate_query_hook(ep, &base_path));
-        } else {
-            out.push_str(&generate_mutation_hook(ep, &base_path));
+        match ep.method.to_uppercase().as_str() {
+            "GET" => out.push_str(&generate_query_hook(ep, &base_path)),
+            "PATCH" => out.push_str(&generate_patch_hook(ep, &base_path)),
+            "DELETE" => out.push_str(&generate_delete_hook(ep, &base_path)),
+            _ => out.push_str(&generate_mutation_hook(ep, &base_path)),
         }
         out.push('\n');
     }
@@ -65,6 +69,55 @@ fn generate_mutation_hook(ep: &EndpointDefinition, base_path: &str) -> String {
     )
 }
 
+fn generate_patch_hook(ep: &EndpointDefinition, base_path: &str) -> String {
+    let hook_name = hook_name(ep, false);
+    let params = path_params(&ep.path);
+    let param_list = format_params(&params);
+    let url = build_url(base_path, &ep.path, &params);
+    format!(
+        "export function {hook}(baseUrl: string{param_list}) {{\n    const queryClient = useQueryClient();\n    return useMutation((patch: Record<string, unknown>) =>\n        fetch(`${{baseUrl}}{url}`, {{ method: 'PATCH', headers: {{ 'Content-Type': 'application/json' }}, body: JSON.stringify(patch) }}).then(res => res.json()),\n        {{ onSuccess: () => queryClient.invalidateQueries(['GET','{list_path}']) }}\n    );\n}}\n",
+        hook = hook_name,
+        url = url,
+        list_path = list_path(&ep.path),
+        param_list = param_list,
+    )
+}
+
+fn generate_delete_hook(ep: &EndpointDefinition, base_path: &str) -> String {
+    let hook_name = hook_name(ep, false);
+    let params = path_params(&ep.path);
+    let param_list = format_params(&params);
+    let url = build_url(base_path, &ep.path, &params);
+    let list_key = format!("['GET','{}']", list_path(&ep.path));
+    // The last path parameter identifies the item to drop from the cached list
+    let remove = match params.last() {
+        Some(id) => format!(
+            "\n                queryClient.setQueryData({list_key}, (old: any) => Array.isArray(old) ? old.filter((item: any) => String(item?.id) !== String({id})) : old);"
+        ),
+        None => String::new(),
+    };
+    format!(
+        "export function {hook}(baseUrl: string{param_list}) {{\n    const queryClient = useQueryClient();\n    return useMutation(() =>\n        fetch(`${{baseUrl}}{url}`, {{ method: 'DELETE' }}).then(res => {{ if (!res.ok) throw new Error(`DELETE failed: ${{res.status}}`); }}),\n        {{\n            onMutate: async () => {{\n                await queryClient.cancelQueries({list_key});\n                const previous = queryClient.getQueryData({list_key});{remove}\n                return {{ previous }};\n            }},\n            onError: (_err: unknown, _vars: unknown, context: any) => queryClient.setQueryData({list_key}, context?.previous),\n            onSettled: () => queryClient.invalidateQueries({list_key}),\n        }}\n    );\n}}\n",
+        hook = hook_name,
+        url = url,
+        list_key = list_key,
+        remove = remove,
+        param_list = param_list,
+    )
+}
+
+/// Path of the collection an item endpoint belongs to (`/users/{id}` -> `/users`).
+fn list_path(path: &str) -> String {
+    let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    while segments
+        .last()
+        .is_some_and(|s| s.starts_with('{') && s.ends_with('}'))
+    {
+        segments.pop();
+    }
+    format!("/{}", segments.join("/"))
+}
+
 fn hook_name(ep: &EndpointDefinition, is_query: bool) -> String {
     let path_part = ep
         .path
@@ -128,7 +181,7 @@ fn capitalize(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::simulator::config::{EndpointDefinition, EndpointKind, ServerConfig};
+    use crate::simulator::config::{EndpointDefinition, ServerConfig};
 
     #[test]
     fn generates_query_and_mutation() {
@@ -137,43 +190,29 @@ mod tests {
             version: None,
             description: None,
             server: Some(ServerConfig {
-                port: None,
                 base_path: "/api".into(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".into(),
                     path: "/pets".into(),
-                    header_match: None,
-                    description: None,
-                    parameters: None,
-                    request_body: None,
-                    responses: Default::default(),
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "POST".into(),
                     path: "/pets".into(),
-                    header_match: None,
-                    description: None,
-                    parameters: None,
-                    request_body: None,
-                    responses: Default::default(),
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
@@ -182,4 +221,50 @@ mod tests {
         assert!(ts.contains("usePostPetsMutation"));
         assert!(ts.contains("/api/pets"));
     }
+
+    fn endpoint(method: &str, path: &str) -> EndpointDefinition {
+        EndpointDefinition {
+            method: method.into(),
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn generates_patch_and_delete_mutations() {
+        let service = ServiceDefinition {
+            name: "Users".into(),
+            version: None,
+            description: None,
+            server: Some(ServerConfig {
+                base_path: "/api".into(),
+                ..Default::default()
+            }),
+            models: None,
+            fixtures: None,
+            fixture_sort: None,
+            bucket: None,
+            partials: None,
+            endpoints: Some(vec![
+                endpoint("GET", "/users"),
+                endpoint("PATCH", "/users/{id}"),
+                endpoint("DELETE", "/users/{id}"),
+            ]),
+            graphql: None,
+            behavior: None,
+            state_machine: None,
+            #[cfg(feature = "iot")]
+            twin: None,
+        };
+        let ts = generate_react_query_hooks(&service).unwrap();
+        assert!(
+            ts.contains("export function useDeleteUsersIdMutation(baseUrl: string, id: string)")
+        );
+        assert!(ts.contains("fetch(`${baseUrl}/api/users/${id}`, { method: 'DELETE' })"));
+        assert!(ts.contains("queryClient.setQueryData(['GET','/users'], (old: any)"));
+        assert!(ts.contains("String(item?.id) !== String(id)"));
+        assert!(ts.contains("export function usePatchUsersIdMutation(baseUrl: string, id: string)"));
+        assert!(ts.contains("method: 'PATCH'"));
+        assert!(ts.contains("(patch: Record<string, unknown>)"));
+    }
 }
@@ -176,43 +176,29 @@ mod tests {
             version: None,
             description: None,
             server: Some(ServerConfig {
-                port: None,
                 base_path: "/api".into(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".into(),
                     path: "/users".into(),
-                    header_match: None,
-                    description: None,
-                    parameters: None,
-                    request_body: None,
-                    responses: Default::default(),
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "POST".into(),
                     path: "/users".into(),
-                    header_match: None,
-                    description: None,
-                    parameters: None,
-                    request_body: None,
-                    responses: Default::default(),
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::Infallible;
+use std::future::Future;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -78,14 +79,24 @@ use tokio::sync::Mutex;
 
 use crate::errors::{ApicentricError, ApicentricResult};
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ParameterDefinition, ParameterLocation, ResponseDefinition,
-    ServerConfig, ServiceDefinition,
+    EndpointDefinition, ParameterDefinition, ParameterLocation, RequestBodyDefinition,
+    ResponseDefinition, ServerConfig, ServiceDefinition,
 };
+use crate::simulator::schema_inference::{infer_schema, merge_schemas};
 
 /// Trait for recording traffic through a proxy.
 #[async_trait(?Send)]
 pub trait RecordingProxy {
-    async fn record(&self, target: &str, output_dir: PathBuf, port: u16) -> ApicentricResult<()>;
+    /// Proxy `target` until interrupted, then write the recorded service.
+    ///
+    /// With `infer_schemas`, JSON bodies are also turned into `models`.
+    async fn record(
+        &self,
+        target: &str,
+        output_dir: PathBuf,
+        port: u16,
+        infer_schemas: bool,
+    ) -> ApicentricResult<()>;
 }
 
 /// Default implementation of [`RecordingProxy`].
@@ -93,7 +104,33 @@ pub struct ProxyRecorder;
 
 #[async_trait(?Send)]
 impl RecordingProxy for ProxyRecorder {
-    async fn record(&self, target: &str, output_dir: PathBuf, port: u16) -> ApicentricResult<()> {
+    async fn record(
+        &self,
+        target: &str,
+        output_dir: PathBuf,
+        port: u16,
+        infer_schemas: bool,
+    ) -> ApicentricResult<()> {
+        let shutdown = async {
+            let _ = tokio::signal::ctrl_c().await;
+        };
+        self.record_until(target, output_dir, port, infer_schemas, shutdown)
+            .await
+            .map(|_| ())
+    }
+}
+
+impl ProxyRecorder {
+    /// Proxy `target` until `shutdown` completes, then write the recorded
+    /// service and return the path of the generated YAML.
+    pub async fn record_until(
+        &self,
+        target: &str,
+        output_dir: PathBuf,
+        port: u16,
+        infer_schemas: bool,
+        shutdown: impl Future<Output = ()>,
+    ) -> ApicentricResult<PathBuf> {
         let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
         // Create a custom TLS configuration that ignores certificate validation errors
@@ -111,6 +148,8 @@ impl RecordingProxy for ProxyRecorder {
         let client: Client<_, Full<Bytes>> = Client::builder(TokioExecutor::new()).build(https);
         let endpoints: Arc<Mutex<HashMap<(String, String), EndpointDefinition>>> =
             Arc::new(Mutex::new(HashMap::new()));
+        let models: Arc<Mutex<BTreeMap<String, serde_json::Value>>> =
+            Arc::new(Mutex::new(BTreeMap::new()));
         let listener = TcpListener::bind(addr).await.map_err(|e| {
             ApicentricError::runtime_error(
                 format!("Failed to bind recording proxy: {}", e),
@@ -123,6 +162,7 @@ impl RecordingProxy for ProxyRecorder {
             addr, target
         );
 
+        tokio::pin!(shutdown);
         loop {
             tokio::select! {
                 res = listener.accept() => {
@@ -131,11 +171,13 @@ impl RecordingProxy for ProxyRecorder {
                     let client = client.clone();
                     let target = target.to_string();
                     let endpoints = endpoints.clone();
+                    let models = models.clone();
                     tokio::spawn(async move {
                         let service = service_fn(move |req: Request<Incoming>| {
                             let client = client.clone();
                             let target = target.clone();
                             let endpoints = endpoints.clone();
+                            let models = models.clone();
                             async move {
                                 let method = req.method().clone();
                                 let headers = req.headers().clone();
@@ -193,7 +235,7 @@ impl RecordingProxy for ProxyRecorder {
                                     .to_string();
                                 {
                                     let mut map = endpoints.lock().await;
-                                    upsert_recorded_endpoint(
+                                    let endpoint = upsert_recorded_endpoint(
                                         &mut map,
                                         &method,
                                         &path,
@@ -202,6 +244,19 @@ impl RecordingProxy for ProxyRecorder {
                                         String::from_utf8_lossy(&resp_bytes).into(),
                                         &parts.headers,
                                     );
+                                    if infer_schemas {
+                                        let request_content_type = headers
+                                            .get(hyper::header::CONTENT_TYPE)
+                                            .and_then(|v| v.to_str().ok());
+                                        record_schemas(
+                                            &mut *models.lock().await,
+                                            endpoint,
+                                            parts.status.as_u16(),
+                                            request_content_type,
+                                            &req_body,
+                                            &resp_bytes,
+                                        );
+                                    }
                                 }
 
                                 let mut client_resp: Response<Full<Bytes>> =
@@ -216,30 +271,31 @@ impl RecordingProxy for ProxyRecorder {
                         }
                     });
                 },
-                _ = tokio::signal::ctrl_c() => {
+                _ = &mut shutdown => {
                     break;
                 }
             }
         }
 
         let map = endpoints.lock().await;
+        let models = models.lock().await;
         let service = ServiceDefinition {
             name: "recorded_service".to_string(),
             version: None,
             description: Some("Recorded service".to_string()),
             server: Some(ServerConfig {
-                port: None,
                 base_path: "/".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
-            models: None,
+            models: (!models.is_empty()).then(|| models.clone()),
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(map.values().cloned().collect()),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
@@ -264,38 +320,32 @@ impl RecordingProxy for ProxyRecorder {
             )
         })?;
         println!("\u{2705} Recorded interactions saved to {}", path.display());
-        Ok(())
+        Ok(path)
     }
 }
 
 const ORIGINAL_PATH_PARAMS_HEADER: &str = "x-apicentric-recorded-path-params";
 
-fn upsert_recorded_endpoint(
-    map: &mut HashMap<(String, String), EndpointDefinition>,
+fn upsert_recorded_endpoint<'a>(
+    map: &'a mut HashMap<(String, String), EndpointDefinition>,
     method: &hyper::Method,
     path: &str,
     status: u16,
     content_type: &str,
     body: String,
     headers: &HeaderMap,
-) {
+) -> &'a mut EndpointDefinition {
     let (normalized_path, parameter_defs, recorded_values) = parameterize_path(path);
     let key = (method.to_string(), normalized_path.clone());
     let entry = map.entry(key).or_insert_with(|| EndpointDefinition {
-        kind: EndpointKind::Http,
         method: method.to_string(),
         path: normalized_path.clone(),
-        header_match: None,
-        description: None,
         parameters: if parameter_defs.is_empty() {
             None
         } else {
             Some(parameter_defs.clone())
         },
-        request_body: None,
-        responses: HashMap::new(),
-        scenarios: None,
-        stream: None,
+        ..Default::default()
     });
 
     entry.path = normalized_path;
@@ -330,9 +380,74 @@ fn upsert_recorded_endpoint(
             schema: None,
             script: None,
             headers: headers_option,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
         },
     );
+    entry
+}
+
+/// Merge the JSON request and response bodies of one exchange into `models`
+/// and point the endpoint at the resulting schemas. Non-JSON bodies are ignored.
+fn record_schemas(
+    models: &mut BTreeMap<String, serde_json::Value>,
+    endpoint: &mut EndpointDefinition,
+    status: u16,
+    request_content_type: Option<&str>,
+    request_body: &[u8],
+    response_body: &[u8],
+) {
+    let mut observe = |name: String, body: &[u8]| {
+        let value = serde_json::from_slice::<serde_json::Value>(body).ok()?;
+        let inferred = infer_schema(&value);
+        let merged = match models.get(&name) {
+            Some(existing) => merge_schemas(existing, &inferred),
+            None => inferred,
+        };
+        models.insert(name.clone(), merged);
+        Some(name)
+    };
+
+    let base = model_base_name(&endpoint.method, &endpoint.path);
+    if let Some(name) = observe(format!("{}Request", base), request_body) {
+        endpoint.request_body = Some(RequestBodyDefinition {
+            required: true,
+            schema: Some(name),
+            content_type: request_content_type.map(str::to_string),
+            validate: false,
+        });
+    }
+    if let Some(name) = observe(format!("{}Response{}", base, status), response_body) {
+        if let Some(response) = endpoint.responses.get_mut(&status) {
+            response.schema = Some(name);
+        }
+    }
+}
+
+/// `GET /users/{param1}` becomes `GetUsersParam1`
+fn model_base_name(method: &str, path: &str) -> String {
+    std::iter::once(method.to_ascii_lowercase())
+        .chain(
+            path.split(|c: char| !c.is_ascii_alphanumeric())
+                .map(str::to_string),
+        )
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
 }
 
 fn parameterize_path(path: &str) -> (String, Vec<ParameterDefinition>, Vec<(String, String)>) {
@@ -523,6 +638,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn record_schemas_merges_observed_responses_into_models() {
+        let mut map: HashMap<(String, String), EndpointDefinition> = HashMap::new();
+        let mut models = BTreeMap::new();
+        let samples = [
+            ("/users/123", r#"{"id":123,"name":"Ada","email":null}"#),
+            (
+                "/users/456",
+                r#"{"id":456,"name":"Bob","email":"bob@example.com","admin":true}"#,
+            ),
+        ];
+        for (path, body) in samples {
+            let endpoint = upsert_recorded_endpoint(
+                &mut map,
+                &Method::GET,
+                path,
+                200,
+                "application/json",
+                body.to_string(),
+                &HeaderMap::new(),
+            );
+            record_schemas(&mut models, endpoint, 200, None, b"", body.as_bytes());
+        }
+
+        assert_eq!(
+            models.get("GetUsersParam1Response200"),
+            Some(&serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "admin": { "type": "boolean" },
+                    "email": { "type": ["string", "null"] },
+                    "id": { "type": "integer" },
+                    "name": { "type": "string" }
+                },
+                "required": ["email", "id", "name"]
+            }))
+        );
+        let endpoint = map.values().next().unwrap();
+        assert_eq!(
+            endpoint.responses[&200].schema.as_deref(),
+            Some("GetUsersParam1Response200")
+        );
+        assert!(endpoint.request_body.is_none());
+    }
+
     #[tokio::test]
     async fn replay_uses_parameterized_template() {
         let mut map: HashMap<(String, String), EndpointDefinition> = HashMap::new();
@@ -554,18 +714,18 @@ mod tests {
             version: None,
             description: None,
             server: Some(ServerConfig {
-                port: None,
                 base_path: "/".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(map.values().cloned().collect()),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         };
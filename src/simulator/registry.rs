@@ -1,17 +1,27 @@
 //! Service Registry - Manages multiple service instances and their lifecycles
 
-use crate::errors::{ApicentricError, ApicentricResult};
+use crate::errors::{ApicentricError, ApicentricResult, SimulatorError};
 use crate::simulator::{
-    config::{PortRange, ServiceDefinition},
+    config::{BehaviorConfig, PortRange, ServiceDefinition},
     log::RequestLogEntry,
     service::ServiceInstance,
     ServiceInfo,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
 use tokio::sync::{broadcast, RwLock};
 use tracing::info;
 
+/// How long a single service took to bind or shut down
+#[derive(Debug, Clone)]
+pub struct ServiceTiming {
+    pub name: String,
+    pub port: u16,
+    pub duration: Duration,
+}
+
 /// Port manager for automatic port assignment
 pub struct PortManager {
     port_range: PortRange,
@@ -31,10 +41,7 @@ impl PortManager {
         if let Some(port) = requested_port {
             // Use requested port if available
             if self.used_ports.contains(&port) {
-                return Err(ApicentricError::runtime_error(
-                    format!("Port {} is already in use", port),
-                    Some("Choose a different port or let the system assign one automatically"),
-                ));
+                return Err(SimulatorError::PortInUse(port).into());
             }
             self.used_ports.push(port);
             Ok(port)
@@ -73,6 +80,11 @@ pub struct ServiceRegistry {
     port_manager: PortManager,
     storage: Arc<dyn crate::storage::Storage>,
     log_sender: broadcast::Sender<RequestLogEntry>,
+    fixture_overrides: HashMap<String, serde_json::Value>,
+    global_behavior: Option<BehaviorConfig>,
+    strict_templates: bool,
+    /// Sockets of drained services, reused by the next start on the same port
+    parked_listeners: HashMap<u16, Arc<TcpListener>>,
 }
 
 impl ServiceRegistry {
@@ -87,6 +99,10 @@ impl ServiceRegistry {
             port_manager: PortManager::new(port_range),
             storage,
             log_sender,
+            fixture_overrides: HashMap::new(),
+            global_behavior: None,
+            strict_templates: false,
+            parked_listeners: HashMap::new(),
         }
     }
 
@@ -94,13 +110,50 @@ impl ServiceRegistry {
         self.storage = storage;
     }
 
+    /// Fixtures injected into every service registered from now on,
+    /// replacing fixtures of the same name from the definition
+    pub fn set_fixture_overrides(&mut self, overrides: HashMap<String, serde_json::Value>) {
+        self.fixture_overrides = overrides;
+    }
+
+    /// Behavior inherited by registered services for settings they leave unset
+    pub fn set_global_behavior(&mut self, behavior: Option<BehaviorConfig>) {
+        self.global_behavior = behavior;
+    }
+
+    /// Make templates of every service registered from now on strict
+    pub fn set_strict_templates(&mut self, strict: bool) {
+        self.strict_templates = strict;
+    }
+
     /// Register a new service
     pub async fn register_service(
         &mut self,
-        definition: ServiceDefinition,
+        mut definition: ServiceDefinition,
     ) -> ApicentricResult<()> {
         let service_name = definition.name.clone();
 
+        if !self.fixture_overrides.is_empty() {
+            definition
+                .fixtures
+                .get_or_insert_with(HashMap::new)
+                .extend(self.fixture_overrides.clone());
+        }
+
+        if let Some(global) = &self.global_behavior {
+            definition.behavior = Some(BehaviorConfig::resolve(
+                None,
+                definition.behavior.as_ref(),
+                Some(global),
+            ));
+        }
+
+        if self.strict_templates {
+            if let Some(server) = definition.server.as_mut() {
+                server.strict_templates = true;
+            }
+        }
+
         if self.services.contains_key(&service_name) {
             return Err(ApicentricError::runtime_error(
                 format!("Service '{}' is already registered", service_name),
@@ -156,10 +209,7 @@ impl ServiceRegistry {
             log::info!("Unregistered service '{}'", service_name);
             Ok(())
         } else {
-            Err(ApicentricError::runtime_error(
-                format!("Service '{}' is not registered", service_name),
-                None::<String>,
-            ))
+            Err(SimulatorError::ServiceNotFound(service_name.to_string()).into())
         }
     }
 
@@ -189,16 +239,56 @@ impl ServiceRegistry {
         }
     }
 
+    /// Reset fixtures and clear runtime data of every registered service
+    pub async fn reset_all(&self) {
+        for service_arc in self.services.values() {
+            let service = service_arc.read().await;
+            service.reset_fixtures().await;
+            service.clear_runtime_data().await;
+        }
+    }
+
+    /// Put every running service into draining mode ahead of a reload.
+    ///
+    /// Requests get a 503 with `Retry-After` until the service is stopped, and
+    /// its socket stays bound so the replacement started on the same port picks
+    /// up queued connections instead of clients being refused.
+    pub async fn drain_all_services(&mut self, retry_after_secs: u64) {
+        for service_arc in self.services.values() {
+            let service = service_arc.read().await;
+            if !service.is_running() {
+                continue;
+            }
+            if let Some(listener) = service.begin_draining(retry_after_secs) {
+                self.parked_listeners.insert(service.port(), listener);
+            }
+        }
+    }
+
     /// Start all registered services
-    pub async fn start_all_services(&mut self) -> ApicentricResult<()> {
+    ///
+    /// Returns how long each service that came up took to bind.
+    pub async fn start_all_services(&mut self) -> ApicentricResult<Vec<ServiceTiming>> {
         let mut errors = Vec::new();
+        let mut timings = Vec::new();
 
         for (service_name, service_arc) in &self.services {
             let mut service = service_arc.write().await;
-            if let Err(e) = service.start().await {
-                errors.push(format!("Failed to start service '{}': {}", service_name, e));
+            if let Some(listener) = self.parked_listeners.remove(&service.port()) {
+                service.adopt_listener(listener);
+            }
+            let started = Instant::now();
+            match service.start().await {
+                Ok(()) => timings.push(ServiceTiming {
+                    name: service_name.clone(),
+                    port: service.port(),
+                    duration: started.elapsed(),
+                }),
+                Err(e) => errors.push(format!("Failed to start service '{}': {}", service_name, e)),
             }
         }
+        // Sockets of services that did not come back are released
+        self.parked_listeners.clear();
 
         if !errors.is_empty() {
             return Err(ApicentricError::runtime_error(
@@ -208,18 +298,29 @@ impl ServiceRegistry {
         }
 
         log::info!("Started {} services", self.services.len());
-        Ok(())
+        Ok(timings)
     }
 
     /// Stop all registered services
-    pub async fn stop_all_services(&mut self) -> ApicentricResult<()> {
+    ///
+    /// Returns how long each running service took to shut down.
+    pub async fn stop_all_services(&mut self) -> ApicentricResult<Vec<ServiceTiming>> {
         let mut errors = Vec::new();
+        let mut timings = Vec::new();
 
         for (service_name, service_arc) in &self.services {
             let mut service = service_arc.write().await;
             if service.is_running() {
-                if let Err(e) = service.stop().await {
-                    errors.push(format!("Failed to stop service '{}': {}", service_name, e));
+                let stopping = Instant::now();
+                match service.stop().await {
+                    Ok(()) => timings.push(ServiceTiming {
+                        name: service_name.clone(),
+                        port: service.port(),
+                        duration: stopping.elapsed(),
+                    }),
+                    Err(e) => {
+                        errors.push(format!("Failed to stop service '{}': {}", service_name, e))
+                    }
                 }
             }
         }
@@ -232,7 +333,7 @@ impl ServiceRegistry {
         }
 
         log::info!("Stopped {} services", self.services.len());
-        Ok(())
+        Ok(timings)
     }
 
     /// Get the number of registered services
@@ -283,9 +384,7 @@ impl ServiceRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::simulator::config::{
-        EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig,
-    };
+    use crate::simulator::config::{EndpointDefinition, ResponseDefinition, ServerConfig};
     use std::collections::HashMap;
 
     fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefinition {
@@ -296,21 +395,16 @@ mod tests {
             server: Some(ServerConfig {
                 port,
                 base_path: format!("/api/{}", name),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![EndpointDefinition {
-                kind: EndpointKind::Http,
                 method: "GET".to_string(),
                 path: "/test".to_string(),
-                header_match: None,
-                description: None,
-                parameters: None,
-                request_body: None,
                 responses: {
                     let mut responses = HashMap::new();
                     responses.insert(
@@ -321,17 +415,26 @@ mod tests {
                             body: r#"{"message": "test"}"#.to_string(),
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                             schema: None,
                         },
                     );
                     responses
                 },
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             }]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -0,0 +1,240 @@
+//! Infer JSON Schemas from observed request and response bodies.
+//!
+//! Used by record mode to fill a service's `models` so generated OpenAPI
+//! documents carry real schemas. Every observation is inferred on its own and
+//! merged into what was seen before: properties missing from some samples stop
+//! being required, `null` adds `"null"` to a schema's `type`, integers widen to numbers
+//! and otherwise incompatible types become an `anyOf`.
+
+use serde_json::{json, Map, Value};
+
+/// Infer a schema describing a single JSON value
+pub fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let mut schema = json!({ "type": "array" });
+            if let Some(items) = items
+                .iter()
+                .map(infer_schema)
+                .reduce(|a, b| merge_schemas(&a, &b))
+            {
+                schema["items"] = items;
+            }
+            schema
+        }
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), infer_schema(value)))
+                .collect();
+            let required: Vec<Value> = fields.keys().cloned().map(Value::String).collect();
+            json!({ "type": "object", "properties": properties, "required": required })
+        }
+    }
+}
+
+/// Merge two inferred schemas into one accepting both observations
+pub fn merge_schemas(a: &Value, b: &Value) -> Value {
+    if a == b {
+        return a.clone();
+    }
+    let (a, a_null) = without_null(a);
+    let (b, b_null) = without_null(b);
+    let merged = match (a, b) {
+        (Some(a), Some(b)) => merge_non_null(&a, &b),
+        (Some(schema), None) | (None, Some(schema)) => schema,
+        (None, None) => return json!({ "type": "null" }),
+    };
+    if a_null || b_null {
+        nullable(merged)
+    } else {
+        merged
+    }
+}
+
+fn merge_non_null(a: &Value, b: &Value) -> Value {
+    if a == b {
+        return a.clone();
+    }
+    match (schema_type(a), schema_type(b)) {
+        (Some("object"), Some("object")) => merge_objects(a, b),
+        (Some("array"), Some("array")) => {
+            let mut schema = json!({ "type": "array" });
+            let items = match (a.get("items"), b.get("items")) {
+                (Some(x), Some(y)) => Some(merge_schemas(x, y)),
+                (x, y) => x.or(y).cloned(),
+            };
+            if let Some(items) = items {
+                schema["items"] = items;
+            }
+            schema
+        }
+        (Some("integer"), Some("number")) | (Some("number"), Some("integer")) => {
+            json!({ "type": "number" })
+        }
+        (Some(x), Some(y)) if x == y => json!({ "type": x }),
+        _ => any_of(a, b),
+    }
+}
+
+fn schema_type(schema: &Value) -> Option<&str> {
+    schema.get("type").and_then(Value::as_str)
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema_type(schema) == Some("null")
+}
+
+/// Split a schema into the part describing non-null values and whether it
+/// also accepts `null`; a schema accepting only `null` has no such part.
+fn without_null(schema: &Value) -> (Option<Value>, bool) {
+    if is_null(schema) {
+        return (None, true);
+    }
+    let mut schema = schema.clone();
+    let mut accepts_null = false;
+    if let Some(types) = schema.get("type").and_then(Value::as_array) {
+        let rest: Vec<Value> = types
+            .iter()
+            .filter(|t| t.as_str() != Some("null"))
+            .cloned()
+            .collect();
+        accepts_null = rest.len() < types.len();
+        schema["type"] = match <[Value; 1]>::try_from(rest) {
+            Ok([single]) => single,
+            Err(rest) => Value::Array(rest),
+        };
+    }
+    if let Some(variants) = schema.get_mut("anyOf").and_then(Value::as_array_mut) {
+        let before = variants.len();
+        variants.retain(|variant| !is_null(variant));
+        accepts_null |= variants.len() < before;
+    }
+    (Some(schema), accepts_null)
+}
+
+/// Widen a schema to also accept `null`, the JSON Schema way: `"null"` joins
+/// the `type` list (or the `anyOf` variants).
+fn nullable(mut schema: Value) -> Value {
+    if let Some(variants) = schema.get_mut("anyOf").and_then(Value::as_array_mut) {
+        if !variants.iter().any(is_null) {
+            variants.push(json!({ "type": "null" }));
+        }
+        return schema;
+    }
+    match schema.get("type") {
+        Some(Value::String(t)) if t != "null" => {
+            schema["type"] = json!([t.clone(), "null"]);
+        }
+        Some(Value::Array(types)) if !types.iter().any(|t| t.as_str() == Some("null")) => {
+            let mut types = types.clone();
+            types.push(json!("null"));
+            schema["type"] = Value::Array(types);
+        }
+        _ => {}
+    }
+    schema
+}
+
+fn merge_objects(a: &Value, b: &Value) -> Value {
+    let empty = Map::new();
+    let props_a = a
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let props_b = b
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut properties = props_a.clone();
+    for (name, schema) in props_b {
+        let merged = match properties.get(name) {
+            Some(existing) => merge_schemas(existing, schema),
+            None => schema.clone(),
+        };
+        properties.insert(name.clone(), merged);
+    }
+
+    let required_in = |schema: &Value, name: &str| {
+        schema
+            .get("required")
+            .and_then(Value::as_array)
+            .is_some_and(|r| r.iter().any(|n| n.as_str() == Some(name)))
+    };
+    let required: Vec<Value> = properties
+        .keys()
+        .filter(|name| required_in(a, name) && required_in(b, name))
+        .cloned()
+        .map(Value::String)
+        .collect();
+
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Combine differing schemas, merging variants that share a type
+fn any_of(a: &Value, b: &Value) -> Value {
+    let variants = |schema: &Value| match schema.get("anyOf").and_then(Value::as_array) {
+        Some(list) => list.clone(),
+        None => vec![schema.clone()],
+    };
+    let mut merged = variants(a);
+    for variant in variants(b) {
+        match merged
+            .iter_mut()
+            .find(|existing| schema_type(existing) == schema_type(&variant))
+        {
+            Some(existing) => *existing = merge_schemas(existing, &variant),
+            None => merged.push(variant),
+        }
+    }
+    json!({ "anyOf": merged })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widens_numbers_and_falls_back_to_any_of() {
+        let ints = infer_schema(&json!([1, 2]));
+        assert_eq!(
+            ints,
+            json!({ "type": "array", "items": { "type": "integer" } })
+        );
+
+        let mixed = infer_schema(&json!([1, 2.5, null]));
+        assert_eq!(
+            mixed,
+            json!({ "type": "array", "items": { "type": ["number", "null"] } })
+        );
+
+        let merged = merge_schemas(&infer_schema(&json!("a")), &infer_schema(&json!(true)));
+        assert_eq!(
+            merged,
+            json!({ "anyOf": [{ "type": "string" }, { "type": "boolean" }] })
+        );
+    }
+
+    #[test]
+    fn inferred_nullable_fields_accept_null_values() {
+        let schema = merge_schemas(
+            &infer_schema(&json!({ "email": "ada@example.com" })),
+            &infer_schema(&json!({ "email": null })),
+        );
+        assert_eq!(
+            schema["properties"]["email"],
+            json!({ "type": ["string", "null"] })
+        );
+
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        assert!(validator.is_valid(&json!({ "email": null })));
+        assert!(validator.is_valid(&json!({ "email": "bob@example.com" })));
+        assert!(!validator.is_valid(&json!({ "email": 42 })));
+    }
+}
@@ -1,19 +1,88 @@
 use crate::errors::ApicentricResult;
 use serde_json::Value;
+use std::time::Duration;
 
 #[cfg(feature = "scripting")]
-use rhai::{Engine, Scope, AST};
+use crate::errors::ApicentricError;
+#[cfg(feature = "scripting")]
+use rhai::{Dynamic, Engine, EvalAltResult, Scope, AST};
+#[cfg(feature = "scripting")]
+use std::cell::Cell;
 #[cfg(feature = "scripting")]
 use std::collections::HashMap;
 #[cfg(feature = "scripting")]
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "scripting")]
+use std::sync::{Arc, Mutex, OnceLock};
+#[cfg(feature = "scripting")]
+use std::time::Instant;
+
+/// How long a script may run before it is aborted, unless configured
+pub const DEFAULT_SCRIPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[cfg(feature = "scripting")]
+thread_local! {
+    /// When the script evaluating on this thread must stop. A script runs on
+    /// one thread from start to end, so concurrent scripts never share it.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// A complete HTTP response returned by a script as `#{ status, headers, body }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl ScriptResponse {
+    /// Read a script result holding a valid HTTP `status`; string bodies are
+    /// sent as-is and anything else as JSON
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let status = value
+            .get("status")?
+            .as_u64()
+            .and_then(|status| u16::try_from(status).ok())
+            .filter(|status| (100..=999).contains(status))?;
+        let headers = value
+            .get("headers")
+            .and_then(Value::as_object)
+            .map(|headers| {
+                headers
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let body = match value.get("body") {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        };
+        Some(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+}
 
 /// A thread-safe scripting engine for simulation logic
 pub struct ScriptingEngine {
     #[cfg(feature = "scripting")]
-    engine: Arc<Mutex<Engine>>,
+    engine: Arc<Engine>,
     #[cfg(feature = "scripting")]
     cache: Arc<Mutex<HashMap<String, AST>>>,
+    /// Whether `fetch` may reach the network
+    #[cfg(feature = "scripting")]
+    network: Arc<AtomicBool>,
+    timeout: Duration,
 }
 
 impl Default for ScriptingEngine {
@@ -34,32 +103,67 @@ impl ScriptingEngine {
             engine.register_fn("print", |s: &str| println!("Script print: {}", s));
             engine.register_fn("now", || chrono::Utc::now().to_rfc3339());
 
+            // Abort runaway scripts such as `loop {}` once the deadline passes
+            engine.on_progress(|operations| {
+                if operations % 1024 != 0 {
+                    return None;
+                }
+                DEADLINE
+                    .get()
+                    .filter(|deadline| Instant::now() >= *deadline)
+                    .map(|_| Dynamic::UNIT)
+            });
+
+            let network = Arc::new(AtomicBool::new(false));
+            register_fetch(&mut engine, Arc::clone(&network));
+
             Self {
-                engine: Arc::new(Mutex::new(engine)),
+                engine: Arc::new(engine),
                 cache: Arc::new(Mutex::new(HashMap::new())),
+                network,
+                timeout: DEFAULT_SCRIPT_TIMEOUT,
             }
         }
         #[cfg(not(feature = "scripting"))]
         {
-            Self {}
+            Self {
+                timeout: DEFAULT_SCRIPT_TIMEOUT,
+            }
         }
     }
 
+    /// Abort scripts that run longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Let scripts call remote services with `fetch`
+    #[allow(unused_mut, unused_variables)]
+    pub fn with_network(mut self, allowed: bool) -> Self {
+        #[cfg(feature = "scripting")]
+        self.network.store(allowed, Ordering::Relaxed);
+        self
+    }
+
     /// Execute a script in the context of a request
     #[allow(unused_variables)]
     pub fn execute(&self, script: &str, context: &Value) -> ApicentricResult<Value> {
         #[cfg(feature = "scripting")]
         {
-            let engine = self.engine.lock().unwrap();
-            let mut cache = self.cache.lock().unwrap();
-
-            // Compile or retrieve from cache
-            let ast = if let Some(ast) = cache.get(script) {
-                ast.clone()
-            } else {
-                let ast = engine.compile(script)?;
-                cache.insert(script.to_string(), ast.clone());
-                ast
+            // Neither lock is held while the script runs, so one slow script
+            // does not hold up the others
+            let cached = self.cache.lock().unwrap().get(script).cloned();
+            let ast = match cached {
+                Some(ast) => ast,
+                None => {
+                    let ast = self.engine.compile(script)?;
+                    self.cache
+                        .lock()
+                        .unwrap()
+                        .insert(script.to_string(), ast.clone());
+                    ast
+                }
             };
 
             // Create scope with context
@@ -69,7 +173,18 @@ impl ScriptingEngine {
 
             // Execute
             // Explicitly specify Dynamic as the return type
-            let result = engine.eval_ast_with_scope::<rhai::Dynamic>(&mut scope, &ast)?;
+            DEADLINE.set(Some(Instant::now() + self.timeout));
+            let result = self
+                .engine
+                .eval_ast_with_scope::<rhai::Dynamic>(&mut scope, &ast);
+            DEADLINE.set(None);
+            let result = result.map_err(|err| match *err {
+                EvalAltResult::ErrorTerminated(..) => ApicentricError::scripting_error(
+                    format!("Script aborted after {}ms", self.timeout.as_millis()),
+                    Some("Check the script for endless loops or raise server.script_timeout_ms"),
+                ),
+                _ => ApicentricError::from(err),
+            })?;
 
             // Convert result back to JSON value
             let json_val: Value = rhai::serde::from_dynamic(&result)?;
@@ -83,6 +198,90 @@ impl ScriptingEngine {
     }
 }
 
+/// Register `fetch(url)` and `fetch(url, #{ method, headers, body })`.
+///
+/// The call blocks the script until the upstream answers or the script's
+/// deadline passes, and yields `#{ status, headers, body }` with JSON bodies
+/// parsed. It fails unless the service allows script network access.
+#[cfg(feature = "scripting")]
+fn register_fetch(engine: &mut Engine, network: Arc<AtomicBool>) {
+    let fetch = move |url: &str, options: rhai::Map| -> Result<Dynamic, Box<EvalAltResult>> {
+        if !network.load(Ordering::Relaxed) {
+            return Err("fetch is disabled; set server.allow_script_network: true".into());
+        }
+        // Every fetch draws on what is left of the script's own deadline
+        let timeout = DEADLINE.get().map_or(DEFAULT_SCRIPT_TIMEOUT, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        });
+        let options: Value = rhai::serde::from_dynamic(&Dynamic::from_map(options))?;
+        let response = fetch_blocking(url, &options, timeout)?;
+        rhai::serde::to_dynamic(response)
+    };
+    let fetch_url = fetch.clone();
+    engine.register_fn("fetch", fetch);
+    engine.register_fn("fetch", move |url: &str| fetch_url(url, rhai::Map::new()));
+}
+
+/// Client shared by every script `fetch`, created on first use
+#[cfg(feature = "scripting")]
+fn fetch_client() -> &'static reqwest::blocking::Client {
+    static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::blocking::Client::new)
+}
+
+/// Send one request, waiting at most `timeout` for the whole exchange.
+/// Scripts run off the async workers, so blocking here stalls no other request.
+#[cfg(feature = "scripting")]
+fn fetch_blocking(url: &str, options: &Value, timeout: Duration) -> Result<Value, String> {
+    if timeout.is_zero() {
+        return Err("fetch called after the script deadline".to_string());
+    }
+    let method = options
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or("GET")
+        .parse::<reqwest::Method>()
+        .map_err(|e| format!("Invalid fetch method: {}", e))?;
+    let mut request = fetch_client().request(method, url).timeout(timeout);
+    for (name, value) in options
+        .get("headers")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        request = request.header(name.as_str(), value);
+    }
+    request = match options.get("body") {
+        None | Some(Value::Null) => request,
+        Some(Value::String(body)) => request.body(body.clone()),
+        Some(body) => request.json(body),
+    };
+
+    let response = request
+        .send()
+        .map_err(|e| format!("fetch {} failed: {}", url, e))?;
+    let status = response.status().as_u16();
+    let headers: serde_json::Map<String, Value> = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                name.to_string(),
+                Value::String(value.to_str().ok()?.to_string()),
+            ))
+        })
+        .collect();
+    let text = response
+        .text()
+        .map_err(|e| format!("fetch {} failed: {}", url, e))?;
+    let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+    Ok(serde_json::json!({ "status": status, "headers": headers, "body": body }))
+}
+
 #[cfg(test)]
 #[cfg(feature = "scripting")]
 mod tests {
@@ -132,4 +331,29 @@ mod tests {
         let result = engine.execute(script, &context).unwrap();
         assert!(result.is_string());
     }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn fetch_is_refused_unless_network_is_allowed() {
+        let engine = ScriptingEngine::new();
+        let err = engine
+            .execute(r#"fetch("http://127.0.0.1:9/")"#, &json!({}))
+            .unwrap_err();
+        assert!(err.to_string().contains("allow_script_network"), "{err}");
+    }
+
+    #[test]
+    fn a_running_script_does_not_block_others() {
+        let engine = Arc::new(ScriptingEngine::new().with_timeout(Duration::from_millis(500)));
+        let slow = {
+            let engine = Arc::clone(&engine);
+            std::thread::spawn(move || engine.execute("loop {}", &json!({})))
+        };
+        std::thread::sleep(Duration::from_millis(50));
+
+        let started = Instant::now();
+        assert_eq!(engine.execute("1 + 1", &json!({})).unwrap(), 2);
+        assert!(started.elapsed() < Duration::from_millis(250));
+        assert!(slow.join().unwrap().is_err());
+    }
 }
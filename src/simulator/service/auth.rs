@@ -0,0 +1,53 @@
+use crate::auth::jwt::{claim_scopes, validate_token_claims, JwtKeys};
+use crate::simulator::config::AuthConfig;
+use hyper::StatusCode;
+use subtle::ConstantTimeEq;
+
+fn bearer_token(authorization: Option<&str>) -> Option<&str> {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Check a request's `Authorization` header against the scopes an endpoint requires.
+///
+/// Returns 401 when the bearer token is missing or fails validation (or the
+/// service has no `auth` block) and 403 when it lacks a required scope.
+pub fn authorize(
+    auth: Option<&AuthConfig>,
+    authorization: Option<&str>,
+    requires: &[String],
+) -> Result<(), StatusCode> {
+    let token = bearer_token(authorization).ok_or(StatusCode::UNAUTHORIZED)?;
+    let auth = auth.ok_or(StatusCode::UNAUTHORIZED)?;
+    let claims = validate_token_claims(token, &JwtKeys::from_secret(&auth.jwt_secret))
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let granted = claim_scopes(&claims, auth.scope_claim.as_deref().unwrap_or("scope"));
+    if requires.iter().all(|scope| granted.contains(scope)) {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// Whether a request may read the internal `/__apicentric/*` endpoints.
+///
+/// They stay open unless `APICENTRIC_ADMIN_TOKEN` or a JWT `auth` block is
+/// configured; then the bearer token must be the admin token or a valid JWT.
+pub fn internal_access_allowed(
+    auth: Option<&AuthConfig>,
+    admin_token: Option<&str>,
+    authorization: Option<&str>,
+) -> bool {
+    if auth.is_none() && admin_token.is_none() {
+        return true;
+    }
+    let Some(token) = bearer_token(authorization) else {
+        return false;
+    };
+    admin_token.is_some_and(|expected| bool::from(token.as_bytes().ct_eq(expected.as_bytes())))
+        || auth.is_some_and(|auth| {
+            validate_token_claims(token, &JwtKeys::from_secret(&auth.jwt_secret)).is_ok()
+        })
+}
@@ -0,0 +1,74 @@
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::EndpointDefinition;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// A response body read from disk when the service is created
+#[derive(Debug, Clone)]
+pub struct BodyFile {
+    /// Decompressed contents, used like an inline `body`
+    pub text: String,
+    /// The file's bytes when it was stored gzip-compressed, served as-is to
+    /// clients that accept gzip
+    pub gzip: Option<Bytes>,
+}
+
+/// Bodies of every `body_file` referenced by the endpoints, keyed by path
+pub type BodyFiles = HashMap<PathBuf, BodyFile>;
+
+/// Read every `body_file` referenced by the endpoints' responses and scenarios
+pub fn load_all(endpoints: &[EndpointDefinition]) -> ApicentricResult<BodyFiles> {
+    let responses = endpoints.iter().flat_map(|endpoint| {
+        endpoint.responses.values().chain(
+            endpoint
+                .scenarios
+                .iter()
+                .flatten()
+                .map(|scenario| &scenario.response.definition),
+        )
+    });
+
+    let mut files = BodyFiles::new();
+    for path in responses.filter_map(|response| response.body_file.as_ref()) {
+        if !files.contains_key(path) {
+            files.insert(path.clone(), load(path)?);
+        }
+    }
+    Ok(files)
+}
+
+/// Read one body file, decompressing it when its name ends in `.gz`
+pub fn load(path: &Path) -> ApicentricResult<BodyFile> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        ApicentricError::config_error(
+            format!("Failed to read body_file {}: {}", path.display(), e),
+            Some("Check the body_file path; relative paths resolve from the working directory"),
+        )
+    })?;
+
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut text)
+            .map_err(|e| {
+                ApicentricError::config_error(
+                    format!("Failed to decompress body_file {}: {}", path.display(), e),
+                    Some("Files ending in .gz must hold gzip-compressed UTF-8 text"),
+                )
+            })?;
+        return Ok(BodyFile {
+            text,
+            gzip: Some(Bytes::from(bytes)),
+        });
+    }
+
+    let text = String::from_utf8(bytes).map_err(|e| {
+        ApicentricError::config_error(
+            format!("body_file {} is not UTF-8 text: {}", path.display(), e),
+            Some("Use body_base64 for binary bodies"),
+        )
+    })?;
+    Ok(BodyFile { text, gzip: None })
+}
@@ -0,0 +1,63 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Validate a request body against the named model's JSON Schema.
+///
+/// The other models are reachable from the schema as
+/// `#/components/schemas/<name>`. Returns one message per violation, each
+/// prefixed with the JSON pointer of the offending value; an empty list means
+/// the body conforms.
+pub fn validate(models: &BTreeMap<String, Value>, model: &str, body: &Value) -> Vec<String> {
+    let Some(schema) = models.get(model) else {
+        return vec![format!("Model '{}' is not defined", model)];
+    };
+    let mut schema = schema.clone();
+    if let Some(root) = schema.as_object_mut() {
+        root.entry("components")
+            .or_insert_with(|| serde_json::json!({ "schemas": models }));
+    }
+
+    match jsonschema::validator_for(&schema) {
+        Ok(validator) => validator
+            .iter_errors(body)
+            .map(|error| {
+                let pointer = error.instance_path.to_string();
+                let pointer = if pointer.is_empty() { "/" } else { &pointer };
+                format!("{}: {}", pointer, error)
+            })
+            .collect(),
+        Err(e) => vec![format!(
+            "Model '{}' is not a valid JSON Schema: {}",
+            model, e
+        )],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_references_to_other_models() {
+        let models = BTreeMap::from([
+            (
+                "Order".to_string(),
+                json!({
+                    "type": "object",
+                    "properties": { "address": { "$ref": "#/components/schemas/Address" } },
+                    "required": ["address"]
+                }),
+            ),
+            (
+                "Address".to_string(),
+                json!({ "type": "object", "required": ["city"] }),
+            ),
+        ]);
+
+        assert!(validate(&models, "Order", &json!({ "address": { "city": "Lugo" } })).is_empty());
+        let errors = validate(&models, "Order", &json!({ "address": {} }));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/address: "), "{}", errors[0]);
+    }
+}
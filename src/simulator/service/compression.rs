@@ -0,0 +1,92 @@
+use std::io::Write;
+
+/// Whether this build can produce the given content coding.
+pub fn is_available(encoding: &str) -> bool {
+    encoding == "gzip" || (encoding == "br" && cfg!(feature = "brotli"))
+}
+
+/// Pick the content coding for a response from an `Accept-Encoding` header.
+///
+/// The coding the client weighs highest wins and ties go to the earliest entry
+/// in `supported`. Codings with `q=0`, or not listed and not covered by `*`,
+/// are never chosen.
+pub fn negotiate<'a>(accept_encoding: &str, supported: &'a [String]) -> Option<&'a str> {
+    let weights: Vec<(String, f32)> = accept_encoding
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.split(';').map(str::trim);
+            let coding = pieces.next().filter(|c| !c.is_empty())?;
+            let quality = pieces
+                .find_map(|p| p.strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some((coding.to_ascii_lowercase(), quality))
+        })
+        .collect();
+    let weight = |coding: &str| {
+        weights
+            .iter()
+            .find(|(c, _)| c == coding)
+            .or_else(|| weights.iter().find(|(c, _)| c == "*"))
+            .map_or(0.0, |(_, q)| *q)
+    };
+
+    let mut best: Option<(&str, f32)> = None;
+    for encoding in supported.iter().filter(|e| is_available(e)) {
+        let quality = weight(encoding);
+        if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+            best = Some((encoding, quality));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+/// Compress a body with `gzip` or `br`, or `None` if the coding is unavailable.
+pub fn compress(encoding: &str, body: &[u8]) -> Option<Vec<u8>> {
+    match encoding {
+        "gzip" => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body).ok()?;
+            encoder.finish().ok()
+        }
+        #[cfg(feature = "brotli")]
+        "br" => {
+            let mut out = Vec::new();
+            {
+                let mut writer = brotli::CompressorWriter::new(&mut out, 4096, 5, 22);
+                writer.write_all(body).ok()?;
+            }
+            Some(out)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn negotiates_by_client_weight_then_server_order() {
+        let supported = vec!["br".to_string(), "gzip".to_string()];
+        assert_eq!(negotiate("gzip", &supported), Some("gzip"));
+        assert_eq!(negotiate("identity", &supported), None);
+        assert_eq!(negotiate("gzip;q=0, deflate", &supported), None);
+        if cfg!(feature = "brotli") {
+            assert_eq!(negotiate("gzip, br", &supported), Some("br"));
+            assert_eq!(negotiate("br;q=0.5, gzip", &supported), Some("gzip"));
+            assert_eq!(negotiate("*", &supported), Some("br"));
+        } else {
+            assert_eq!(negotiate("gzip, br", &supported), Some("gzip"));
+        }
+
+        let gzipped = compress("gzip", b"hello hello hello").unwrap();
+        let mut plain = String::new();
+        flate2::read::GzDecoder::new(gzipped.as_slice())
+            .read_to_string(&mut plain)
+            .unwrap();
+        assert_eq!(plain, "hello hello hello");
+    }
+}
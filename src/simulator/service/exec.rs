@@ -0,0 +1,115 @@
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::ServerConfig;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Check that the service opted into `exec` and allowlisted the program.
+///
+/// Returns the reason the command is refused otherwise.
+pub fn check_allowed(server: Option<&ServerConfig>, command: &[String]) -> Result<(), String> {
+    let program = command
+        .first()
+        .ok_or_else(|| "exec command is empty".to_string())?;
+    let server = server
+        .filter(|server| server.allow_exec)
+        .ok_or_else(|| "exec responses are disabled; set server.allow_exec: true".to_string())?;
+    if server
+        .exec_allowlist
+        .iter()
+        .any(|allowed| allowed == program)
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Command '{}' is not listed in server.exec_allowlist",
+            program
+        ))
+    }
+}
+
+/// Run `command` with `input` on stdin and return what it wrote to stdout.
+///
+/// The process is killed when it outlives `timeout`; a non-zero exit status
+/// is an error carrying its stderr.
+pub async fn run(
+    command: &[String],
+    input: Vec<u8>,
+    timeout: Duration,
+) -> ApicentricResult<Vec<u8>> {
+    let program = command.first().map(String::as_str).unwrap_or_default();
+    let mut child = Command::new(program)
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to start command '{}': {}", program, e),
+                Some("Check that the program is installed and on PATH"),
+            )
+        })?;
+
+    // Feed stdin separately so a command writing a large body cannot deadlock
+    if let Some(mut stdin) = child.stdin.take() {
+        tokio::spawn(async move {
+            let _ = stdin.write_all(&input).await;
+        });
+    }
+
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            ApicentricError::runtime_error(
+                format!(
+                    "Command '{}' timed out after {} ms",
+                    program,
+                    timeout.as_millis()
+                ),
+                Some("Raise server.script_timeout_ms or speed up the command"),
+            )
+        })?
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to run command '{}': {}", program, e),
+                None::<String>,
+            )
+        })?;
+    if !output.status.success() {
+        return Err(ApicentricError::runtime_error(
+            format!(
+                "Command '{}' exited with {}: {}",
+                program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            None::<String>,
+        ));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(allow_exec: bool, allowlist: &[&str]) -> ServerConfig {
+        let mut server: ServerConfig = serde_yaml::from_str("base_path: /").unwrap();
+        server.allow_exec = allow_exec;
+        server.exec_allowlist = allowlist.iter().map(|p| p.to_string()).collect();
+        server
+    }
+
+    #[test]
+    fn exec_needs_opt_in_and_allowlisted_program() {
+        let command = vec!["cat".to_string()];
+        assert!(check_allowed(None, &command).is_err());
+        assert!(check_allowed(Some(&server(false, &["cat"])), &command).is_err());
+        assert!(check_allowed(Some(&server(true, &["echo"])), &command).is_err());
+        assert!(check_allowed(Some(&server(true, &["cat"])), &command).is_ok());
+        assert!(check_allowed(Some(&server(true, &["cat"])), &[]).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::simulator::config::ResponseDefinition;
+
+/// Language ranges from an `Accept-Language` header, most preferred first.
+///
+/// Ranges with `q=0` are dropped; equal weights keep their header order.
+pub fn preferred_languages(header: &str) -> Vec<String> {
+    let mut ranges: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.split(';').map(str::trim);
+            let tag = pieces.next().filter(|t| !t.is_empty())?;
+            let quality = pieces
+                .find_map(|p| p.strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (quality > 0.0).then(|| (tag.to_ascii_lowercase(), quality))
+        })
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranges.into_iter().map(|(tag, _)| tag).collect()
+}
+
+/// Find the locale key that best satisfies an `Accept-Language` header.
+///
+/// Each range is tried in preference order, first as an exact tag and then by
+/// its primary subtag, so `es-MX` falls back to an `es` example.
+pub fn best_match<'a>(header: &str, locales: &[&'a str]) -> Option<&'a str> {
+    let primary = |tag: &str| {
+        tag.split('-')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    };
+    preferred_languages(header)
+        .iter()
+        .filter(|range| range.as_str() != "*")
+        .find_map(|range| {
+            locales
+                .iter()
+                .find(|l| l.eq_ignore_ascii_case(range))
+                .or_else(|| locales.iter().find(|l| primary(l) == primary(range)))
+                .copied()
+        })
+}
+
+/// Replace the response body with the example matching the client's preferred
+/// language and tag it with `Content-Language`. The body is left as the default
+/// when no example key matches.
+pub fn localize(response: &mut ResponseDefinition, accept_language: &str) {
+    let Some(examples) = &response.examples else {
+        return;
+    };
+    let mut locales: Vec<&str> = examples.keys().map(String::as_str).collect();
+    locales.sort();
+    let Some(locale) = best_match(accept_language, &locales) else {
+        return;
+    };
+    let (locale, body) = (locale.to_string(), examples[locale].clone());
+    response.body = body;
+    response
+        .headers
+        .get_or_insert_with(Default::default)
+        .insert("content-language".to_string(), locale);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_ranges_by_quality_and_matches_primary_subtag() {
+        assert_eq!(
+            preferred_languages("fr;q=0.5, es-MX, de;q=0, en;q=0.8"),
+            vec!["es-mx", "en", "fr"]
+        );
+        assert_eq!(best_match("es-MX, en;q=0.8", &["en", "es"]), Some("es"));
+        assert_eq!(best_match("pt, en;q=0.1", &["en", "es"]), Some("en"));
+        assert_eq!(best_match("pt, *", &["en", "es"]), None);
+    }
+}
@@ -1,14 +1,27 @@
 //! Service Instance - Individual service implementation with state management
 
+pub mod auth;
+pub mod body_file;
+pub mod body_schema;
+pub mod compression;
+pub mod exec;
 pub mod graphql;
 pub mod http_server;
+pub mod locale;
+pub mod pretty_json;
+pub mod proxy_transform;
+pub mod rate_limit;
 pub mod router;
 pub mod routing;
 pub mod scenario;
+pub mod sparse_fields;
 pub mod state;
 pub mod state_service;
+pub mod streaming;
+pub mod tls;
 #[cfg(feature = "iot")]
 pub mod twin_runner;
+pub mod websocket;
 
 pub use graphql::*;
 pub use http_server::HttpServer;
@@ -17,23 +30,33 @@ pub use routing::*;
 pub use scenario::ScenarioService;
 pub use state::*;
 pub use state_service::StateService;
+pub use streaming::{EventStream, RecordFraming, RecordStream};
 
-use crate::errors::{ApicentricError, ApicentricResult};
+use crate::errors::{ApicentricError, ApicentricResult, SimulatorError};
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ParameterDefinition, ParameterLocation, ResponseDefinition,
-    ScenarioDefinition, ScenarioStrategy, ServiceDefinition,
+    BehaviorConfig, CorsConfig, EndpointDefinition, EndpointFallback, EndpointKind,
+    ParameterDefinition, ParameterLocation, ProxyTlsConfig, ResponseDefinition, ScenarioDefinition,
+    ScenarioStrategy, ServiceDefinition, TrailingSlashMode,
 };
-use crate::simulator::log::RequestLogEntry;
-use crate::simulator::scripting::ScriptingEngine;
-use crate::simulator::template::{RequestContext, TemplateContext, TemplateEngine};
-use crate::storage::Storage;
+use crate::simulator::log::{describe_body, LogRedactor, RequestLogEntry};
+use crate::simulator::scripting::{ScriptResponse, ScriptingEngine, DEFAULT_SCRIPT_TIMEOUT};
+use crate::simulator::template::{
+    body_content_type, parse_cookies, RequestContext, TemplateContext, TemplateEngine,
+};
+use crate::storage::{ServiceVersion, Storage};
+use base64::Engine;
+use body_file::BodyFiles;
 use bytes::Bytes;
-use http_body_util::Full;
-use hyper::header::HOST;
+use futures_util::StreamExt;
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::Frame;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, HOST, TRAILER};
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::{Request, Response, StatusCode};
 use hyper_util::rt::TokioIo;
+use proxy_transform::{ProxyTransforms, Transform};
 use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -41,13 +64,63 @@ use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Instant;
 use tokio::net::TcpListener;
 use tokio::sync::{broadcast, RwLock};
 use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+
+/// Body type written to the wire by the service HTTP server
+pub type ServiceBody = UnsyncBoxBody<Bytes, Infallible>;
+
+/// Trailers attached to a response as an extension, sent after a chunked body
+#[derive(Debug, Clone)]
+pub struct ResponseTrailers(pub HeaderMap);
+
+/// Convert a buffered response into its wire form, switching to a chunked
+/// body when the response carries [`ResponseTrailers`] or a [`RecordStream`],
+/// or to an open-ended one for an [`EventStream`].
+pub fn into_service_body(response: Response<Full<Bytes>>) -> Response<ServiceBody> {
+    let (mut parts, body) = response.into_parts();
+    if let Some(events) = parts.extensions.remove::<EventStream>() {
+        return Response::from_parts(parts, events.into_body());
+    }
+    let trailers = parts.extensions.remove::<ResponseTrailers>();
+    let records = parts.extensions.remove::<RecordStream>();
+    if trailers.is_none() && records.is_none() {
+        return Response::from_parts(parts, body.boxed_unsync());
+    }
+
+    let (chunks, delay) = match records {
+        Some(RecordStream { records, delay }) => (records, delay),
+        None => {
+            let data = futures_util::FutureExt::now_or_never(body.collect())
+                .and_then(Result::ok)
+                .map(|collected| collected.to_bytes())
+                .unwrap_or_default();
+            (vec![data], std::time::Duration::ZERO)
+        }
+    };
+    let data = futures_util::stream::iter(chunks.into_iter().enumerate()).then(
+        move |(index, chunk)| async move {
+            if index > 0 && !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Ok::<_, Infallible>(Frame::data(chunk))
+        },
+    );
+    let trailers = futures_util::stream::iter(
+        trailers.map(|ResponseTrailers(map)| Ok::<_, Infallible>(Frame::trailers(map))),
+    );
+    let stream = StreamBody::new(data.chain(trailers));
+    Response::from_parts(parts, stream.boxed_unsync())
+}
 
 /// Individual service instance with HTTP server capabilities
 pub struct ServiceInstance {
     definition: Arc<StdRwLock<ServiceDefinition>>,
+    /// Path regex of each endpoint, compiled once and keyed by endpoint index
+    routes: Arc<StdRwLock<Arc<RouteTable>>>,
     port: u16,
     state: Arc<RwLock<ServiceState>>,
     template_engine: Arc<TemplateEngine>,
@@ -59,29 +132,103 @@ pub struct ServiceInstance {
     active_scenario: Arc<RwLock<Option<String>>>,
     graphql: Option<Arc<GraphQLMocks>>,
     storage: Arc<dyn Storage>,
+    proxy_client: reqwest::Client,
+    /// Contents of the `body_file`s referenced by the responses
+    body_files: Arc<BodyFiles>,
+    /// Compiled `proxy_response_transform` expressions
+    proxy_transforms: Arc<ProxyTransforms>,
+    /// Terminates TLS on accepted connections when `server.tls` is set
+    tls_acceptor: Option<TlsAcceptor>,
+    /// Bound socket, kept so a reload can hand it to the replacement instance
+    listener: Option<Arc<TcpListener>>,
+    /// `Retry-After` seconds while draining for a reload; requests get a 503
+    draining: Arc<StdRwLock<Option<u64>>>,
 }
 
 impl ServiceInstance {
     /// Create a new service instance
     pub fn new(
-        definition: ServiceDefinition,
+        mut definition: ServiceDefinition,
         port: u16,
         storage: Arc<dyn Storage>,
         log_sender: broadcast::Sender<RequestLogEntry>,
     ) -> ApicentricResult<Self> {
+        definition.sort_fixtures();
         let fixtures = definition.fixtures.clone();
         let bucket = definition.bucket.clone();
         let graphql_cfg = definition.graphql.clone();
+        let partials = definition.partials.clone();
+        let state_machine = definition.state_machine.clone();
+        let strict_templates = definition
+            .server
+            .as_ref()
+            .is_some_and(|server| server.strict_templates);
+        let redact = definition
+            .behavior
+            .as_ref()
+            .and_then(|b| b.redact.clone())
+            .unwrap_or_default();
+
+        let proxy_client = Self::build_proxy_client(
+            definition
+                .server
+                .as_ref()
+                .and_then(|s| s.proxy_tls.as_ref()),
+        )?;
+
+        let body_files = body_file::load_all(definition.endpoints.as_deref().unwrap_or_default())?;
+        let proxy_transforms = proxy_transform::compile_all(&definition)?;
+        let tls_acceptor = definition
+            .server
+            .as_ref()
+            .and_then(|s| s.tls.as_ref())
+            .map(tls::acceptor)
+            .transpose()?;
 
+        let routes = Arc::new(StdRwLock::new(Arc::new(RouteTable::compile(
+            definition.endpoints.as_deref().unwrap_or_default(),
+        ))));
         let definition = Arc::new(StdRwLock::new(definition));
 
-        let state = ServiceState::new(fixtures, bucket, Arc::clone(&storage), Some(log_sender));
+        let mut state = ServiceState::new(fixtures, bucket, Arc::clone(&storage), Some(log_sender));
+        state.set_log_redactor(LogRedactor::new(&redact)?);
+        state.set_state_machine(state_machine.map(|machine| machine.initial));
 
         // Initialize template engine and register bucket helpers
         let mut template_engine = TemplateEngine::new()?;
+        template_engine.set_strict_mode(strict_templates);
         template_engine.register_bucket_helpers(state.bucket())?;
+        if let Some(partials) = &partials {
+            template_engine.register_partials(partials)?;
+        }
+        Self::compile_response_templates(
+            &mut template_engine,
+            definition
+                .read()
+                .unwrap()
+                .endpoints
+                .as_deref()
+                .unwrap_or_default(),
+        );
 
-        let scripting_engine = Arc::new(ScriptingEngine::new());
+        let script_timeout = definition
+            .read()
+            .unwrap()
+            .server
+            .as_ref()
+            .and_then(|server| server.script_timeout_ms)
+            .map_or(DEFAULT_SCRIPT_TIMEOUT, std::time::Duration::from_millis);
+        let allow_script_network = definition
+            .read()
+            .unwrap()
+            .server
+            .as_ref()
+            .is_some_and(|server| server.allow_script_network);
+        let scripting_engine = Arc::new(
+            ScriptingEngine::new()
+                .with_timeout(script_timeout)
+                .with_network(allow_script_network),
+        );
 
         let graphql = if let Some(gql_cfg) = graphql_cfg {
             Some(Arc::new(load_graphql_mocks(&gql_cfg)?))
@@ -90,10 +237,12 @@ impl ServiceInstance {
         };
 
         let saved_definition = { definition.read().unwrap().clone() };
-        let _ = storage.save_service(&saved_definition);
+        let _ = storage.save_service_version(&saved_definition);
+        let active_scenario = storage.load_scenario(&saved_definition.name).ok().flatten();
 
         Ok(Self {
             definition,
+            routes,
             port,
             state: Arc::new(RwLock::new(state)),
             template_engine: Arc::new(template_engine),
@@ -102,9 +251,84 @@ impl ServiceInstance {
             #[cfg(feature = "iot")]
             twin_handle: None,
             is_running: false,
-            active_scenario: Arc::new(RwLock::new(None)),
+            active_scenario: Arc::new(RwLock::new(active_scenario)),
             graphql,
             storage,
+            proxy_client,
+            body_files: Arc::new(body_files),
+            proxy_transforms: Arc::new(proxy_transforms),
+            tls_acceptor,
+            listener: None,
+            draining: Arc::new(StdRwLock::new(None)),
+        })
+    }
+
+    /// Compile every templated response body and header once, under names the
+    /// handler can look up (`"{endpoint_index}:{status}:body"`).
+    ///
+    /// Templates that fail to compile are skipped so the error still surfaces
+    /// when the response is rendered.
+    fn compile_response_templates(engine: &mut TemplateEngine, endpoints: &[EndpointDefinition]) {
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            for (status, response) in &endpoint.responses {
+                let mut templates =
+                    vec![(Self::body_template_name(index, *status), &*response.body)];
+                for (key, values) in response.headers.iter().flatten() {
+                    for (line, value) in values.lines().enumerate() {
+                        templates
+                            .push((Self::header_template_name(index, *status, key, line), value));
+                    }
+                }
+                for (name, template) in templates {
+                    if !template.contains("{{") {
+                        continue;
+                    }
+                    if let Err(e) = engine.compile_template(&name, template) {
+                        log::debug!("Not caching template '{}': {}", name, e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn body_template_name(endpoint_index: usize, status: u16) -> String {
+        format!("{}:{}:body", endpoint_index, status)
+    }
+
+    fn header_template_name(endpoint_index: usize, status: u16, key: &str, line: usize) -> String {
+        format!("{}:{}:header:{}:{}", endpoint_index, status, key, line)
+    }
+
+    /// Build the HTTP client used for proxying, applying the service's TLS options
+    fn build_proxy_client(tls: Option<&ProxyTlsConfig>) -> ApicentricResult<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(tls) = tls {
+            builder = builder.danger_accept_invalid_certs(tls.accept_invalid_certs);
+            if let Some(path) = &tls.ca_cert {
+                let pem = std::fs::read(path).map_err(|e| {
+                    ApicentricError::fs_error(
+                        format!(
+                            "Failed to read proxy CA certificate {}: {}",
+                            path.display(),
+                            e
+                        ),
+                        Some("Check that proxy_tls.ca_cert points to a readable PEM file"),
+                    )
+                })?;
+                let cert = reqwest::Certificate::from_pem(&pem).map_err(|e| {
+                    ApicentricError::config_error(
+                        format!("Invalid proxy CA certificate {}: {}", path.display(), e),
+                        Some("proxy_tls.ca_cert must contain a PEM encoded certificate"),
+                    )
+                })?;
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        builder.build().map_err(|e| {
+            ApicentricError::config_error(
+                format!("Failed to build proxy client: {}", e),
+                None::<String>,
+            )
         })
     }
 
@@ -157,21 +381,39 @@ impl ServiceInstance {
         // Create TCP listener for the service
         // Use 0.0.0.0 to bind to all interfaces (required for LAN access on mobile)
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
-        let listener = TcpListener::bind(addr).await.map_err(|e| {
-            ApicentricError::runtime_error(
-                format!("Failed to bind to port {}: {}", self.port, e),
-                Some("Port may already be in use or unavailable"),
-            )
-        })?;
+        let listener = match self.listener.take() {
+            Some(listener) => listener,
+            None => Arc::new(TcpListener::bind(addr).await.map_err(|e| {
+                if e.kind() == std::io::ErrorKind::AddrInUse {
+                    return SimulatorError::PortInUse(self.port).into();
+                }
+                ApicentricError::runtime_error(
+                    format!("Failed to bind to port {}: {}", self.port, e),
+                    Some("Port may be unavailable"),
+                )
+            })?),
+        };
+        self.listener = Some(Arc::clone(&listener));
+        *self.draining.write().unwrap() = None;
 
         // Clone necessary data for the server task
         let definition = Arc::clone(&self.definition);
+        let routes = Arc::clone(&self.routes);
         let state = Arc::clone(&self.state);
         let template_engine = Arc::clone(&self.template_engine);
         let scripting_engine = Arc::clone(&self.scripting_engine);
         let active_scenario = Arc::clone(&self.active_scenario);
         let graphql = self.graphql.clone();
         let storage = Arc::clone(&self.storage);
+        let proxy_client = self.proxy_client.clone();
+        let body_files = Arc::clone(&self.body_files);
+        let proxy_transforms = Arc::clone(&self.proxy_transforms);
+        let draining = Arc::clone(&self.draining);
+        let tls_acceptor = self.tls_acceptor.clone();
+        let require_client_cert = server_cfg
+            .tls
+            .as_ref()
+            .is_some_and(|tls| tls.require_client_cert);
 
         // Spawn the HTTP server task
         let server_handle = tokio::spawn(async move {
@@ -183,71 +425,125 @@ impl ServiceInstance {
                 &format!("Started on port {} at {}", addr.port(), base_path),
                 200,
                 None,
+                None,
             )
             .await;
 
             loop {
                 match listener.accept().await {
                     Ok((stream, _)) => {
-                        let io = TokioIo::new(stream);
+                        let tls_acceptor = tls_acceptor.clone();
                         let service_name_for_request = service_name.clone();
                         let service_name_for_error = service_name.clone();
                         let definition = Arc::clone(&definition);
+                        let routes = Arc::clone(&routes);
                         let state = Arc::clone(&state);
                         let template_engine = Arc::clone(&template_engine);
                         let scripting_engine = Arc::clone(&scripting_engine);
                         let scenario_cfg_outer = Arc::clone(&active_scenario);
                         let graphql_cfg_outer = graphql.clone();
                         let storage = Arc::clone(&storage);
+                        let proxy_client = proxy_client.clone();
+                        let body_files = Arc::clone(&body_files);
+                        let proxy_transforms = Arc::clone(&proxy_transforms);
+                        let draining = Arc::clone(&draining);
 
                         tokio::task::spawn(async move {
-                            let service = service_fn(move |req| {
-                                let service_name = service_name_for_request.clone();
-                                let definition = Arc::clone(&definition);
-                                let state = Arc::clone(&state);
-                                let template_engine = Arc::clone(&template_engine);
-                                let scripting_engine = Arc::clone(&scripting_engine);
-                                let scenario_cfg = Arc::clone(&scenario_cfg_outer);
-                                let graphql_cfg = graphql_cfg_outer.clone();
-                                let storage = Arc::clone(&storage);
-
-                                async move {
-                                    match Self::handle_request_static(
-                                        req,
-                                        Arc::clone(&definition),
-                                        state,
-                                        template_engine,
-                                        scripting_engine,
-                                        scenario_cfg,
-                                        graphql_cfg,
-                                        storage,
-                                    )
-                                    .await
-                                    {
-                                        Ok(resp) => Ok::<_, Infallible>(resp),
+                            let (io, client_cert): (Box<dyn tls::Connection>, _) =
+                                match tls_acceptor {
+                                    Some(acceptor) => match acceptor.accept(stream).await {
+                                        Ok(stream) => {
+                                            let client_cert = tls::client_cert(&stream);
+                                            (Box::new(stream), client_cert)
+                                        }
                                         Err(err) => {
-                                            eprintln!(
-                                                "Error handling request for service '{}': {}",
-                                                service_name, err
+                                            log::debug!(
+                                                "TLS handshake failed for service '{}': {}",
+                                                service_name_for_error,
+                                                err
                                             );
-                                            let fallback = match Response::builder()
-                                                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                                                .header("content-type", "application/json")
-                                                .body(Full::new(Bytes::from(format!(
-                                                    r#"{{"error": "{}"}}"#,
-                                                    err
-                                                )))) {
-                                                Ok(r) => r,
-                                                Err(_) => Response::new(Full::new(Bytes::new())),
-                                            };
-                                            Ok::<_, Infallible>(fallback)
+                                            return;
+                                        }
+                                    },
+                                    None => (Box::new(stream), None),
+                                };
+                            let io = TokioIo::new(io);
+
+                            let service =
+                                service_fn(move |mut req: Request<hyper::body::Incoming>| {
+                                    let service_name = service_name_for_request.clone();
+                                    let definition = Arc::clone(&definition);
+                                    let routes = Arc::clone(&routes);
+                                    let state = Arc::clone(&state);
+                                    let template_engine = Arc::clone(&template_engine);
+                                    let scripting_engine = Arc::clone(&scripting_engine);
+                                    let scenario_cfg = Arc::clone(&scenario_cfg_outer);
+                                    let graphql_cfg = graphql_cfg_outer.clone();
+                                    let storage = Arc::clone(&storage);
+                                    let proxy_client = proxy_client.clone();
+                                    let body_files = Arc::clone(&body_files);
+                                    let proxy_transforms = Arc::clone(&proxy_transforms);
+                                    let retry_after = *draining.read().unwrap();
+                                    let client_cert = client_cert.clone();
+
+                                    async move {
+                                        if let Some(retry_after) = retry_after {
+                                            return Ok::<_, Infallible>(into_service_body(
+                                                Self::draining_response(&service_name, retry_after),
+                                            ));
+                                        }
+                                        match client_cert {
+                                            Some(cert) => {
+                                                req.extensions_mut().insert(cert);
+                                            }
+                                            None if require_client_cert => {
+                                                return Ok(into_service_body(
+                                                    Self::client_cert_required_response(
+                                                        &service_name,
+                                                    ),
+                                                ));
+                                            }
+                                            None => {}
+                                        }
+                                        match Self::handle_request_static(
+                                            req,
+                                            Arc::clone(&definition),
+                                            routes,
+                                            state,
+                                            template_engine,
+                                            scripting_engine,
+                                            scenario_cfg,
+                                            graphql_cfg,
+                                            storage,
+                                            proxy_client,
+                                            body_files,
+                                            proxy_transforms,
+                                        )
+                                        .await
+                                        {
+                                            Ok(resp) => {
+                                                Ok::<_, Infallible>(into_service_body(resp))
+                                            }
+                                            Err(err) => {
+                                                eprintln!(
+                                                    "Error handling request for service '{}': {}",
+                                                    service_name, err
+                                                );
+                                                let fallback = Self::internal_error_response(
+                                                    &service_name,
+                                                    &err,
+                                                );
+                                                Ok::<_, Infallible>(into_service_body(fallback))
+                                            }
                                         }
                                     }
-                                }
-                            });
+                                });
 
-                            if let Err(err) =
-                                http1::Builder::new().serve_connection(io, service).await
+                            // Upgrades keep the connection alive for WebSocket endpoints
+                            if let Err(err) = http1::Builder::new()
+                                .serve_connection(io, service)
+                                .with_upgrades()
+                                .await
                             {
                                 eprintln!(
                                     "Error serving connection for service '{}': {:?}",
@@ -294,6 +590,7 @@ impl ServiceInstance {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         }
 
+        self.listener = None;
         self.is_running = false;
 
         println!(
@@ -304,6 +601,83 @@ impl ServiceInstance {
         Ok(())
     }
 
+    /// Answer every request with a 503 until the service stops, and return the
+    /// bound socket so the replacement instance can keep accepting on it.
+    ///
+    /// Used while a reload swaps services so clients see a retryable 503
+    /// instead of a refused connection.
+    pub fn begin_draining(&self, retry_after_secs: u64) -> Option<Arc<TcpListener>> {
+        *self.draining.write().unwrap() = Some(retry_after_secs);
+        self.listener.clone()
+    }
+
+    /// Serve on an already bound socket instead of binding the port on start
+    pub fn adopt_listener(&mut self, listener: Arc<TcpListener>) {
+        if !self.is_running {
+            self.listener = Some(listener);
+        }
+    }
+
+    /// Build the 503 answered while draining, asking the client to retry on a
+    /// fresh connection
+    fn draining_response(service_name: &str, retry_after_secs: u64) -> Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "error": {
+                "message": "Service is reloading",
+                "service": service_name,
+            }
+        });
+        let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+        *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+        let headers = response.headers_mut();
+        headers.insert(
+            hyper::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            hyper::header::RETRY_AFTER,
+            HeaderValue::from(retry_after_secs),
+        );
+        headers.insert(hyper::header::CONNECTION, HeaderValue::from_static("close"));
+        response
+    }
+
+    /// Build the 401 sent when `server.tls.require_client_cert` is set and the
+    /// connection presented no client certificate
+    fn client_cert_required_response(service_name: &str) -> Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "error": {
+                "message": "Client certificate required",
+                "service": service_name,
+            }
+        });
+        let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+        *response.status_mut() = StatusCode::UNAUTHORIZED;
+        response.headers_mut().insert(
+            hyper::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+
+    /// Build the 500 response sent when request handling fails, using the
+    /// envelope `{"error": {"message": ..., "service": ...}}`
+    fn internal_error_response(service_name: &str, err: &ApicentricError) -> Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "error": {
+                "message": err.to_string(),
+                "service": service_name,
+            }
+        });
+        let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        response.headers_mut().insert(
+            hyper::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+
     /// Handle a single HTTP request (for external use)
     pub async fn handle_request(
         &self,
@@ -322,12 +696,16 @@ impl ServiceInstance {
         Self::handle_request_static(
             req,
             Arc::clone(&self.definition),
+            Arc::clone(&self.routes),
             Arc::clone(&self.state),
             Arc::clone(&self.template_engine),
             Arc::clone(&self.scripting_engine),
             Arc::clone(&self.active_scenario),
             self.graphql.clone(),
             Arc::clone(&self.storage),
+            self.proxy_client.clone(),
+            Arc::clone(&self.body_files),
+            Arc::clone(&self.proxy_transforms),
         )
         .await
     }
@@ -384,8 +762,13 @@ impl ServiceInstance {
         self.definition.read().unwrap().clone()
     }
 
-    /// Set the active scenario for this service
+    /// Set the active scenario for this service and persist it so a reload or
+    /// restart keeps it active
     pub async fn set_scenario(&self, scenario: Option<String>) {
+        let name = self.definition.read().unwrap().name.clone();
+        if let Err(err) = self.storage.save_scenario(&name, scenario.as_deref()) {
+            log::warn!("Failed to persist scenario for {}: {}", name, err);
+        }
         let mut guard = self.active_scenario.write().await;
         *guard = scenario;
     }
@@ -429,7 +812,13 @@ impl ServiceInstance {
         state.query_logs(service, route, method, status, limit)
     }
 
-    /// Internal helper to record a request log entry
+    /// Saved versions of this service's definition, oldest first
+    pub fn history(&self) -> ApicentricResult<Vec<ServiceVersion>> {
+        self.storage.service_history(&self.name())
+    }
+
+    /// Internal helper to record a request log entry, timed from `started`
+    #[allow(clippy::too_many_arguments)]
     async fn record_log(
         state: &Arc<RwLock<ServiceState>>,
         service: &str,
@@ -438,16 +827,19 @@ impl ServiceInstance {
         path: &str,
         status: u16,
         payload: Option<String>,
+        started: Option<Instant>,
     ) {
-        let mut guard = state.write().await;
-        guard.add_log_entry(RequestLogEntry::new(
+        let mut entry = RequestLogEntry::new(
             service.to_string(),
             endpoint,
             method.to_string(),
             path.to_string(),
             status,
             payload,
-        ));
+        );
+        entry.duration_ms = started.map(|started| started.elapsed().as_millis() as u64);
+        let mut guard = state.write().await;
+        guard.add_log_entry(entry);
     }
 
     /// Get all fixtures
@@ -589,7 +981,8 @@ impl ServiceInstance {
         }
     }
 
-    /// Find an endpoint by method, path and headers with parameter extraction
+    /// Find an endpoint by method, path and headers with parameter extraction,
+    /// using the same matcher as live requests without a query string
     pub fn find_endpoint_with_params(
         &self,
         method: &str,
@@ -597,22 +990,17 @@ impl ServiceInstance {
         headers: &HashMap<String, String>,
     ) -> Option<RouteMatch> {
         let definition = self.definition.read().unwrap();
-        if let Some(endpoints) = &definition.endpoints {
-            for (index, endpoint) in endpoints.iter().enumerate() {
-                if endpoint.method.to_uppercase() == method.to_uppercase()
-                    && Self::headers_match(endpoint, headers)
-                {
-                    if let Some(path_params) = self.extract_path_parameters(&endpoint.path, path) {
-                        return Some(RouteMatch {
-                            endpoint: endpoint.clone(),
-                            endpoint_index: index,
-                            path_params,
-                        });
-                    }
-                }
-            }
-        }
-        None
+        let routes = Arc::clone(&self.routes.read().unwrap());
+        Self::find_endpoint_with_params_static(
+            definition.endpoints.as_deref().unwrap_or_default(),
+            &routes,
+            method,
+            path,
+            headers,
+            &HashMap::new(),
+        )
+        .ok()
+        .flatten()
     }
 
     /// Find an endpoint by method, path and headers (legacy reference)
@@ -622,18 +1010,17 @@ impl ServiceInstance {
         path: &str,
         headers: &HashMap<String, String>,
     ) -> Option<EndpointDefinition> {
-        let definition = self.definition.read().unwrap();
-        if let Some(endpoints) = &definition.endpoints {
-            for endpoint in endpoints {
-                if endpoint.method.to_uppercase() == method.to_uppercase()
-                    && Self::headers_match(endpoint, headers)
-                    && self.extract_path_parameters(&endpoint.path, path).is_some()
-                {
-                    return Some(endpoint.clone());
-                }
-            }
-        }
-        None
+        self.find_endpoint_with_params(method, path, headers)
+            .map(|route_match| route_match.endpoint)
+    }
+
+    /// Check if request query parameters satisfy an endpoint's query_match criteria
+    fn query_matches(endpoint: &EndpointDefinition, query: &HashMap<String, String>) -> bool {
+        endpoint
+            .query_match
+            .iter()
+            .flatten()
+            .all(|(key, expected)| query.get(key) == Some(expected))
     }
 
     /// Check if request headers satisfy an endpoint's header_match criteria
@@ -649,72 +1036,80 @@ impl ServiceInstance {
         true
     }
 
-    /// Extract path parameters from a request path against an endpoint path pattern
-    fn extract_path_parameters(
-        &self,
-        endpoint_path: &str,
-        request_path: &str,
-    ) -> Option<PathParameters> {
-        // Convert endpoint path pattern to regex
-        let regex_pattern = self.endpoint_path_to_regex(endpoint_path);
-
-        match Regex::new(&regex_pattern) {
-            Ok(regex) => {
-                if let Some(captures) = regex.captures(request_path) {
-                    let mut params = PathParameters::new();
-
-                    // Extract named parameters
-                    for name in regex.capture_names().flatten() {
-                        if let Some(matched) = captures.name(name) {
-                            params.insert(name.to_string(), matched.as_str().to_string());
-                        }
-                    }
-
-                    Some(params)
-                } else {
-                    None
-                }
-            }
-            Err(_) => {
-                // Fallback to exact matching if regex compilation fails
-                if endpoint_path == request_path {
-                    Some(PathParameters::new())
-                } else {
-                    None
-                }
-            }
-        }
-    }
-
-    /// Convert an endpoint path pattern to a regex pattern
-    fn endpoint_path_to_regex(&self, endpoint_path: &str) -> String {
-        Self::endpoint_path_to_regex_static(endpoint_path)
-    }
-
     /// Static request handler for use in the HTTP server
     #[allow(clippy::too_many_arguments)]
     async fn handle_request_static(
         req: Request<hyper::body::Incoming>,
         definition: Arc<StdRwLock<ServiceDefinition>>,
+        routes: Arc<StdRwLock<Arc<RouteTable>>>,
         state: Arc<RwLock<ServiceState>>,
         template_engine: Arc<TemplateEngine>,
         scripting_engine: Arc<ScriptingEngine>,
         active_scenario: Arc<RwLock<Option<String>>>,
         graphql: Option<Arc<GraphQLMocks>>,
         storage: Arc<dyn Storage>,
+        proxy_client: reqwest::Client,
+        body_files: Arc<BodyFiles>,
+        proxy_transforms: Arc<ProxyTransforms>,
     ) -> ApicentricResult<Response<Full<Bytes>>> {
-        let (service_name, base_path, endpoints, cors_cfg, proxy_base_url, record_unknown) = {
+        let started = Instant::now();
+        let (
+            service_name,
+            base_path,
+            endpoints,
+            cors_cfg,
+            proxy_base_url,
+            proxy_transform,
+            record_unknown,
+            service_behavior,
+            service_pretty_json,
+            auth_cfg,
+            compression_cfg,
+            tenant_header,
+            trailing_slash,
+        ) = {
             let def = definition.read().unwrap();
-            let (base_path, cors_cfg, proxy_cfg, record_unknown) = if let Some(server) = &def.server
-            {
+            let (
+                base_path,
+                cors_cfg,
+                proxy_cfg,
+                proxy_transform,
+                record_unknown,
+                pretty_json,
+                auth_cfg,
+                compression,
+                tenant_header,
+                trailing_slash,
+            ) = if let Some(server) = &def.server {
                 (
                     server.base_path.clone(),
                     server.cors.clone(),
                     server.proxy_base_url.clone(),
+                    server.proxy_response_transform.clone(),
                     server.record_unknown,
+                    server.pretty_json,
+                    server.auth.clone(),
+                    server.compression.clone(),
+                    server.tenant_header.clone(),
+                    server
+                        .trailing_slash
+                        .as_ref()
+                        .map(|t| t.mode)
+                        .unwrap_or_default(),
                 )
             } else {
-                ("/".to_string(), None, None, false)
+                (
+                    "/".to_string(),
+                    None,
+                    None,
+                    None,
+                    false,
+                    false,
+                    None,
+                    None,
+                    None,
+                    TrailingSlashMode::default(),
+                )
             };
 
             (
@@ -723,11 +1118,20 @@ impl ServiceInstance {
                 def.endpoints.clone().unwrap_or_default(),
                 cors_cfg,
                 proxy_cfg,
+                proxy_transform,
                 record_unknown,
+                def.behavior.clone(),
+                pretty_json,
+                auth_cfg,
+                compression,
+                tenant_header,
+                trailing_slash,
             )
         };
 
-        let (parts, body) = req.into_parts();
+        let compiled_routes = Arc::clone(&routes.read().unwrap());
+
+        let (mut parts, body) = req.into_parts();
         let method = parts.method.as_str();
         let path = parts.uri.path();
 
@@ -747,6 +1151,7 @@ impl ServiceInstance {
             ),
             200,
             None,
+            None,
         )
         .await;
 
@@ -780,6 +1185,8 @@ impl ServiceInstance {
             })
             .unwrap_or_default();
 
+        let client_cert = parts.extensions.get::<tls::ClientCert>().cloned();
+
         // Parse headers
         let headers: HashMap<String, String> = parts
             .headers
@@ -787,8 +1194,77 @@ impl ServiceInstance {
             .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or("").to_string()))
             .collect();
 
+        // Remove base path from request path if it matches
+        let relative_path = if path.starts_with(&base_path) {
+            &path[base_path.len()..]
+        } else {
+            path
+        };
+
+        // Ensure relative path starts with '/'
+        let relative_path = if relative_path.is_empty() || !relative_path.starts_with('/') {
+            format!("/{}", relative_path.trim_start_matches('/'))
+        } else {
+            relative_path.to_string()
+        };
+
+        // Endpoints are also served under each of the service's `path_prefixes`;
+        // the path as requested wins, so a literal `/v1/...` endpoint stays reachable
+        let relative_path = if compiled_routes.matching(&relative_path).next().is_some() {
+            relative_path
+        } else {
+            definition
+                .read()
+                .unwrap()
+                .server
+                .iter()
+                .flat_map(|server| &server.path_prefixes)
+                .find_map(|prefix| Self::strip_path_prefix(&relative_path, prefix))
+                .unwrap_or(relative_path)
+        };
+
+        // A plain OPTIONS (no CORS preflight headers) reports the methods the
+        // path supports instead of a CORS response
+        let is_preflight =
+            headers.contains_key("origin") && headers.contains_key("access-control-request-method");
+        if method == "OPTIONS" && !is_preflight {
+            let mut allowed: Vec<String> = compiled_routes
+                .matching(&relative_path)
+                .filter_map(|index| endpoints.get(index))
+                .map(|ep| ep.method.to_uppercase())
+                .collect();
+            if !allowed.is_empty() {
+                allowed.push("OPTIONS".to_string());
+                allowed.sort();
+                allowed.dedup();
+                let resp = Response::builder()
+                    .status(StatusCode::NO_CONTENT)
+                    .header("allow", allowed.join(", "))
+                    .body(Full::new(Bytes::from_static(b"")))
+                    .map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build OPTIONS response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                Self::record_log(
+                    &state,
+                    &service_name,
+                    None,
+                    method,
+                    path,
+                    StatusCode::NO_CONTENT.as_u16(),
+                    None,
+                    Some(started),
+                )
+                .await;
+                return Ok(resp);
+            }
+        }
+
         // Handle CORS preflight
-        if method == "OPTIONS" {
+        let cors_disabled = cors_cfg.as_ref().is_some_and(|c| !c.enabled);
+        if method == "OPTIONS" && !cors_disabled {
             let origin = headers.get("origin").cloned().unwrap_or_default();
 
             let allow_origin = match &cors_cfg {
@@ -838,7 +1314,13 @@ impl ServiceInstance {
                 .header("access-control-allow-origin", &allow_origin)
                 .header("access-control-allow-methods", &allow_methods)
                 .header("access-control-allow-headers", &req_headers)
-                .header("access-control-max-age", "86400")
+                .header(
+                    "access-control-max-age",
+                    cors_cfg
+                        .as_ref()
+                        .map_or(86400, |c| c.max_age_secs)
+                        .to_string(),
+                )
                 .body(Full::new(Bytes::from_static(b"")))
                 .map_err(|e| {
                     ApicentricError::runtime_error(
@@ -855,6 +1337,7 @@ impl ServiceInstance {
                 "CORS preflight response sent",
                 204,
                 None,
+                None,
             )
             .await;
             Self::record_log(
@@ -865,12 +1348,14 @@ impl ServiceInstance {
                 path,
                 StatusCode::NO_CONTENT.as_u16(),
                 None,
+                Some(started),
             )
             .await;
             return Ok(resp);
         }
 
-        // Parse request body if present
+        // Parse request body if present. Polling it is what makes hyper answer
+        // `Expect: 100-continue`, so clients holding back uploads send them now.
         let body_bytes = match http_body_util::BodyExt::collect(body).await {
             Ok(collected) => collected.to_bytes(),
             Err(_) => {
@@ -894,6 +1379,7 @@ impl ServiceInstance {
                     path,
                     StatusCode::BAD_REQUEST.as_u16(),
                     None,
+                    Some(started),
                 )
                 .await;
                 return Ok(resp);
@@ -901,15 +1387,15 @@ impl ServiceInstance {
         };
 
         let request_body = if !body_bytes.is_empty() {
-            let body_str = String::from_utf8_lossy(&body_bytes);
             Self::record_log(
                 &state,
                 &service_name,
                 None,
                 "DEBUG",
-                &format!("Request body: {}", body_str),
+                &format!("Request body: {}", describe_body(&body_bytes)),
                 200,
                 None,
+                None,
             )
             .await;
 
@@ -921,39 +1407,32 @@ impl ServiceInstance {
                 .unwrap_or("")
                 .to_lowercase();
 
-            if content_type.contains("application/x-www-form-urlencoded") {
-                // Parse form-encoded body
-                let mut map = serde_json::Map::new();
-                for (k, v) in url::form_urlencoded::parse(body_str.as_bytes()) {
-                    map.insert(k.to_string(), Value::String(v.into_owned()));
+            match (
+                std::str::from_utf8(&body_bytes),
+                body_content_type(&content_type),
+            ) {
+                // Binary payloads are left unparsed rather than lossily decoded
+                (Err(_), _) => None,
+                (Ok(body_str), "application/x-www-form-urlencoded") => {
+                    // Parse form-encoded body
+                    let mut map = serde_json::Map::new();
+                    for (k, v) in url::form_urlencoded::parse(body_str.as_bytes()) {
+                        map.insert(k.to_string(), Value::String(v.into_owned()));
+                    }
+                    Some(Value::Object(map))
+                }
+                (Ok(body_str), "application/xml") => {
+                    // Parse SOAP / XML body so templates can access
+                    // `request.body.Envelope.Body.<Op>.<field>` like JSON.
+                    crate::simulator::soap::xml_to_value(body_str).ok()
                 }
-                Some(Value::Object(map))
-            } else if crate::simulator::soap::is_xml_content_type(&content_type) {
-                // Parse SOAP / XML body so templates can access
-                // `request.body.Envelope.Body.<Op>.<field>` like JSON.
-                crate::simulator::soap::xml_to_value(&body_str).ok()
-            } else {
                 // Try to parse as JSON
-                serde_json::from_str::<Value>(&body_str).ok()
+                (Ok(body_str), _) => serde_json::from_str::<Value>(body_str).ok(),
             }
         } else {
             None
         };
 
-        // Remove base path from request path if it matches
-        let relative_path = if path.starts_with(&base_path) {
-            &path[base_path.len()..]
-        } else {
-            path
-        };
-
-        // Ensure relative path starts with '/'
-        let relative_path = if relative_path.is_empty() || !relative_path.starts_with('/') {
-            format!("/{}", relative_path.trim_start_matches('/'))
-        } else {
-            relative_path.to_string()
-        };
-
         // Handle GraphQL endpoint if configured
         if let Some(gql) = &graphql {
             if let Some((resp, status)) = handle_graphql_request(
@@ -970,13 +1449,62 @@ impl ServiceInstance {
             )
             .await
             {
-                Self::record_log(&state, &service_name, None, method, path, status, None).await;
+                Self::record_log(
+                    &state,
+                    &service_name,
+                    None,
+                    method,
+                    path,
+                    status,
+                    None,
+                    Some(started),
+                )
+                .await;
                 return Ok(resp);
             }
         }
 
-        // Internal logs endpoint
-        if method == "GET" && relative_path == "/__apicentric/logs" {
+        // Internal logs endpoint, unless disabled with `internal_endpoints: false`
+        let internal_endpoints = definition
+            .read()
+            .unwrap()
+            .server
+            .as_ref()
+            .and_then(|s| s.internal_endpoints)
+            .unwrap_or(true);
+        if internal_endpoints && method == "GET" && relative_path == "/__apicentric/logs" {
+            let admin_token = std::env::var("APICENTRIC_ADMIN_TOKEN").ok();
+            if !auth::internal_access_allowed(
+                auth_cfg.as_ref(),
+                admin_token.as_deref(),
+                headers.get("authorization").map(String::as_str),
+            ) {
+                let resp = Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .header("content-type", "application/json")
+                    .header("www-authenticate", "Bearer")
+                    .body(Full::new(Bytes::from(
+                        r#"{"error": "Unauthorized: missing or invalid token"}"#,
+                    )))
+                    .map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build logs response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                Self::record_log(
+                    &state,
+                    &service_name,
+                    None,
+                    method,
+                    path,
+                    StatusCode::UNAUTHORIZED.as_u16(),
+                    None,
+                    Some(started),
+                )
+                .await;
+                return Ok(resp);
+            }
             let limit = query_params
                 .get("limit")
                 .and_then(|v| v.parse::<usize>().ok())
@@ -1007,23 +1535,536 @@ impl ServiceInstance {
                         None::<String>,
                     )
                 })?;
-            Self::record_log(&state, &service_name, None, method, path, 200, None).await;
-            return Ok(resp);
-        }
+            Self::record_log(
+                &state,
+                &service_name,
+                None,
+                method,
+                path,
+                200,
+                None,
+                Some(started),
+            )
+            .await;
+            return Ok(resp);
+        }
 
         // Find matching endpoint with parameter extraction
-        let route_match =
-            Self::find_endpoint_with_params_static(&endpoints, method, &relative_path, &headers);
+        let route_match = match Self::find_endpoint_with_params_static(
+            &endpoints,
+            &compiled_routes,
+            method,
+            &relative_path,
+            &headers,
+            &query_params,
+        ) {
+            Ok(route_match) => route_match,
+            Err(message) => {
+                let resp = Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .header("content-type", "application/json")
+                    .body(Full::new(Bytes::from(
+                        serde_json::json!({ "error": message }).to_string(),
+                    )))
+                    .map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build bad request response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                Self::record_log(
+                    &state,
+                    &service_name,
+                    None,
+                    method,
+                    path,
+                    StatusCode::BAD_REQUEST.as_u16(),
+                    None,
+                    Some(started),
+                )
+                .await;
+                return Ok(resp);
+            }
+        };
+
+        // Retry with the trailing slash toggled unless the service is strict about it
+        let route_match = match (route_match, Self::toggle_trailing_slash(&relative_path)) {
+            (None, Some(alternate)) if trailing_slash != TrailingSlashMode::Strict => {
+                let alternate_match = Self::find_endpoint_with_params_static(
+                    &endpoints,
+                    &compiled_routes,
+                    method,
+                    &alternate,
+                    &headers,
+                    &query_params,
+                )
+                .ok()
+                .flatten();
+                if alternate_match.is_some() && trailing_slash == TrailingSlashMode::Redirect {
+                    let mut location =
+                        Self::toggle_trailing_slash(path).unwrap_or_else(|| path.into());
+                    if let Some(query) = parts.uri.query() {
+                        location = format!("{}?{}", location, query);
+                    }
+                    let resp = Response::builder()
+                        .status(StatusCode::PERMANENT_REDIRECT)
+                        .header("location", location)
+                        .body(Full::new(Bytes::new()))
+                        .map_err(|e| {
+                            ApicentricError::runtime_error(
+                                format!("Failed to build redirect response: {}", e),
+                                None::<String>,
+                            )
+                        })?;
+                    Self::record_log(
+                        &state,
+                        &service_name,
+                        None,
+                        method,
+                        path,
+                        StatusCode::PERMANENT_REDIRECT.as_u16(),
+                        None,
+                        Some(started),
+                    )
+                    .await;
+                    return Ok(resp);
+                }
+                alternate_match
+            }
+            (route_match, _) => route_match,
+        };
+
+        // The matched endpoint's behavior overrides the service's
+        let behavior = BehaviorConfig::resolve(
+            route_match
+                .as_ref()
+                .and_then(|m| m.endpoint.behavior.as_ref()),
+            service_behavior.as_ref(),
+            None,
+        );
+
+        // Rate limiting: the window lives in the shared state so every
+        // connection counts against the same limit. An endpoint with its own
+        // limit gets its own window.
+        let limit = behavior.rate_limiting.as_ref().filter(|rl| rl.enabled);
+        if let Some(limit) = limit {
+            let scope = route_match
+                .as_ref()
+                .filter(|m| {
+                    m.endpoint
+                        .behavior
+                        .as_ref()
+                        .is_some_and(|b| b.rate_limiting.is_some())
+                })
+                .map(|m| m.endpoint_index);
+            let checked = state
+                .write()
+                .await
+                .check_rate_limit(scope, limit.requests_per_minute);
+            if let Err(wait) = checked {
+                let resp = Response::builder()
+                    .status(StatusCode::TOO_MANY_REQUESTS)
+                    .header("content-type", "application/json")
+                    .header(
+                        "retry-after",
+                        rate_limit::retry_after_secs(wait).to_string(),
+                    )
+                    .body(Full::new(Bytes::from(
+                        serde_json::json!({ "error": "Too many requests" }).to_string(),
+                    )))
+                    .map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build rate limit response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                Self::record_log(
+                    &state,
+                    &service_name,
+                    None,
+                    method,
+                    path,
+                    429,
+                    None,
+                    Some(started),
+                )
+                .await;
+                return Ok(resp);
+            }
+        }
+
+        // Chaos testing: fail a share of requests before any response is built
+        let injected = behavior
+            .error_simulation
+            .as_ref()
+            .and_then(|sim| sim.roll(&mut rand::thread_rng()));
+        if let Some(code) = injected {
+            let status = StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            let resp = Response::builder()
+                .status(status)
+                .header("content-type", "application/json")
+                .body(Full::new(Bytes::from(
+                    serde_json::json!({
+                        "error": "Simulated error",
+                        "status": status.as_u16(),
+                    })
+                    .to_string(),
+                )))
+                .map_err(|e| {
+                    ApicentricError::runtime_error(
+                        format!("Failed to build simulated error response: {}", e),
+                        None::<String>,
+                    )
+                })?;
+            Self::record_log(
+                &state,
+                &service_name,
+                None,
+                method,
+                path,
+                status.as_u16(),
+                None,
+                Some(started),
+            )
+            .await;
+            return Ok(resp);
+        }
 
         match route_match {
             Some(route_match) => {
+                // Requests carrying the tenant header get their own state partition
+                let tenant = tenant_header
+                    .as_ref()
+                    .and_then(|name| headers.get(&name.to_ascii_lowercase()))
+                    .cloned();
+                if let Some(tenant) = tenant.as_deref() {
+                    state.write().await.partition_mut(Some(tenant));
+                }
+
+                // Enforce the scopes the endpoint requires from a bearer token
+                if let Some(requires) = route_match
+                    .endpoint
+                    .requires
+                    .as_ref()
+                    .filter(|r| !r.is_empty())
+                {
+                    if let Err(status) = auth::authorize(
+                        auth_cfg.as_ref(),
+                        headers.get("authorization").map(String::as_str),
+                        requires,
+                    ) {
+                        let (challenge, message) = if status == StatusCode::FORBIDDEN {
+                            (
+                                format!(
+                                    "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+                                    requires.join(" ")
+                                ),
+                                "Forbidden: token lacks a required scope",
+                            )
+                        } else {
+                            (
+                                "Bearer".to_string(),
+                                "Unauthorized: missing or invalid token",
+                            )
+                        };
+                        let resp = Response::builder()
+                            .status(status)
+                            .header("content-type", "application/json")
+                            .header("www-authenticate", challenge)
+                            .body(Full::new(Bytes::from(
+                                serde_json::json!({ "error": message }).to_string(),
+                            )))
+                            .map_err(|e| {
+                                ApicentricError::runtime_error(
+                                    format!("Failed to build auth response: {}", e),
+                                    None::<String>,
+                                )
+                            })?;
+                        Self::record_log(
+                            &state,
+                            &service_name,
+                            Some(route_match.endpoint_index),
+                            method,
+                            path,
+                            status.as_u16(),
+                            None,
+                            Some(started),
+                        )
+                        .await;
+                        return Ok(resp);
+                    }
+                }
+
+                // Reject bodies that don't conform to the endpoint's request model
+                if let Some((model, body_def)) = route_match
+                    .endpoint
+                    .request_body
+                    .as_ref()
+                    .filter(|body_def| body_def.validate)
+                    .and_then(|body_def| Some((body_def.schema.as_deref()?, body_def)))
+                {
+                    let errors = match &request_body {
+                        Some(body) => {
+                            let def = definition.read().unwrap();
+                            let no_models = Default::default();
+                            body_schema::validate(
+                                def.models.as_ref().unwrap_or(&no_models),
+                                model,
+                                body,
+                            )
+                        }
+                        None if body_bytes.is_empty() && !body_def.required => Vec::new(),
+                        None if body_bytes.is_empty() => {
+                            vec!["Request body is required".to_string()]
+                        }
+                        None => vec!["Request body is not valid JSON".to_string()],
+                    };
+                    if !errors.is_empty() {
+                        let resp = Response::builder()
+                            .status(StatusCode::UNPROCESSABLE_ENTITY)
+                            .header("content-type", "application/json")
+                            .body(Full::new(Bytes::from(
+                                serde_json::json!({
+                                    "error": format!("Request body does not match model '{}'", model),
+                                    "details": errors,
+                                })
+                                .to_string(),
+                            )))
+                            .map_err(|e| {
+                                ApicentricError::runtime_error(
+                                    format!("Failed to build validation response: {}", e),
+                                    None::<String>,
+                                )
+                            })?;
+                        Self::record_log(
+                            &state,
+                            &service_name,
+                            Some(route_match.endpoint_index),
+                            method,
+                            path,
+                            StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+                            None,
+                            Some(started),
+                        )
+                        .await;
+                        return Ok(resp);
+                    }
+                }
+
+                let compression_cfg = behavior.compression.clone().or(compression_cfg);
+                if let Some(latency) = &behavior.latency {
+                    let delay_ms = latency.sample_ms(&mut rand::thread_rng());
+                    tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                }
+                // Ad-hoc latency requested by the client on top of the configured one
+                if let Some(delay_ms) = headers
+                    .get("x-mock-delay")
+                    .and_then(|value| behavior.mock_delay_ms(value))
+                {
+                    tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                }
+
+                // Reject writes made against a stale version of the resource
+                if let (Some(template), Some(if_match)) =
+                    (&route_match.endpoint.etag, headers.get("if-match"))
+                {
+                    let request_context = RequestContext::from_request_data(
+                        method.to_string(),
+                        relative_path.clone(),
+                        query_params.clone(),
+                        headers.clone(),
+                        request_body.clone(),
+                    );
+                    let etag = Self::compute_etag(
+                        template,
+                        &state,
+                        tenant.as_deref(),
+                        &route_match.path_params,
+                        request_context,
+                        &template_engine,
+                    )
+                    .await;
+                    if !Self::if_match_satisfied(if_match, &etag) {
+                        let resp = Response::builder()
+                            .status(StatusCode::PRECONDITION_FAILED)
+                            .header("content-type", "application/json")
+                            .header("etag", &etag)
+                            .body(Full::new(Bytes::from(
+                                r#"{"error": "Precondition failed: resource has been modified"}"#,
+                            )))
+                            .map_err(|e| {
+                                ApicentricError::runtime_error(
+                                    format!("Failed to build precondition response: {}", e),
+                                    None::<String>,
+                                )
+                            })?;
+                        Self::record_log(
+                            &state,
+                            &service_name,
+                            Some(route_match.endpoint_index),
+                            method,
+                            path,
+                            StatusCode::PRECONDITION_FAILED.as_u16(),
+                            None,
+                            Some(started),
+                        )
+                        .await;
+                        return Ok(resp);
+                    }
+                }
+
+                // Advance the state machine; hits from a state the transition
+                // does not start from are rejected
+                if let Some(transition) = &route_match.endpoint.transition {
+                    let moved = state
+                        .write()
+                        .await
+                        .partition_mut(tenant.as_deref())
+                        .apply_transition(transition);
+                    if let Err(current) = moved {
+                        let resp = Response::builder()
+                            .status(StatusCode::CONFLICT)
+                            .header("content-type", "application/json")
+                            .body(Full::new(Bytes::from(
+                                serde_json::json!({
+                                    "error": format!(
+                                        "Invalid transition from '{}' to '{}'",
+                                        current, transition.to
+                                    ),
+                                    "state": current,
+                                })
+                                .to_string(),
+                            )))
+                            .map_err(|e| {
+                                ApicentricError::runtime_error(
+                                    format!("Failed to build transition response: {}", e),
+                                    None::<String>,
+                                )
+                            })?;
+                        Self::record_log(
+                            &state,
+                            &service_name,
+                            Some(route_match.endpoint_index),
+                            method,
+                            path,
+                            StatusCode::CONFLICT.as_u16(),
+                            None,
+                            Some(started),
+                        )
+                        .await;
+                        return Ok(resp);
+                    }
+                }
+
+                // WebSocket endpoints hand the connection to their own task
+                if route_match.endpoint.kind == EndpointKind::WebSocket {
+                    let stream_cfg = route_match.endpoint.stream.clone().unwrap_or_default();
+                    let events = EventStream {
+                        initial: stream_cfg.initial,
+                        periodic: stream_cfg.periodic,
+                        template_engine: Arc::clone(&template_engine),
+                        state: Arc::clone(&state),
+                        tenant: tenant.clone(),
+                        path_params: route_match.path_params.clone(),
+                        request: RequestContext::from_request_data(
+                            method.to_string(),
+                            relative_path.clone(),
+                            query_params.clone(),
+                            headers.clone(),
+                            None,
+                        )
+                        .with_client_cert(client_cert.clone()),
+                    };
+                    let log = websocket::ConnectionLog {
+                        service: service_name.clone(),
+                        endpoint: route_match.endpoint_index,
+                        path: path.to_string(),
+                    };
+                    let resp = websocket::accept(
+                        &parts.headers,
+                        &mut parts.extensions,
+                        events,
+                        stream_cfg.on_message,
+                        log,
+                    )?;
+                    Self::record_log(
+                        &state,
+                        &service_name,
+                        Some(route_match.endpoint_index),
+                        method,
+                        path,
+                        resp.status().as_u16(),
+                        None,
+                        Some(started),
+                    )
+                    .await;
+                    return Ok(resp);
+                }
+
+                // Event stream endpoints answer with messages until the client leaves
+                if route_match.endpoint.is_event_stream() {
+                    let stream_cfg = route_match.endpoint.stream.clone().unwrap_or_default();
+                    let mut response = Response::builder()
+                        .status(StatusCode::OK)
+                        .header("content-type", "text/event-stream")
+                        .header("cache-control", "no-cache");
+                    let origin_hdr = headers.get("origin").map(String::as_str).unwrap_or("");
+                    for (name, value) in Self::cors_headers(cors_cfg.as_ref(), origin_hdr) {
+                        response = response.header(name, value);
+                    }
+                    let mut resp = response.body(Full::new(Bytes::new())).map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build event stream response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                    resp.extensions_mut().insert(EventStream {
+                        initial: stream_cfg.initial,
+                        periodic: stream_cfg.periodic,
+                        template_engine: Arc::clone(&template_engine),
+                        state: Arc::clone(&state),
+                        tenant: tenant.clone(),
+                        path_params: route_match.path_params.clone(),
+                        request: RequestContext::from_request_data(
+                            method.to_string(),
+                            relative_path.clone(),
+                            query_params.clone(),
+                            headers.clone(),
+                            request_body.clone(),
+                        )
+                        .with_client_cert(client_cert.clone()),
+                    });
+                    Self::record_log(
+                        &state,
+                        &service_name,
+                        Some(route_match.endpoint_index),
+                        method,
+                        path,
+                        StatusCode::OK.as_u16(),
+                        None,
+                        Some(started),
+                    )
+                    .await;
+                    return Ok(resp);
+                }
+
                 // Evaluate conditions to find the right response
                 let mut selected_response: Option<ResponseDefinition> = None;
                 let mut selected_status = 200u16;
 
+                // An explicitly requested example wins over scenarios and conditions
+                let named_example = query_params
+                    .get("__example")
+                    .and_then(|name| Self::select_named_example(&route_match.endpoint, name));
+
+                let example_requested = named_example.is_some();
+
                 // Try to match explicit or rotating scenarios
                 let active = active_scenario.read().await.clone();
-                if let Some((status, resp)) = Self::match_scenario(
+                if let Some((status, resp)) = named_example {
+                    selected_status = status;
+                    selected_response = Some(resp);
+                } else if let Some((status, resp)) = Self::match_scenario(
                     &route_match.endpoint,
                     &state,
                     route_match.endpoint_index,
@@ -1031,6 +2072,17 @@ impl ServiceInstance {
                     &query_params,
                     &headers,
                     &request_body,
+                    &body_bytes,
+                )
+                .await
+                {
+                    selected_status = status;
+                    selected_response = Some(resp);
+                } else if let Some((status, resp)) = Self::take_limited_response(
+                    &route_match.endpoint,
+                    route_match.endpoint_index,
+                    &state,
+                    tenant.as_deref(),
                 )
                 .await
                 {
@@ -1039,6 +2091,10 @@ impl ServiceInstance {
                 } else {
                     // Try to find a response with a matching condition
                     for (status_code, response_def) in &route_match.endpoint.responses {
+                        if response_def.serve_times.is_some() {
+                            // Limited responses were considered above and are used up
+                            continue;
+                        }
                         if let Some(ref condition) = response_def.condition {
                             // Create template context for condition evaluation
                             let state_guard = state.read().await;
@@ -1048,10 +2104,11 @@ impl ServiceInstance {
                                 query_params.clone(),
                                 headers.clone(),
                                 request_body.clone(),
-                            );
+                            )
+                            .with_client_cert(client_cert.clone());
 
                             let template_context = TemplateContext::new(
-                                &state_guard,
+                                state_guard.partition(tenant.as_deref()),
                                 &route_match.path_params,
                                 request_context,
                             );
@@ -1085,11 +2142,19 @@ impl ServiceInstance {
 
                     // If no conditional response matched, use default (200 if available)
                     if selected_response.is_none() {
-                        if let Some(default_response) = route_match.endpoint.responses.get(&200) {
+                        if let Some(default_response) = route_match
+                            .endpoint
+                            .responses
+                            .get(&200)
+                            .filter(|response| response.serve_times.is_none())
+                        {
                             selected_response = Some(default_response.clone());
                             selected_status = 200;
-                        } else if let Some((status, response)) =
-                            route_match.endpoint.responses.iter().next()
+                        } else if let Some((status, response)) = route_match
+                            .endpoint
+                            .responses
+                            .iter()
+                            .find(|(_, response)| response.serve_times.is_none())
                         {
                             selected_response = Some(response.clone());
                             selected_status = *status;
@@ -1097,6 +2162,15 @@ impl ServiceInstance {
                     }
                 }
 
+                // Serve the example matching the client's preferred language
+                if let (Some(response_def), Some(accept_language), false) = (
+                    selected_response.as_mut(),
+                    headers.get("accept-language"),
+                    example_requested,
+                ) {
+                    locale::localize(response_def, accept_language);
+                }
+
                 if let Some(response_def) = selected_response {
                     let request_context = RequestContext::from_request_data(
                         method.to_string(),
@@ -1104,19 +2178,31 @@ impl ServiceInstance {
                         query_params.clone(),
                         headers.clone(),
                         request_body.clone(),
-                    );
+                    )
+                    .with_client_cert(client_cert.clone());
 
                     let mut script_body_override = None;
+                    let mut scripted_response = None;
                     if let Some(ref script_path) = response_def.script {
                         match Self::execute_script(
                             script_path.as_path(),
                             &state,
+                            tenant.as_deref(),
                             &scripting_engine,
                             &route_match.path_params,
                             &request_context,
                         )
                         .await
                         {
+                            Ok(result) if result.get("status").is_some() => {
+                                match ScriptResponse::from_value(&result) {
+                                    Some(scripted) => scripted_response = Some(scripted),
+                                    None => log::warn!(
+                                        "Script returned an invalid status: {}",
+                                        result["status"]
+                                    ),
+                                }
+                            }
                             Ok(result) => {
                                 if !result.is_null() {
                                     script_body_override = Some(result);
@@ -1128,14 +2214,131 @@ impl ServiceInstance {
                         }
                     }
 
+                    // A script that returns `{ status, headers, body }` answers directly
+                    if let Some(scripted) = scripted_response {
+                        let mut response = Response::builder().status(
+                            StatusCode::from_u16(scripted.status).unwrap_or(StatusCode::OK),
+                        );
+                        if !scripted
+                            .headers
+                            .iter()
+                            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                        {
+                            response = response.header("content-type", &response_def.content_type);
+                        }
+                        for (name, value) in &scripted.headers {
+                            response = response.header(name, value);
+                        }
+                        let origin_hdr = headers.get("origin").map(String::as_str).unwrap_or("");
+                        for (name, value) in Self::cors_headers(cors_cfg.as_ref(), origin_hdr) {
+                            response = response.header(name, value);
+                        }
+                        let response = response
+                            .body(Full::new(Bytes::from(scripted.body)))
+                            .map_err(|e| {
+                                ApicentricError::runtime_error(
+                                    format!("Failed to build scripted response: {}", e),
+                                    Some("Check the header names and values the script returns"),
+                                )
+                            })?;
+                        let logged_body =
+                            (!body_bytes.is_empty()).then(|| describe_body(&body_bytes));
+                        Self::record_log(
+                            &state,
+                            &service_name,
+                            Some(route_match.endpoint_index),
+                            method,
+                            path,
+                            scripted.status,
+                            logged_body,
+                            Some(started),
+                        )
+                        .await;
+                        return Ok(response);
+                    }
+
+                    // External commands answer with their stdout, but only when the
+                    // service opted in and allowlisted the program
+                    let exec_output = match &response_def.exec {
+                        Some(command) => {
+                            let server_cfg = definition.read().unwrap().server.clone();
+                            if let Err(reason) = exec::check_allowed(server_cfg.as_ref(), command) {
+                                log::warn!("Refused exec for {} {}: {}", method, path, reason);
+                                let resp = Response::builder()
+                                    .status(StatusCode::FORBIDDEN)
+                                    .header("content-type", "application/json")
+                                    .body(Full::new(Bytes::from(
+                                        serde_json::json!({ "error": reason }).to_string(),
+                                    )))
+                                    .map_err(|e| {
+                                        ApicentricError::runtime_error(
+                                            format!("Failed to build exec refusal: {}", e),
+                                            None::<String>,
+                                        )
+                                    })?;
+                                Self::record_log(
+                                    &state,
+                                    &service_name,
+                                    Some(route_match.endpoint_index),
+                                    method,
+                                    path,
+                                    StatusCode::FORBIDDEN.as_u16(),
+                                    None,
+                                    Some(started),
+                                )
+                                .await;
+                                return Ok(resp);
+                            }
+                            let input = serde_json::json!({
+                                "request": {
+                                    "method": request_context.method.clone(),
+                                    "path": request_context.path.clone(),
+                                    "query": request_context.query.clone(),
+                                    "headers": request_context.headers.clone(),
+                                    "body": request_context.body.clone(),
+                                },
+                                "params": route_match.path_params.all().clone(),
+                            });
+                            let timeout = server_cfg
+                                .as_ref()
+                                .and_then(|server| server.script_timeout_ms)
+                                .map_or(DEFAULT_SCRIPT_TIMEOUT, std::time::Duration::from_millis);
+                            Some(exec::run(command, serde_json::to_vec(&input)?, timeout).await?)
+                        }
+                        None => None,
+                    };
+
                     let state_guard = state.read().await;
                     let template_context = TemplateContext::new(
-                        &state_guard,
+                        state_guard.partition(tenant.as_deref()),
                         &route_match.path_params,
                         request_context,
                     );
                     drop(state_guard);
 
+                    // Binary bodies are sent verbatim, skipping templates and formatting
+                    let raw_body = match (&script_body_override, &response_def.body_base64) {
+                        _ if exec_output.is_some() => exec_output,
+                        (None, Some(encoded)) => Some(
+                            base64::engine::general_purpose::STANDARD
+                                .decode(encoded.trim())
+                                .map_err(|e| {
+                                    ApicentricError::runtime_error(
+                                        format!(
+                                            "Invalid body_base64 for {} {} in service '{}': {}",
+                                            method, path, service_name, e
+                                        ),
+                                        Some("Encode the raw bytes with standard base64"),
+                                    )
+                                })?,
+                        ),
+                        _ => None,
+                    };
+
+                    let body_file = match (&script_body_override, &response_def.body_file) {
+                        (None, Some(file)) => body_files.get(file),
+                        _ => None,
+                    };
                     let response_body = if let Some(body_v) = script_body_override {
                         if body_v.is_string() {
                             body_v.as_str().unwrap().to_string()
@@ -1143,12 +2346,15 @@ impl ServiceInstance {
                             serde_json::to_string(&body_v)
                                 .unwrap_or_else(|_| response_def.body.clone())
                         }
+                    } else if let Some(file) = body_file {
+                        file.text.clone()
                     } else {
                         response_def.body.clone()
                     };
-                    let processed_body = if response_body.contains("{{") {
+                    let processed_body = if raw_body.is_none() && response_body.contains("{{") {
                         match Self::process_response_body_template(
                             &response_body,
+                            &Self::body_template_name(route_match.endpoint_index, selected_status),
                             &template_context,
                             &template_engine,
                             &service_name,
@@ -1170,12 +2376,38 @@ impl ServiceInstance {
                         response_body
                     };
 
+                    // Defer to the real backend until the mock has data to serve
+                    if let (Some(EndpointFallback::Proxy), Some(base_url)) =
+                        (route_match.endpoint.fallback, proxy_base_url.as_deref())
+                    {
+                        if raw_body.is_none() && Self::is_empty_body(&processed_body) {
+                            return Self::forward_to_proxy(
+                                &proxy_client,
+                                base_url,
+                                &parts,
+                                &body_bytes,
+                                &relative_path,
+                                &state,
+                                &service_name,
+                                Some(route_match.endpoint_index),
+                                route_match
+                                    .endpoint
+                                    .proxy_response_transform
+                                    .as_ref()
+                                    .or(proxy_transform.as_ref())
+                                    .and_then(|expression| proxy_transforms.get(expression)),
+                                started,
+                            )
+                            .await;
+                        }
+                    }
+
                     if let Some(ref side_effects) = response_def.side_effects {
                         let mut state_guard = state.write().await;
                         for side_effect in side_effects {
                             if let Err(e) = Self::process_side_effect(
                                 side_effect,
-                                &mut state_guard,
+                                state_guard.partition_mut(tenant.as_deref()),
                                 &template_context,
                                 &template_engine,
                             ) {
@@ -1184,72 +2416,181 @@ impl ServiceInstance {
                         }
                     }
 
+                    let (content_type, records, processed_body) = match raw_body {
+                        Some(bytes) => (response_def.content_type.clone(), None, bytes),
+                        None => {
+                            let processed_body =
+                                match (response_def.sparse_fields, parts.uri.query()) {
+                                    (true, Some(query))
+                                        if pretty_json::is_json_content_type(
+                                            &response_def.content_type,
+                                        ) =>
+                                    {
+                                        sparse_fields::prune(
+                                            &processed_body,
+                                            &sparse_fields::requested_fields(query),
+                                        )
+                                        .unwrap_or(processed_body)
+                                    }
+                                    _ => processed_body,
+                                };
+                            let processed_body =
+                                match response_def.pretty_json.unwrap_or(service_pretty_json) {
+                                    true => pretty_json::pretty_print(
+                                        &response_def.content_type,
+                                        &processed_body,
+                                    )
+                                    .unwrap_or(processed_body),
+                                    false => processed_body,
+                                };
+
+                            // Array bodies with a streaming content type are sent one record at a time
+                            let framing =
+                                RecordFraming::from_content_type(&response_def.content_type);
+                            let records = framing.as_ref().and_then(|f| f.frame(&processed_body));
+                            let content_type = match (&framing, &records) {
+                                (Some(framing), Some(_)) => {
+                                    framing.content_type(&response_def.content_type)
+                                }
+                                _ => response_def.content_type.clone(),
+                            };
+                            let processed_body = match &records {
+                                Some(records) => records.concat(),
+                                None => processed_body.into_bytes(),
+                            };
+                            (content_type, records, processed_body)
+                        }
+                    };
+
+                    // Bodies stored gzip-compressed go out as stored unless they were rewritten
+                    let stored_gzip = body_file
+                        .and_then(|file| {
+                            file.gzip
+                                .as_ref()
+                                .filter(|_| file.text.as_bytes() == processed_body)
+                        })
+                        .filter(|_| {
+                            headers.get("accept-encoding").is_some_and(|accept| {
+                                compression::negotiate(accept, &["gzip".to_string()]).is_some()
+                            })
+                        });
+
+                    // Compress whole bodies with the best encoding both sides accept
+                    let encoding = match stored_gzip {
+                        Some(gzip) => Some(("gzip".to_string(), gzip.to_vec())),
+                        None => compression_cfg
+                            .as_ref()
+                            .filter(|cfg| records.is_none() && processed_body.len() >= cfg.min_size)
+                            .and_then(|cfg| {
+                                let accept = headers.get("accept-encoding")?;
+                                let encoding = compression::negotiate(accept, &cfg.encodings)?;
+                                compression::compress(encoding, &processed_body)
+                                    .map(|body| (encoding.to_string(), body))
+                            }),
+                    };
+                    let (processed_body, content_encoding) = match encoding {
+                        Some((encoding, body)) => (body, Some(encoding)),
+                        None => (processed_body, None),
+                    };
+
                     let mut response = Response::builder()
                         .status(StatusCode::from_u16(selected_status).unwrap_or(StatusCode::OK))
-                        .header("content-type", &response_def.content_type);
+                        .header("content-type", content_type);
+                    if compression_cfg.is_some() || body_file.is_some_and(|f| f.gzip.is_some()) {
+                        response = response.header("vary", "accept-encoding");
+                    }
+                    if let Some(encoding) = content_encoding {
+                        response = response.header("content-encoding", encoding);
+                    }
 
                     if let Some(ref headers_map) = response_def.headers {
-                        for (key, value) in headers_map {
+                        // Multi-valued headers (e.g. Set-Cookie) hold one value per line
+                        for (key, line, value) in headers_map.iter().flat_map(|(key, values)| {
+                            values
+                                .lines()
+                                .enumerate()
+                                .map(move |(line, v)| (key, line, v))
+                        }) {
                             let header_value = if value.contains("{{") {
-                                match template_engine.render(value, &template_context) {
+                                let name = Self::header_template_name(
+                                    route_match.endpoint_index,
+                                    selected_status,
+                                    key,
+                                    line,
+                                );
+                                match template_engine.render_cached(&name, value, &template_context)
+                                {
                                     Ok(v) => v,
                                     Err(e) => {
                                         log::warn!("Header template rendering error: {}", e);
+                                        value.to_string()
+                                    }
+                                }
+                            } else {
+                                value.to_string()
+                            };
+                            response = response.header(key, header_value);
+                        }
+                    }
+
+                    let mut trailers = HeaderMap::new();
+                    if let Some(ref trailers_map) = response_def.trailers {
+                        for (key, value) in trailers_map {
+                            let trailer_value = if value.contains("{{") {
+                                match template_engine.render(value, &template_context) {
+                                    Ok(v) => v,
+                                    Err(e) => {
+                                        log::warn!("Trailer template rendering error: {}", e);
                                         value.clone()
                                     }
                                 }
                             } else {
                                 value.clone()
                             };
-                            response = response.header(key, header_value);
+                            match (
+                                HeaderName::from_bytes(key.as_bytes()),
+                                HeaderValue::from_str(&trailer_value),
+                            ) {
+                                (Ok(name), Ok(value)) => {
+                                    trailers.insert(name, value);
+                                }
+                                _ => log::warn!("Invalid trailer '{}' ignored", key),
+                            }
                         }
                     }
+                    if !trailers.is_empty() {
+                        let names: Vec<&str> = trailers.keys().map(|k| k.as_str()).collect();
+                        response = response.header(TRAILER, names.join(", "));
+                    }
 
-                    // Add CORS headers if enabled
-                    if let Some(cfg) = &cors_cfg {
-                        let origin_hdr = headers.get("origin").cloned().unwrap_or_default();
-                        let allow_origin = if cfg.origins.iter().any(|o| o == "*") {
-                            "*".to_string()
-                        } else if cfg
-                            .origins
-                            .iter()
-                            .any(|o| o.eq_ignore_ascii_case(&origin_hdr))
-                        {
-                            origin_hdr.clone()
-                        } else {
-                            "*".to_string()
-                        };
-                        let allow_methods = cfg
-                            .methods
-                            .clone()
-                            .map(|v| v.join(", "))
-                            .unwrap_or_else(|| {
-                                "GET, POST, PUT, DELETE, PATCH, OPTIONS".to_string()
-                            });
-                        let allow_headers = cfg
-                            .headers
-                            .clone()
-                            .map(|v| v.join(", "))
-                            .unwrap_or_else(|| "Content-Type, Authorization".to_string());
-
-                        // println!("🔧 [{}] Adding CORS headers to response:", service_name);
-                        // println!("   🌍 Access-Control-Allow-Origin: {}", allow_origin);
-                        // println!("   🛠️ Access-Control-Allow-Methods: {}", allow_methods);
-                        // println!("   📋 Access-Control-Allow-Headers: {}", allow_headers);
-
-                        response = response
-                            .header("access-control-allow-origin", &allow_origin)
-                            .header("access-control-allow-methods", &allow_methods)
-                            .header("access-control-allow-headers", &allow_headers);
-                    } else {
-                        // println!(
-                        //     "⚠️ [{}] No CORS config, adding wildcard origin",
-                        //     service_name
-                        // );
-                        response = response.header("access-control-allow-origin", "*");
+                    // Tag the resource as it stands after any side effects
+                    if let Some(ref template) = route_match.endpoint.etag {
+                        let request_context = RequestContext::from_request_data(
+                            method.to_string(),
+                            relative_path.clone(),
+                            query_params.clone(),
+                            headers.clone(),
+                            request_body.clone(),
+                        );
+                        let etag = Self::compute_etag(
+                            template,
+                            &state,
+                            tenant.as_deref(),
+                            &route_match.path_params,
+                            request_context,
+                            &template_engine,
+                        )
+                        .await;
+                        response = response.header("etag", etag);
+                    }
+
+                    // Add CORS headers unless disabled with `cors: none`
+                    let origin_hdr = headers.get("origin").map(String::as_str).unwrap_or("");
+                    for (name, value) in Self::cors_headers(cors_cfg.as_ref(), origin_hdr) {
+                        response = response.header(name, value);
                     }
 
-                    let final_response = response
+                    let mut final_response = response
                         .body(Full::new(Bytes::from(processed_body)))
                         .map_err(|e| {
                             ApicentricError::runtime_error(
@@ -1257,7 +2598,20 @@ impl ServiceInstance {
                                 None::<String>,
                             )
                         })?;
+                    if !trailers.is_empty() {
+                        final_response
+                            .extensions_mut()
+                            .insert(ResponseTrailers(trailers));
+                    }
+                    if let (Some(records), Some(delay_ms)) = (records, response_def.record_delay_ms)
+                    {
+                        final_response.extensions_mut().insert(RecordStream {
+                            records,
+                            delay: std::time::Duration::from_millis(delay_ms),
+                        });
+                    }
 
+                    let logged_body = (!body_bytes.is_empty()).then(|| describe_body(&body_bytes));
                     Self::record_log(
                         &state,
                         &service_name,
@@ -1265,7 +2619,8 @@ impl ServiceInstance {
                         method,
                         path,
                         selected_status,
-                        None,
+                        logged_body,
+                        Some(started),
                     )
                     .await;
                     Ok(final_response)
@@ -1289,118 +2644,46 @@ impl ServiceInstance {
                         Some(route_match.endpoint_index),
                         method,
                         path,
-                        StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-                        None,
-                    )
-                    .await;
-                    Ok(resp)
-                }
-            }
-            None => {
-                // No matching endpoint found
-                if let Some(base_url) = proxy_base_url {
-                    // Forward request to proxy target
-                    let query = parts
-                        .uri
-                        .query()
-                        .map(|q| format!("?{}", q))
-                        .unwrap_or_default();
-                    let target_url = format!(
-                        "{}{}{}",
-                        base_url.trim_end_matches('/'),
-                        relative_path,
-                        query
-                    );
-
-                    let client = reqwest::Client::new();
-                    let req_method = reqwest::Method::from_bytes(method.as_bytes())
-                        .unwrap_or(reqwest::Method::GET);
-                    let mut builder = client.request(req_method, target_url);
-
-                    // Copy headers except host
-                    for (name, value) in parts.headers.iter() {
-                        if name != HOST {
-                            if let Ok(v) = value.to_str() {
-                                builder = builder.header(name.as_str(), v);
-                            }
-                        }
-                    }
-
-                    if !body_bytes.is_empty() {
-                        builder = builder.body(body_bytes.clone());
-                    }
-
-                    match builder.send().await {
-                        Ok(resp) => {
-                            let status = StatusCode::from_u16(resp.status().as_u16())
-                                .unwrap_or(StatusCode::OK);
-                            let headers = resp.headers().clone();
-                            let bytes = resp.bytes().await.unwrap_or_else(|_| Bytes::new());
-                            let mut response = Response::builder().status(status);
-                            for (name, value) in headers.iter() {
-                                if let Ok(v) = value.to_str() {
-                                    response = response.header(name.as_str(), v);
-                                }
-                            }
-                            let final_resp = response.body(Full::new(bytes)).map_err(|e| {
-                                ApicentricError::runtime_error(
-                                    format!("Failed to build proxy response: {}", e),
-                                    None::<String>,
-                                )
-                            })?;
-                            Self::record_log(
-                                &state,
-                                &service_name,
-                                None,
-                                method,
-                                path,
-                                status.as_u16(),
-                                None,
-                            )
-                            .await;
-                            Ok(final_resp)
-                        }
-                        Err(e) => {
-                            let resp = Response::builder()
-                                .status(StatusCode::BAD_GATEWAY)
-                                .header("content-type", "application/json")
-                                .body(Full::new(Bytes::from(format!(
-                                    r#"{{"error": "Proxy request failed", "details": "{}"}}"#,
-                                    e
-                                ))))
-                                .map_err(|e| {
-                                    ApicentricError::runtime_error(
-                                        format!("Failed to build proxy error response: {}", e),
-                                        None::<String>,
-                                    )
-                                })?;
-                            Self::record_log(
-                                &state,
-                                &service_name,
-                                None,
-                                method,
-                                path,
-                                StatusCode::BAD_GATEWAY.as_u16(),
-                                None,
-                            )
-                            .await;
-                            Ok(resp)
-                        }
-                    }
+                        StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                        None,
+                        Some(started),
+                    )
+                    .await;
+                    Ok(resp)
+                }
+            }
+            None => {
+                // No matching endpoint found
+                if let Some(base_url) = proxy_base_url {
+                    // Forward request to proxy target
+                    Self::forward_to_proxy(
+                        &proxy_client,
+                        &base_url,
+                        &parts,
+                        &body_bytes,
+                        &relative_path,
+                        &state,
+                        &service_name,
+                        None,
+                        proxy_transform
+                            .as_ref()
+                            .and_then(|expression| proxy_transforms.get(expression)),
+                        started,
+                    )
+                    .await
                 } else if record_unknown {
                     let (placeholder_endpoint, recorded_path) =
                         Self::build_recorded_endpoint(method, &relative_path);
 
                     let saved_definition = {
                         let mut def = definition.write().unwrap();
-                        match def.endpoints.as_mut() {
-                            Some(endpoints) => endpoints.push(placeholder_endpoint),
-                            None => def.endpoints = Some(vec![placeholder_endpoint]),
-                        }
+                        let endpoints = def.endpoints.get_or_insert_with(Vec::new);
+                        endpoints.push(placeholder_endpoint);
+                        *routes.write().unwrap() = Arc::new(RouteTable::compile(endpoints));
                         def.clone()
                     };
 
-                    if let Err(err) = storage.save_service(&saved_definition) {
+                    if let Err(err) = storage.save_service_version(&saved_definition) {
                         log::warn!(
                             "Failed to persist recorded endpoint for {} {}: {}",
                             method,
@@ -1424,38 +2707,9 @@ impl ServiceInstance {
                         .status(StatusCode::CONFLICT)
                         .header("content-type", "application/json");
 
-                    if let Some(cfg) = &cors_cfg {
-                        let origin_hdr = headers.get("origin").cloned().unwrap_or_default();
-                        let allow_origin = if cfg.origins.iter().any(|o| o == "*") {
-                            "*".to_string()
-                        } else if cfg
-                            .origins
-                            .iter()
-                            .any(|o| o.eq_ignore_ascii_case(&origin_hdr))
-                        {
-                            origin_hdr.clone()
-                        } else {
-                            "*".to_string()
-                        };
-                        let allow_methods = cfg
-                            .methods
-                            .clone()
-                            .map(|v| v.join(", "))
-                            .unwrap_or_else(|| {
-                                "GET, POST, PUT, DELETE, PATCH, OPTIONS".to_string()
-                            });
-                        let allow_headers = cfg
-                            .headers
-                            .clone()
-                            .map(|v| v.join(", "))
-                            .unwrap_or_else(|| "Content-Type, Authorization".to_string());
-
-                        response = response
-                            .header("access-control-allow-origin", &allow_origin)
-                            .header("access-control-allow-methods", &allow_methods)
-                            .header("access-control-allow-headers", &allow_headers);
-                    } else {
-                        response = response.header("access-control-allow-origin", "*");
+                    let origin_hdr = headers.get("origin").map(String::as_str).unwrap_or("");
+                    for (name, value) in Self::cors_headers(cors_cfg.as_ref(), origin_hdr) {
+                        response = response.header(name, value);
                     }
 
                     let resp = response
@@ -1474,6 +2728,7 @@ impl ServiceInstance {
                         path,
                         StatusCode::CONFLICT.as_u16(),
                         None,
+                        Some(started),
                     )
                     .await;
                     Ok(resp)
@@ -1481,10 +2736,15 @@ impl ServiceInstance {
                     let resp = Response::builder()
                         .status(StatusCode::NOT_FOUND)
                         .header("content-type", "application/json")
-                        .body(Full::new(Bytes::from(format!(
-                            r#"{{"error": "Endpoint not found", "method": "{}", "path": "{}", "service": "{}"}}"#,
-                            method, relative_path, service_name
-                        ))))
+                        .body(Full::new(Bytes::from(
+                            serde_json::json!({
+                                "error": "Endpoint not found",
+                                "method": method,
+                                "path": relative_path,
+                                "service": service_name,
+                            })
+                            .to_string(),
+                        )))
                         .map_err(|e| {
                             ApicentricError::runtime_error(
                                 format!("Failed to build not found response: {}", e),
@@ -1499,6 +2759,7 @@ impl ServiceInstance {
                         path,
                         StatusCode::NOT_FOUND.as_u16(),
                         None,
+                        Some(started),
                     )
                     .await;
                     Ok(resp)
@@ -1507,65 +2768,257 @@ impl ServiceInstance {
         }
     }
 
-    /// Static version of endpoint finding with parameter extraction
+    /// Apply a JMESPath transform to an uncompressed JSON proxy response
+    fn transform_proxied_body(
+        transform: &Transform,
+        headers: &reqwest::header::HeaderMap,
+        body: &Bytes,
+    ) -> Option<Bytes> {
+        let content_type = headers
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())?;
+        if !pretty_json::is_json_content_type(content_type)
+            || headers.contains_key(reqwest::header::CONTENT_ENCODING)
+        {
+            return None;
+        }
+        let document: Value = serde_json::from_slice(body).ok()?;
+        proxy_transform::apply(transform, &document).map(|result| Bytes::from(result.to_string()))
+    }
+
+    /// Forward a request to the proxy target and relay its response
+    #[allow(clippy::too_many_arguments)]
+    async fn forward_to_proxy(
+        proxy_client: &reqwest::Client,
+        base_url: &str,
+        parts: &hyper::http::request::Parts,
+        body_bytes: &Bytes,
+        relative_path: &str,
+        state: &Arc<RwLock<ServiceState>>,
+        service_name: &str,
+        endpoint_index: Option<usize>,
+        transform: Option<&Transform>,
+        started: Instant,
+    ) -> ApicentricResult<Response<Full<Bytes>>> {
+        let method = parts.method.as_str();
+        let path = parts.uri.path();
+        let query = parts
+            .uri
+            .query()
+            .map(|q| format!("?{}", q))
+            .unwrap_or_default();
+        let target_url = format!(
+            "{}{}{}",
+            base_url.trim_end_matches('/'),
+            relative_path,
+            query
+        );
+
+        let req_method =
+            reqwest::Method::from_bytes(method.as_bytes()).unwrap_or(reqwest::Method::GET);
+        let mut builder = proxy_client.request(req_method, target_url);
+
+        // Copy headers except host
+        for (name, value) in parts.headers.iter() {
+            if name != HOST {
+                if let Ok(v) = value.to_str() {
+                    builder = builder.header(name.as_str(), v);
+                }
+            }
+        }
+
+        if !body_bytes.is_empty() {
+            builder = builder.body(body_bytes.clone());
+        }
+
+        match builder.send().await {
+            Ok(resp) => {
+                let status = StatusCode::from_u16(resp.status().as_u16()).unwrap_or(StatusCode::OK);
+                let headers = resp.headers().clone();
+                let bytes = resp.bytes().await.unwrap_or_else(|_| Bytes::new());
+                let transformed = transform.and_then(|transform| {
+                    Self::transform_proxied_body(transform, &headers, &bytes)
+                });
+                let mut response = Response::builder().status(status);
+                for (name, value) in headers.iter() {
+                    // The reshaped body no longer matches the upstream length
+                    if transformed.is_some() && name == reqwest::header::CONTENT_LENGTH {
+                        continue;
+                    }
+                    if let Ok(v) = value.to_str() {
+                        response = response.header(name.as_str(), v);
+                    }
+                }
+                let bytes = transformed.unwrap_or(bytes);
+                let final_resp = response.body(Full::new(bytes)).map_err(|e| {
+                    ApicentricError::runtime_error(
+                        format!("Failed to build proxy response: {}", e),
+                        None::<String>,
+                    )
+                })?;
+                let logged_body = (!body_bytes.is_empty()).then(|| describe_body(body_bytes));
+                Self::record_log(
+                    state,
+                    service_name,
+                    endpoint_index,
+                    method,
+                    path,
+                    status.as_u16(),
+                    logged_body,
+                    Some(started),
+                )
+                .await;
+                Ok(final_resp)
+            }
+            Err(e) => {
+                let resp = Response::builder()
+                    .status(StatusCode::BAD_GATEWAY)
+                    .header("content-type", "application/json")
+                    .body(Full::new(Bytes::from(
+                        serde_json::json!({
+                            "error": "Proxy request failed",
+                            "details": e.to_string(),
+                        })
+                        .to_string(),
+                    )))
+                    .map_err(|e| {
+                        ApicentricError::runtime_error(
+                            format!("Failed to build proxy error response: {}", e),
+                            None::<String>,
+                        )
+                    })?;
+                Self::record_log(
+                    state,
+                    service_name,
+                    endpoint_index,
+                    method,
+                    path,
+                    StatusCode::BAD_GATEWAY.as_u16(),
+                    None,
+                    Some(started),
+                )
+                .await;
+                Ok(resp)
+            }
+        }
+    }
+
+    /// Static version of endpoint finding with parameter extraction, using the
+    /// regexes precompiled in a [`RouteTable`].
+    ///
+    /// Returns an error message when the only endpoints matching the path shape
+    /// declare path parameter types the request does not satisfy.
     fn find_endpoint_with_params_static(
         endpoints: &[EndpointDefinition],
+        routes: &RouteTable,
         method: &str,
         path: &str,
         headers: &HashMap<String, String>,
-    ) -> Option<RouteMatch> {
-        for (index, endpoint) in endpoints.iter().enumerate() {
+        query: &HashMap<String, String>,
+    ) -> Result<Option<RouteMatch>, String> {
+        let mut type_mismatch = None;
+        let candidates = routes
+            .order()
+            .iter()
+            .filter_map(|&index| Some((index, endpoints.get(index)?)));
+        for (index, endpoint) in candidates {
             if endpoint.method.to_uppercase() == method.to_uppercase()
                 && Self::headers_match(endpoint, headers)
+                && Self::query_matches(endpoint, query)
             {
-                if let Some(path_params) =
-                    Self::extract_path_parameters_static(&endpoint.path, path)
-                {
-                    return Some(RouteMatch {
-                        endpoint: endpoint.clone(),
-                        endpoint_index: index,
-                        path_params,
-                    });
+                match Self::extract_path_parameters_static(
+                    routes.regex(index),
+                    &endpoint.path,
+                    path,
+                    endpoint.parameters.as_deref().unwrap_or_default(),
+                ) {
+                    Some(Ok(path_params)) => {
+                        return Ok(Some(RouteMatch {
+                            endpoint: endpoint.clone(),
+                            endpoint_index: index,
+                            path_params,
+                        }));
+                    }
+                    Some(Err(message)) => {
+                        type_mismatch.get_or_insert(message);
+                    }
+                    None => {}
                 }
             }
         }
-        None
+        type_mismatch.map_or(Ok(None), Err)
+    }
+
+    /// `path` without `prefix`, when the prefix covers whole leading segments
+    fn strip_path_prefix(path: &str, prefix: &str) -> Option<String> {
+        let prefix = prefix.trim_end_matches('/');
+        match path.strip_prefix(prefix)? {
+            "" => Some("/".to_string()),
+            rest if rest.starts_with('/') => Some(rest.to_string()),
+            _ => None,
+        }
     }
 
-    /// Static version of path parameter extraction
+    /// The same path with its trailing slash added or removed; `None` for the root
+    fn toggle_trailing_slash(path: &str) -> Option<String> {
+        match path.strip_suffix('/') {
+            _ if path.is_empty() || path == "/" => None,
+            Some(trimmed) => Some(trimmed.to_string()),
+            None => Some(format!("{}/", path)),
+        }
+    }
+
+    /// Rank of each path segment: literal 2, `{param}` 1, `{*wildcard}` 0.
+    ///
+    /// Compared lexicographically, so the first differing segment decides.
+    fn path_specificity(path: &str) -> Vec<u8> {
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match segment.strip_prefix('{') {
+                Some(rest) if rest.starts_with('*') => 0,
+                Some(_) => 1,
+                None => 2,
+            })
+            .collect()
+    }
+
+    /// Static version of path parameter extraction.
+    ///
+    /// `None` means the path does not match; `Some(Err)` means it matches but a
+    /// segment does not parse as the type declared in `parameters`.
     fn extract_path_parameters_static(
+        regex: Option<&Regex>,
         endpoint_path: &str,
         request_path: &str,
-    ) -> Option<PathParameters> {
-        // Convert endpoint path pattern to regex
-        let regex_pattern = Self::endpoint_path_to_regex_static(endpoint_path);
-
-        match Regex::new(&regex_pattern) {
-            Ok(regex) => {
-                if let Some(captures) = regex.captures(request_path) {
-                    let mut params = PathParameters::new();
-
-                    // Extract named parameters
-                    for name in regex.capture_names().flatten() {
-                        if let Some(matched) = captures.name(name) {
-                            params.insert(name.to_string(), matched.as_str().to_string());
-                        }
-                    }
+        parameters: &[ParameterDefinition],
+    ) -> Option<Result<PathParameters, String>> {
+        let Some(regex) = regex else {
+            // Fallback to exact matching if regex compilation failed
+            return (endpoint_path == request_path).then(|| Ok(PathParameters::new()));
+        };
+        let captures = regex.captures(request_path)?;
+        let mut params = PathParameters::new();
 
-                    Some(params)
-                } else {
-                    None
-                }
-            }
-            Err(_) => {
-                // Fallback to exact matching if regex compilation fails
-                if endpoint_path == request_path {
-                    Some(PathParameters::new())
-                } else {
-                    None
+        // Extract named parameters
+        for name in regex.capture_names().flatten() {
+            if let Some(matched) = captures.name(name) {
+                let value = matched.as_str();
+                let declared = parameters
+                    .iter()
+                    .find(|p| p.name == name && matches!(p.location, ParameterLocation::Path));
+                if let Some(declared) = declared {
+                    if !declared.accepts(value) {
+                        return Some(Err(format!(
+                            "Path parameter '{}' must be of type {}, got '{}'",
+                            name, declared.param_type, value
+                        )));
+                    }
                 }
+                params.insert(name.to_string(), value.to_string());
             }
         }
+
+        Some(Ok(params))
     }
 
     fn build_recorded_endpoint(method: &str, relative_path: &str) -> (EndpointDefinition, String) {
@@ -1586,25 +3039,30 @@ impl ServiceInstance {
                 schema: None,
                 script: None,
                 headers: None,
+                trailers: None,
+                examples: None,
+                record_delay_ms: None,
+                pretty_json: None,
+                sparse_fields: false,
+                serve_times: None,
+                exec: None,
                 side_effects: None,
+                body_base64: None,
+                body_file: None,
             },
         );
 
         let endpoint = EndpointDefinition {
-            kind: EndpointKind::Http,
             method: method.to_uppercase(),
             path: normalized_path.clone(),
-            header_match: None,
             description: Some("Endpoint generado automáticamente desde tráfico real".to_string()),
             parameters: if parameters.is_empty() {
                 None
             } else {
                 Some(parameters)
             },
-            request_body: None,
             responses,
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         };
 
         (endpoint, normalized_path)
@@ -1671,11 +3129,39 @@ impl ServiceInstance {
             && stripped.chars().all(|c| c.is_ascii_hexdigit())
     }
 
-    /// Execute a user-provided script with request context
+    /// The lowest-status response still within its `serve_times` budget,
+    /// counting this serve against it
+    async fn take_limited_response(
+        endpoint: &EndpointDefinition,
+        endpoint_index: usize,
+        state: &Arc<RwLock<ServiceState>>,
+        tenant: Option<&str>,
+    ) -> Option<(u16, ResponseDefinition)> {
+        let mut limited: Vec<(u16, usize, &ResponseDefinition)> = endpoint
+            .responses
+            .iter()
+            .filter_map(|(status, response)| Some((*status, response.serve_times?, response)))
+            .collect();
+        if limited.is_empty() {
+            return None;
+        }
+        limited.sort_by_key(|(status, ..)| *status);
+
+        let mut state = state.write().await;
+        let partition = state.partition_mut(tenant);
+        limited
+            .into_iter()
+            .find(|(status, limit, _)| partition.take_serve(endpoint_index, *status, *limit))
+            .map(|(status, _, response)| (status, response.clone()))
+    }
+
+    /// Execute a user-provided script with request context, merging a returned
+    /// object into runtime data unless it is a response carrying a `status`
     async fn execute_script(
         script_path: &Path,
         state: &Arc<RwLock<ServiceState>>,
-        scripting_engine: &ScriptingEngine,
+        tenant: Option<&str>,
+        scripting_engine: &Arc<ScriptingEngine>,
         path_params: &PathParameters,
         request_context: &RequestContext,
     ) -> ApicentricResult<Value> {
@@ -1696,17 +3182,28 @@ impl ServiceInstance {
                 "body": request_context.body.clone(),
             },
             "params": path_params.all().clone(),
-            "fixtures": state_guard.all_fixtures().clone(),
-            "runtime": state_guard.all_runtime_data().clone(),
+            "fixtures": state_guard.partition(tenant).all_fixtures().clone(),
+            "runtime": state_guard.partition(tenant).all_runtime_data().clone(),
         });
         drop(state_guard);
 
-        let result = scripting_engine.execute(&script_source, &context)?;
+        // Scripts run on the blocking pool so a slow `fetch` cannot stall the server
+        let engine = Arc::clone(scripting_engine);
+        let result = tokio::task::spawn_blocking(move || engine.execute(&script_source, &context))
+            .await
+            .map_err(|e| {
+                ApicentricError::scripting_error(
+                    format!("Script task failed: {}", e),
+                    None::<String>,
+                )
+            })??;
 
-        if let serde_json::Value::Object(ref map) = result {
+        // Objects with a `status` are a whole response, not runtime data
+        if let Some(map) = result.as_object().filter(|map| !map.contains_key("status")) {
             let mut state_guard = state.write().await;
+            let partition = state_guard.partition_mut(tenant);
             for (k, v) in map {
-                state_guard.set_runtime_data(k.clone(), v.clone());
+                partition.set_runtime_data(k.clone(), v.clone());
             }
         }
 
@@ -1720,6 +3217,19 @@ impl ServiceInstance {
         template_context: &TemplateContext,
         template_engine: &TemplateEngine,
     ) -> ApicentricResult<()> {
+        if side_effect.action == "remove_from_fixture_by_param" {
+            let (field, keys) = Self::side_effect_item_keys(side_effect, template_context)?;
+            for key in &keys {
+                if state
+                    .remove_fixture_array_item_by_field(&side_effect.target, &field, key)?
+                    .is_some()
+                {
+                    break;
+                }
+            }
+            return Ok(());
+        }
+
         // Render the side effect value template
         let rendered_value = template_engine.render(&side_effect.value, template_context)?;
 
@@ -1741,6 +3251,19 @@ impl ServiceInstance {
             "remove_from_fixture" => {
                 state.remove_fixture(&side_effect.target);
             }
+            "update_fixture_by_param" => {
+                let (field, keys) = Self::side_effect_item_keys(side_effect, template_context)?;
+                for key in &keys {
+                    if state.update_fixture_array_item_by_field(
+                        &side_effect.target,
+                        &field,
+                        key,
+                        value.clone(),
+                    )? {
+                        break;
+                    }
+                }
+            }
             "set_runtime_data" => {
                 state.set_runtime_data(side_effect.target.clone(), value);
             }
@@ -1750,7 +3273,7 @@ impl ServiceInstance {
             _ => {
                 return Err(ApicentricError::runtime_error(
                     format!("Unknown side effect action: {}", side_effect.action),
-                    Some("Use supported actions: add_to_fixture, update_fixture, remove_from_fixture, set_runtime_data, remove_runtime_data")
+                    Some("Use supported actions: add_to_fixture, update_fixture, remove_from_fixture, update_fixture_by_param, remove_from_fixture_by_param, set_runtime_data, remove_runtime_data")
                 ));
             }
         }
@@ -1758,6 +3281,39 @@ impl ServiceInstance {
         Ok(())
     }
 
+    /// Field name and candidate values identifying the fixture item a
+    /// `*_by_param` side effect operates on. The path parameter is tried both as
+    /// a string and as JSON so `/users/1` matches `id: 1` as well as `id: "1"`.
+    fn side_effect_item_keys(
+        side_effect: &crate::simulator::config::SideEffect,
+        template_context: &TemplateContext,
+    ) -> ApicentricResult<(String, Vec<Value>)> {
+        let param = side_effect.param.as_deref().ok_or_else(|| {
+            ApicentricError::runtime_error(
+                format!("Side effect '{}' requires a param", side_effect.action),
+                Some("Set `param` to the path parameter naming the item, e.g. `param: id`"),
+            )
+        })?;
+        let raw = template_context.params.get(param).ok_or_else(|| {
+            ApicentricError::runtime_error(
+                format!("Path parameter '{}' not found for side effect", param),
+                Some("Use a parameter declared in the endpoint path, e.g. /users/{id}"),
+            )
+        })?;
+        let mut keys = Vec::new();
+        if let Ok(parsed) = serde_json::from_str::<Value>(raw) {
+            if !parsed.is_string() {
+                keys.push(parsed);
+            }
+        }
+        keys.push(Value::String(raw.clone()));
+        let field = side_effect
+            .field
+            .clone()
+            .unwrap_or_else(|| param.to_string());
+        Ok((field, keys))
+    }
+
     /// Static version of endpoint path to regex conversion
     fn endpoint_path_to_regex_static(endpoint_path: &str) -> String {
         let mut result = String::new();
@@ -1780,8 +3336,22 @@ impl ServiceInstance {
                         }
                     }
 
-                    if !param_name.is_empty() {
-                        result.push_str(&format!("(?P<{}>[^/]+)", param_name));
+                    // `{*name}` is a catch-all spanning the rest of the path and
+                    // `{name:type}` constrains a single segment
+                    if let Some(rest_name) = param_name.strip_prefix('*') {
+                        result.push_str(&format!("(?P<{}>.+)", rest_name));
+                    } else if !param_name.is_empty() {
+                        let (name, class) = match param_name.split_once(':') {
+                            Some((name, "int")) => (name, r"\d+"),
+                            Some((name, "uuid")) => (
+                                name,
+                                "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+                            ),
+                            // `string` and types rejected at load time
+                            Some((name, _)) => (name, "[^/]+"),
+                            None => (param_name.as_str(), "[^/]+"),
+                        };
+                        result.push_str(&format!("(?P<{}>{})", name, class));
                     }
                 }
                 // Escape special regex characters
@@ -1799,7 +3369,106 @@ impl ServiceInstance {
         format!("^{}$", result)
     }
 
-    /// Match a scenario based on query, header, or body conditions
+    /// CORS headers for a regular response. Without a CORS config only a
+    /// wildcard origin is sent; a disabled config (`cors: none`) sends nothing.
+    fn cors_headers(cors_cfg: Option<&CorsConfig>, origin: &str) -> Vec<(&'static str, String)> {
+        let Some(cfg) = cors_cfg else {
+            return vec![("access-control-allow-origin", "*".to_string())];
+        };
+        if !cfg.enabled {
+            return Vec::new();
+        }
+        let allow_origin = if cfg.origins.iter().any(|o| o == "*") {
+            "*".to_string()
+        } else if cfg.origins.iter().any(|o| o.eq_ignore_ascii_case(origin)) {
+            origin.to_string()
+        } else {
+            "*".to_string()
+        };
+        let allow_methods = cfg
+            .methods
+            .clone()
+            .map(|v| v.join(", "))
+            .unwrap_or_else(|| "GET, POST, PUT, DELETE, PATCH, OPTIONS".to_string());
+        let allow_headers = cfg
+            .headers
+            .clone()
+            .map(|v| v.join(", "))
+            .unwrap_or_else(|| "Content-Type, Authorization".to_string());
+        let mut headers = vec![
+            ("access-control-allow-origin", allow_origin),
+            ("access-control-allow-methods", allow_methods),
+            ("access-control-allow-headers", allow_headers),
+        ];
+        if !cfg.expose_headers.is_empty() {
+            headers.push((
+                "access-control-expose-headers",
+                cfg.expose_headers.join(", "),
+            ));
+        }
+        headers
+    }
+
+    /// Whether a rendered body carries no data (blank, `null`, `[]` or `{}`)
+    fn is_empty_body(body: &str) -> bool {
+        matches!(body.trim(), "" | "null" | "[]" | "{}")
+    }
+
+    /// Find the response declaring an example with the given name and return
+    /// it with the example as its body. Lower status codes are checked first.
+    fn select_named_example(
+        endpoint: &EndpointDefinition,
+        name: &str,
+    ) -> Option<(u16, ResponseDefinition)> {
+        let mut statuses: Vec<&u16> = endpoint.responses.keys().collect();
+        statuses.sort();
+        statuses.into_iter().find_map(|status| {
+            let response = &endpoint.responses[status];
+            let body = response.examples.as_ref()?.get(name)?;
+            let mut selected = response.clone();
+            selected.body = body.clone();
+            Some((*status, selected))
+        })
+    }
+
+    /// Render an endpoint's `etag` template against the current state and
+    /// hash it into a quoted entity tag
+    async fn compute_etag(
+        template: &str,
+        state: &Arc<RwLock<ServiceState>>,
+        tenant: Option<&str>,
+        path_params: &PathParameters,
+        request_context: RequestContext,
+        template_engine: &TemplateEngine,
+    ) -> String {
+        use std::hash::{Hash, Hasher};
+
+        let state_guard = state.read().await;
+        let template_context =
+            TemplateContext::new(state_guard.partition(tenant), path_params, request_context);
+        drop(state_guard);
+        let rendered = template_engine
+            .render(template, &template_context)
+            .unwrap_or_else(|e| {
+                log::warn!("ETag template rendering error: {}", e);
+                String::new()
+            });
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        rendered.hash(&mut hasher);
+        format!("\"{:016x}\"", hasher.finish())
+    }
+
+    /// Whether an `If-Match` header value accepts the given entity tag
+    fn if_match_satisfied(if_match: &str, etag: &str) -> bool {
+        if_match
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag == etag)
+    }
+
+    /// Match a scenario based on query, header, cookie, body, raw-body regex or
+    /// content-type conditions
+    #[allow(clippy::too_many_arguments)]
     async fn match_scenario(
         endpoint: &EndpointDefinition,
         state: &Arc<RwLock<ServiceState>>,
@@ -1808,6 +3477,7 @@ impl ServiceInstance {
         query: &HashMap<String, String>,
         headers: &HashMap<String, String>,
         body: &Option<Value>,
+        raw_body: &[u8],
     ) -> Option<(u16, ResponseDefinition)> {
         if let Some(scenarios) = &endpoint.scenarios {
             // First evaluate explicit conditions
@@ -1835,6 +3505,15 @@ impl ServiceInstance {
                             }
                         }
                     }
+                    if matches {
+                        if let Some(c) = &cond.cookies {
+                            let cookies = headers
+                                .get("cookie")
+                                .map(|header| parse_cookies(header))
+                                .unwrap_or_default();
+                            matches = c.iter().all(|(k, v)| cookies.get(k) == Some(v));
+                        }
+                    }
                     if matches {
                         if let Some(b) = &cond.body {
                             if let Some(Value::Object(obj)) = body {
@@ -1849,6 +3528,29 @@ impl ServiceInstance {
                             }
                         }
                     }
+                    if matches {
+                        // Raw-body regex for payloads that don't parse into keys (XML, text)
+                        if let Some(pattern) = &cond.body_regex {
+                            matches = match Regex::new(pattern) {
+                                Ok(re) => re.is_match(&String::from_utf8_lossy(raw_body)),
+                                Err(e) => {
+                                    log::warn!("Invalid body_regex '{}': {}", pattern, e);
+                                    false
+                                }
+                            };
+                        }
+                    }
+                    if matches {
+                        if let Some(expected) = &cond.content_type {
+                            let parsed_as = body.as_ref().map(|_| {
+                                body_content_type(
+                                    headers.get("content-type").map_or("", String::as_str),
+                                )
+                            });
+                            matches = parsed_as
+                                .is_some_and(|parsed_as| parsed_as.eq_ignore_ascii_case(expected));
+                        }
+                    }
                     if matches {
                         return Some((
                             scenario.response.status,
@@ -1883,7 +3585,17 @@ impl ServiceInstance {
                     .unwrap_or(ScenarioStrategy::Sequential);
                 let index = {
                     let mut guard = state.write().await;
-                    guard.next_response_index(endpoint_index, candidates.len(), strategy)
+                    if let ScenarioStrategy::Weighted = strategy {
+                        let weights: Vec<u32> =
+                            candidates.iter().map(|s| s.weight.unwrap_or(1)).collect();
+                        let client = candidates[0]
+                            .sticky
+                            .as_ref()
+                            .and_then(|key| key.client_id(headers));
+                        guard.weighted_response_index(endpoint_index, &weights, client)
+                    } else {
+                        guard.next_response_index(endpoint_index, candidates.len(), strategy)
+                    }
                 };
                 let scenario = candidates[index];
                 return Some((
@@ -1934,6 +3646,28 @@ impl ServiceInstance {
                     ));
                 }
                 seen_endpoints.insert(key);
+
+                if let Some(error) = endpoint.path_errors().into_iter().next() {
+                    return Err(ApicentricError::config_error(
+                        format!("{} of {} {}", error.message, endpoint.method, endpoint.path),
+                        error.suggestion,
+                    ));
+                }
+            }
+        }
+
+        // Body validation needs a model to validate against
+        for endpoint in definition.endpoints.iter().flatten() {
+            if let Some(ref request_body) = endpoint.request_body {
+                if request_body.validate && request_body.schema.is_none() {
+                    return Err(ApicentricError::config_error(
+                        format!(
+                            "Endpoint {} {} validates its request body but names no schema",
+                            endpoint.method, endpoint.path
+                        ),
+                        Some("Set request_body.schema to a model from the models section"),
+                    ));
+                }
             }
         }
 
@@ -1961,6 +3695,7 @@ impl ServiceInstance {
     /// Process response body template with robust error handling and validation
     fn process_response_body_template(
         response_body: &str,
+        template_name: &str,
         template_context: &TemplateContext,
         template_engine: &TemplateEngine,
         service_name: &str,
@@ -1969,7 +3704,7 @@ impl ServiceInstance {
     ) -> ApicentricResult<String> {
         let processed_body = if response_body.contains("{{") {
             // Template contains Handlebars placeholders, attempt to render
-            match template_engine.render(response_body, template_context) {
+            match template_engine.render_cached(template_name, response_body, template_context) {
                 Ok(rendered) => {
                     // Validate that rendered body is not empty when template was expected to produce content
                     let trimmed = rendered.trim();
@@ -2047,8 +3782,7 @@ impl ServiceInstance {
 mod tests {
     use super::*;
     use crate::simulator::config::{
-        EndpointKind, ResponseDefinition, ScenarioConditions, ScenarioDefinition, ScenarioResponse,
-        ServerConfig,
+        ResponseDefinition, ScenarioConditions, ScenarioDefinition, ScenarioResponse, ServerConfig,
     };
     use bytes::Bytes;
     use http_body_util::{BodyExt, Full};
@@ -2074,9 +3808,7 @@ mod tests {
             server: Some(ServerConfig {
                 port: Some(8001),
                 base_path: "/api/v1".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: {
@@ -2090,16 +3822,14 @@ mod tests {
                 );
                 Some(fixtures)
             },
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users".to_string(),
-                    header_match: None,
                     description: Some("Get all users".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2111,22 +3841,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users/1".to_string(),
-                    header_match: None,
                     description: Some("Get user by ID".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2138,17 +3872,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -2374,6 +4117,29 @@ mod tests {
             action: "add_to_fixture".to_string(),
             target: "users".to_string(),
             value: r#"{"id": 1, "name": "Alice"}"#.to_string(),
+            param: None,
+            field: None,
+        };
+
+        ServiceInstance::process_side_effect(
+            &side_effect,
+            &mut state,
+            &template_context,
+            &template_engine,
+        )
+        .unwrap();
+
+        let users = state.get_fixture("users").unwrap().as_array().unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0]["name"], "Alice");
+
+        // Test set_runtime_data side effect
+        let side_effect = SideEffect {
+            action: "set_runtime_data".to_string(),
+            target: "last_user_id".to_string(),
+            value: "1".to_string(),
+            param: None,
+            field: None,
         };
 
         ServiceInstance::process_side_effect(
@@ -2384,27 +4150,54 @@ mod tests {
         )
         .unwrap();
 
+        let last_id = state.get_runtime_data("last_user_id").unwrap();
+        assert_eq!(last_id, &serde_json::json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_remove_from_fixture_by_path_param() {
+        use crate::simulator::config::SideEffect;
+        use crate::simulator::template::{RequestContext, TemplateContext, TemplateEngine};
+
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let mut state = ServiceState::new(
+            Some(HashMap::from([(
+                "users".to_string(),
+                serde_json::json!([{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}]),
+            )])),
+            None,
+            storage,
+            None,
+        );
+
+        let template_engine = TemplateEngine::new().unwrap();
+        let mut params = PathParameters::new();
+        params.insert("id".to_string(), "2".to_string());
+        let request_context = RequestContext::from_request_data(
+            "DELETE".to_string(),
+            "/users/2".to_string(),
+            HashMap::new(),
+            HashMap::new(),
+            None,
+        );
+        let template_context = TemplateContext::new(&state, &params, request_context);
+
+        let side_effect: SideEffect = serde_yaml::from_str(
+            "action: remove_from_fixture_by_param\ntarget: users\nparam: id\n",
+        )
+        .unwrap();
+        ServiceInstance::process_side_effect(
+            &side_effect,
+            &mut state,
+            &template_context,
+            &template_engine,
+        )
+        .unwrap();
+
         let users = state.get_fixture("users").unwrap().as_array().unwrap();
         assert_eq!(users.len(), 1);
         assert_eq!(users[0]["name"], "Alice");
-
-        // Test set_runtime_data side effect
-        let side_effect = SideEffect {
-            action: "set_runtime_data".to_string(),
-            target: "last_user_id".to_string(),
-            value: "1".to_string(),
-        };
-
-        ServiceInstance::process_side_effect(
-            &side_effect,
-            &mut state,
-            &template_context,
-            &template_engine,
-        )
-        .unwrap();
-
-        let last_id = state.get_runtime_data("last_user_id").unwrap();
-        assert_eq!(last_id, &serde_json::json!(1));
+        assert!(users.iter().all(|u| u["id"] != 2));
     }
 
     #[tokio::test]
@@ -2426,6 +4219,28 @@ mod tests {
         assert!(endpoint.is_some());
     }
 
+    #[tokio::test]
+    async fn test_endpoint_finding_matches_like_live_requests() {
+        use crate::simulator::config::EndpointBuilder;
+        let definition = ServiceDefinition::builder("users")
+            .endpoint(EndpointBuilder::get("/users/{id}"))
+            .endpoint(EndpointBuilder::get("/users/me"))
+            .endpoint(EndpointBuilder::get("/orders/{id:int}"))
+            .build();
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, _) = broadcast::channel(100);
+        let service = ServiceInstance::new(definition, 8010, storage, tx).unwrap();
+
+        let headers = HashMap::new();
+        let route_match = service
+            .find_endpoint_with_params("GET", "/users/me", &headers)
+            .unwrap();
+        assert_eq!(route_match.endpoint_index, 1);
+        assert!(service
+            .find_endpoint("GET", "/orders/abc", &headers)
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_path_parameter_extraction() {
         let definition = create_test_service_definition_with_params();
@@ -2490,27 +4305,61 @@ mod tests {
 
     #[test]
     fn test_endpoint_path_to_regex() {
-        let definition = create_test_service_definition();
-        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
-        let (tx, _) = broadcast::channel(100);
-        let service = ServiceInstance::new(definition, 8009, storage, tx).unwrap();
-
         // Test simple parameter
-        let regex = service.endpoint_path_to_regex("/users/{id}");
+        let regex = ServiceInstance::endpoint_path_to_regex_static("/users/{id}");
         assert_eq!(regex, "^/users/(?P<id>[^/]+)$");
 
         // Test multiple parameters
-        let regex = service.endpoint_path_to_regex("/users/{userId}/orders/{orderId}");
+        let regex =
+            ServiceInstance::endpoint_path_to_regex_static("/users/{userId}/orders/{orderId}");
         assert_eq!(
             regex,
             "^/users/(?P<userId>[^/]+)/orders/(?P<orderId>[^/]+)$"
         );
 
         // Test no parameters
-        let regex = service.endpoint_path_to_regex("/users");
+        let regex = ServiceInstance::endpoint_path_to_regex_static("/users");
         assert_eq!(regex, "^/users$");
     }
 
+    #[test]
+    fn test_typed_path_parameters_constrain_segments() {
+        use crate::simulator::config::EndpointBuilder;
+        let endpoints = vec![
+            EndpointBuilder::get("/users/{id:int}").build(),
+            EndpointBuilder::get("/orders/{id:uuid}").build(),
+            EndpointBuilder::get("/users/{slug:string}").build(),
+        ];
+        let routes = RouteTable::compile(&endpoints);
+        let headers = HashMap::new();
+        let find = |path: &str| {
+            ServiceInstance::find_endpoint_with_params_static(
+                &endpoints,
+                &routes,
+                "GET",
+                path,
+                &headers,
+                &HashMap::new(),
+            )
+            .unwrap()
+        };
+
+        let numeric = find("/users/42").unwrap();
+        assert_eq!(numeric.endpoint_index, 0);
+        assert_eq!(numeric.path_params.get("id").unwrap(), "42");
+        let slug = find("/users/abc").unwrap();
+        assert_eq!(slug.endpoint_index, 2);
+        assert_eq!(slug.path_params.get("slug").unwrap(), "abc");
+
+        let order = find("/orders/123e4567-e89b-12d3-a456-426614174000").unwrap();
+        assert_eq!(
+            order.path_params.get("id").unwrap(),
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
+        assert!(find("/orders/123e4567-e89b-12d3").is_none());
+        assert!(find("/orders/not-a-uuid").is_none());
+    }
+
     #[tokio::test]
     async fn test_template_processing_with_params() {
         use crate::simulator::template::{RequestContext, TemplateContext, TemplateEngine};
@@ -2553,9 +4402,7 @@ mod tests {
             server: Some(ServerConfig {
                 port: Some(8001),
                 base_path: "/api/v1".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: {
@@ -2569,16 +4416,14 @@ mod tests {
                 );
                 Some(fixtures)
             },
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users/{id}".to_string(),
-                    header_match: None,
                     description: Some("Get user by ID".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2592,22 +4437,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/users/{userId}/orders/{orderId}".to_string(),
-                    header_match: None,
                     description: Some("Get user order".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2621,17 +4470,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -2645,16 +4503,15 @@ mod tests {
             server: Some(ServerConfig {
                 port: Some(8001),
                 base_path: "/api/v1".to_string(),
-                proxy_base_url: None,
-                cors: None,
-                record_unknown: false,
+                ..Default::default()
             }),
             models: None,
             fixtures: None,
+            fixture_sort: None,
             bucket: None,
+            partials: None,
             endpoints: Some(vec![
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/headers".to_string(),
                     header_match: Some({
@@ -2663,8 +4520,6 @@ mod tests {
                         headers
                     }),
                     description: Some("Get with header match".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2676,22 +4531,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
                 EndpointDefinition {
-                    kind: EndpointKind::Http,
                     method: "GET".to_string(),
                     path: "/headers".to_string(),
-                    header_match: None,
                     description: Some("Get without header match".to_string()),
-                    parameters: None,
-                    request_body: None,
                     responses: {
                         let mut responses = HashMap::new();
                         responses.insert(
@@ -2703,17 +4562,26 @@ mod tests {
                                 schema: None,
                                 script: None,
                                 headers: None,
+                                trailers: None,
+                                examples: None,
+                                record_delay_ms: None,
+                                pretty_json: None,
+                                sparse_fields: false,
+                                serve_times: None,
+                                exec: None,
                                 side_effects: None,
+                                body_base64: None,
+                                body_file: None,
                             },
                         );
                         responses
                     },
-                    scenarios: None,
-                    stream: None,
+                    ..Default::default()
                 },
             ]),
             graphql: None,
             behavior: None,
+            state_machine: None,
             #[cfg(feature = "iot")]
             twin: None,
         }
@@ -2740,16 +4608,10 @@ mod tests {
             .as_mut()
             .unwrap()
             .push(EndpointDefinition {
-                kind: EndpointKind::Http,
                 method: "GET".to_string(),
                 path: "/users".to_string(), // Duplicate path with same method
                 description: Some("Duplicate endpoint".to_string()),
-                header_match: None,
-                parameters: None,
-                request_body: None,
-                responses: HashMap::new(),
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             });
 
         let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
@@ -2759,31 +4621,202 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_active_scenario_survives_reload() {
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, _) = broadcast::channel(100);
+        let service = ServiceInstance::new(
+            create_test_service_definition(),
+            8008,
+            storage.clone(),
+            tx.clone(),
+        )
+        .unwrap();
+        service.set_scenario(Some("outage".to_string())).await;
+        drop(service);
+
+        let reloaded =
+            ServiceInstance::new(create_test_service_definition(), 8008, storage.clone(), tx)
+                .unwrap();
+        assert_eq!(reloaded.get_scenario().await, Some("outage".to_string()));
+
+        reloaded.set_scenario(None).await;
+        assert_eq!(storage.load_scenario("test-service").unwrap(), None);
+    }
+
     #[test]
     fn test_endpoint_path_to_regex_handles_unclosed_brace() {
         let pattern = ServiceInstance::endpoint_path_to_regex_static("/users/{id");
         assert!(pattern.contains("users"));
     }
 
+    #[test]
+    fn test_wildcard_segment_captures_rest_of_path() {
+        use crate::simulator::config::EndpointBuilder;
+        let endpoints = vec![EndpointBuilder::get("/files/{*path}").build()];
+        let routes = RouteTable::compile(&endpoints);
+        let headers = HashMap::new();
+        let matched = ServiceInstance::find_endpoint_with_params_static(
+            &endpoints,
+            &routes,
+            "GET",
+            "/files/a/b/c.txt",
+            &headers,
+            &HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(matched.path_params.get("path").unwrap(), "a/b/c.txt");
+        assert!(ServiceInstance::find_endpoint_with_params_static(
+            &endpoints,
+            &routes,
+            "GET",
+            "/files/",
+            &headers,
+            &HashMap::new(),
+        )
+        .unwrap()
+        .is_none());
+
+        let mut definition = create_test_service_definition();
+        definition
+            .endpoints
+            .as_mut()
+            .unwrap()
+            .push(EndpointBuilder::get("/files/{*path}/meta").build());
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, _) = broadcast::channel(100);
+        let service = ServiceInstance::new(definition, 0, storage, tx).unwrap();
+        let err = service.validate_consistency().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Wildcard segment '{*path}' must be the last segment"));
+    }
+
+    #[test]
+    fn test_query_match_selects_endpoint() {
+        use crate::simulator::config::EndpointBuilder;
+        let mut images = EndpointBuilder::get("/search").build();
+        images.query_match = Some(HashMap::from([("type".to_string(), "image".to_string())]));
+        let mut videos = EndpointBuilder::get("/search").build();
+        videos.query_match = Some(HashMap::from([("type".to_string(), "video".to_string())]));
+        // The unconstrained endpoint comes first but only serves other queries
+        let endpoints = vec![EndpointBuilder::get("/search").build(), images, videos];
+        let routes = RouteTable::compile(&endpoints);
+        let headers = HashMap::new();
+        let find = |query: &[(&str, &str)]| {
+            let query: HashMap<String, String> = query
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            ServiceInstance::find_endpoint_with_params_static(
+                &endpoints, &routes, "GET", "/search", &headers, &query,
+            )
+            .unwrap()
+            .unwrap()
+            .endpoint_index
+        };
+
+        assert_eq!(find(&[("type", "image")]), 1);
+        assert_eq!(find(&[("type", "video"), ("page", "2")]), 2);
+        assert_eq!(find(&[("type", "audio")]), 0);
+        assert_eq!(find(&[]), 0);
+    }
+
+    #[test]
+    fn test_most_specific_route_wins_regardless_of_order() {
+        use crate::simulator::config::EndpointBuilder;
+        let endpoints = vec![
+            EndpointBuilder::get("/users/{*rest}").build(),
+            EndpointBuilder::get("/users/{id}").build(),
+            EndpointBuilder::get("/users/me").build(),
+        ];
+        let routes = RouteTable::compile(&endpoints);
+        let headers = HashMap::new();
+        let find = |path: &str| {
+            ServiceInstance::find_endpoint_with_params_static(
+                &endpoints,
+                &routes,
+                "GET",
+                path,
+                &headers,
+                &HashMap::new(),
+            )
+            .unwrap()
+            .unwrap()
+            .endpoint_index
+        };
+
+        assert_eq!(find("/users/me"), 2);
+        assert_eq!(find("/users/42"), 1);
+        assert_eq!(find("/users/42/posts"), 0);
+    }
+
+    #[test]
+    fn test_precompiled_routes_extract_named_params() {
+        use crate::simulator::config::EndpointBuilder;
+        let endpoints = vec![
+            EndpointBuilder::get("/users/{id}/posts/{post_id}").build(),
+            // `file-name` is not a valid capture name, so this path is matched exactly
+            EndpointBuilder::get("/files/{file-name}").build(),
+        ];
+        let routes = RouteTable::compile(&endpoints);
+        assert!(routes.regex(0).is_some() && routes.regex(1).is_none());
+
+        let headers = HashMap::new();
+        let matched = ServiceInstance::find_endpoint_with_params_static(
+            &endpoints,
+            &routes,
+            "GET",
+            "/users/7/posts/42",
+            &headers,
+            &HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(matched.endpoint_index, 0);
+        assert_eq!(matched.path_params.get("id").unwrap(), "7");
+        assert_eq!(matched.path_params.get("post_id").unwrap(), "42");
+
+        let exact = ServiceInstance::find_endpoint_with_params_static(
+            &endpoints,
+            &routes,
+            "GET",
+            "/files/{file-name}",
+            &headers,
+            &HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(exact.endpoint_index, 1);
+        assert!(ServiceInstance::find_endpoint_with_params_static(
+            &endpoints,
+            &routes,
+            "GET",
+            "/files/report.pdf",
+            &headers,
+            &HashMap::new(),
+        )
+        .unwrap()
+        .is_none());
+    }
+
     #[tokio::test]
     async fn test_scenario_matching() {
         // Build endpoint with various scenarios
         let endpoint = EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/test".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
-            responses: HashMap::new(),
             scenarios: Some(vec![
                 ScenarioDefinition {
                     name: Some("query".to_string()),
                     conditions: Some(ScenarioConditions {
                         query: Some(HashMap::from([("mode".to_string(), "1".to_string())])),
                         headers: None,
+                        cookies: None,
                         body: None,
+                        body_regex: None,
+                        content_type: None,
                     }),
                     response: ScenarioResponse {
                         status: 200,
@@ -2794,17 +4827,31 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                     strategy: None,
+                    weight: None,
+                    sticky: None,
                 },
                 ScenarioDefinition {
                     name: Some("header".to_string()),
                     conditions: Some(ScenarioConditions {
                         query: None,
                         headers: Some(HashMap::from([("x-scn".to_string(), "hdr".to_string())])),
+                        cookies: None,
                         body: None,
+                        body_regex: None,
+                        content_type: None,
                     }),
                     response: ScenarioResponse {
                         status: 201,
@@ -2815,20 +4862,34 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                     strategy: None,
+                    weight: None,
+                    sticky: None,
                 },
                 ScenarioDefinition {
                     name: Some("body".to_string()),
                     conditions: Some(ScenarioConditions {
                         query: None,
                         headers: None,
+                        cookies: None,
                         body: Some(HashMap::from([(
                             "kind".to_string(),
                             serde_json::json!("b"),
                         )])),
+                        body_regex: None,
+                        content_type: None,
                     }),
                     response: ScenarioResponse {
                         status: 202,
@@ -2839,10 +4900,21 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                     strategy: None,
+                    weight: None,
+                    sticky: None,
                 },
                 ScenarioDefinition {
                     name: Some("error".to_string()),
@@ -2856,13 +4928,24 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                     strategy: None,
+                    weight: None,
+                    sticky: None,
                 },
             ]),
-            stream: None,
+            ..Default::default()
         };
 
         let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
@@ -2879,6 +4962,7 @@ mod tests {
             &query,
             &HashMap::new(),
             &None,
+            b"",
         )
         .await;
         assert_eq!(res.unwrap().0, 200);
@@ -2894,6 +4978,7 @@ mod tests {
             &HashMap::new(),
             &headers,
             &None,
+            b"",
         )
         .await;
         assert_eq!(res.unwrap().0, 201);
@@ -2908,6 +4993,7 @@ mod tests {
             &HashMap::new(),
             &HashMap::new(),
             &body,
+            b"",
         )
         .await;
         assert_eq!(res.unwrap().0, 202);
@@ -2921,27 +5007,74 @@ mod tests {
             &HashMap::new(),
             &HashMap::new(),
             &None,
+            b"",
         )
         .await;
         assert_eq!(res.unwrap().0, 500);
     }
 
+    #[tokio::test]
+    async fn test_scenario_matching_body_regex() {
+        let endpoint: EndpointDefinition = serde_yaml::from_str(
+            r#"
+method: POST
+path: /soap
+responses: {}
+scenarios:
+  - name: refund
+    conditions:
+      body_regex: '<(\w+:)?RefundOrder>'
+    response:
+      status: 200
+      content_type: text/xml
+      body: <Refunded/>
+  - name: other
+    response:
+      status: 500
+      content_type: text/xml
+      body: <Fault/>
+"#,
+        )
+        .unwrap();
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let state = Arc::new(RwLock::new(ServiceState::new(None, None, storage, None)));
+        let xml = br#"<soap:Envelope><soap:Body><m:RefundOrder><id>7</id></m:RefundOrder></soap:Body></soap:Envelope>"#;
+
+        let matched = |raw: &'static [u8]| {
+            let (endpoint, state) = (&endpoint, &state);
+            async move {
+                ServiceInstance::match_scenario(
+                    endpoint,
+                    state,
+                    0,
+                    Some("other".to_string()),
+                    &HashMap::new(),
+                    &HashMap::new(),
+                    &None,
+                    raw,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let (status, response) = matched(xml).await;
+        assert_eq!(status, 200);
+        assert_eq!(response.body, "<Refunded/>");
+        assert_eq!(matched(b"<m:CancelOrder/>").await.0, 500);
+    }
+
     #[tokio::test]
     async fn test_scenario_rotation_sequential() {
         let endpoint = EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/rotate".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
-            responses: HashMap::new(),
             scenarios: Some(vec![
                 ScenarioDefinition {
                     name: None,
                     conditions: None,
                     strategy: Some(ScenarioStrategy::Sequential),
+                    weight: None,
+                    sticky: None,
                     response: ScenarioResponse {
                         status: 200,
                         definition: ResponseDefinition {
@@ -2951,7 +5084,16 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                 },
@@ -2959,6 +5101,8 @@ mod tests {
                     name: None,
                     conditions: None,
                     strategy: Some(ScenarioStrategy::Sequential),
+                    weight: None,
+                    sticky: None,
                     response: ScenarioResponse {
                         status: 201,
                         definition: ResponseDefinition {
@@ -2968,7 +5112,16 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                 },
@@ -2976,6 +5129,8 @@ mod tests {
                     name: None,
                     conditions: None,
                     strategy: Some(ScenarioStrategy::Sequential),
+                    weight: None,
+                    sticky: None,
                     response: ScenarioResponse {
                         status: 202,
                         definition: ResponseDefinition {
@@ -2985,12 +5140,21 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                 },
             ]),
-            stream: None,
+            ..Default::default()
         };
         let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
         let state = Arc::new(RwLock::new(ServiceState::new(None, None, storage, None)));
@@ -3004,6 +5168,7 @@ mod tests {
                 &HashMap::new(),
                 &HashMap::new(),
                 &None,
+                b"",
             )
             .await
             .unwrap()
@@ -3018,19 +5183,15 @@ mod tests {
         use std::collections::HashSet;
 
         let endpoint = EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/random".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
-            responses: HashMap::new(),
             scenarios: Some(vec![
                 ScenarioDefinition {
                     name: None,
                     conditions: None,
                     strategy: Some(ScenarioStrategy::Random),
+                    weight: None,
+                    sticky: None,
                     response: ScenarioResponse {
                         status: 200,
                         definition: ResponseDefinition {
@@ -3040,7 +5201,16 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                 },
@@ -3048,6 +5218,8 @@ mod tests {
                     name: None,
                     conditions: None,
                     strategy: Some(ScenarioStrategy::Random),
+                    weight: None,
+                    sticky: None,
                     response: ScenarioResponse {
                         status: 500,
                         definition: ResponseDefinition {
@@ -3057,12 +5229,21 @@ mod tests {
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     },
                 },
             ]),
-            stream: None,
+            ..Default::default()
         };
 
         let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
@@ -3077,6 +5258,7 @@ mod tests {
                 &HashMap::new(),
                 &HashMap::new(),
                 &None,
+                b"",
             )
             .await
             .unwrap()
@@ -3086,6 +5268,88 @@ mod tests {
         assert_eq!(statuses.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_weighted_scenarios_are_sticky_per_client() {
+        let endpoint: EndpointDefinition = serde_yaml::from_str(
+            r#"
+method: GET
+path: /ab
+responses: {}
+scenarios:
+  - strategy: weighted
+    weight: 20
+    sticky:
+      cookie: client_id
+    response:
+      status: 200
+      content_type: text/plain
+      body: A
+  - strategy: weighted
+    weight: 80
+    response:
+      status: 201
+      content_type: text/plain
+      body: B
+"#,
+        )
+        .unwrap();
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let state = Arc::new(RwLock::new(ServiceState::new(None, None, storage, None)));
+
+        let mut variant_a = 0;
+        for client in 0..1000 {
+            let mut headers = HashMap::new();
+            headers.insert(
+                "cookie".to_string(),
+                format!("theme=dark; client_id=c{}", client),
+            );
+            let mut seen = std::collections::HashSet::new();
+            for _ in 0..5 {
+                let (status, _) = ServiceInstance::match_scenario(
+                    &endpoint,
+                    &state,
+                    0,
+                    None,
+                    &HashMap::new(),
+                    &headers,
+                    &None,
+                    b"",
+                )
+                .await
+                .unwrap();
+                seen.insert(status);
+            }
+            assert_eq!(seen.len(), 1, "client c{} switched variants", client);
+            if seen.contains(&200) {
+                variant_a += 1;
+            }
+        }
+        assert!(
+            (120..=280).contains(&variant_a),
+            "variant A served {}",
+            variant_a
+        );
+    }
+
+    #[tokio::test]
+    async fn internal_error_response_is_valid_json() {
+        let err = ApicentricError::runtime_error(
+            r#"Template "body" failed: unexpected "}}" token"#,
+            None::<String>,
+        );
+        let response = ServiceInstance::internal_error_response("svc \"quoted\"", &err);
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.headers()["content-type"], "application/json");
+
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["service"], "svc \"quoted\"");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains(r#"Template "body" failed"#));
+    }
+
     fn spawn_upstream_server(port: u16) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
             let listener = TcpListener::bind(("127.0.0.1", port)).await.unwrap();
@@ -3164,7 +5428,209 @@ mod tests {
         assert_eq!(body, "header=abc;body=hello");
 
         service.stop().await.unwrap();
-        upstream_handle.await.unwrap();
+        // The shared proxy client keeps the upstream connection alive
+        upstream_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_fallback_proxy_until_fixture_is_populated() {
+        let upstream_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            drop(listener);
+            port
+        };
+        let upstream_handle = spawn_upstream_server(upstream_port);
+
+        let definition: ServiceDefinition = serde_yaml::from_str(&format!(
+            r#"
+name: hybrid
+server:
+  base_path: /api
+  proxy_base_url: http://127.0.0.1:{}
+endpoints:
+  - method: GET
+    path: /users
+    fallback: proxy
+    responses:
+      200:
+        content_type: application/json
+        body: '{{{{json fixtures.users}}}}'
+"#,
+            upstream_port
+        ))
+        .unwrap();
+
+        let service_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            drop(listener);
+            port
+        };
+
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, _) = broadcast::channel(100);
+        let mut service = ServiceInstance::new(definition, service_port, storage, tx).unwrap();
+        service.start().await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+
+        let client = reqwest::Client::new();
+        let url = format!("http://127.0.0.1:{}/api/users", service_port);
+        let resp = client
+            .get(&url)
+            .header("x-test-header", "upstream")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "header=upstream;body=");
+
+        service
+            .update_fixture("users", serde_json::json!([{"id": 1}]))
+            .await;
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status(), ReqStatusCode::OK);
+        assert_eq!(resp.text().await.unwrap(), r#"[{"id":1}]"#);
+
+        service.stop().await.unwrap();
+        upstream_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_proxy_passes_binary_bodies_through_untouched() {
+        let upstream_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            drop(listener);
+            port
+        };
+        let upstream_handle = tokio::spawn(async move {
+            let listener = TcpListener::bind(("127.0.0.1", upstream_port))
+                .await
+                .unwrap();
+            if let Ok((stream, _)) = listener.accept().await {
+                let io = TokioIo::new(stream);
+                let service = service_fn(|req: HyperRequest<hyper::body::Incoming>| async move {
+                    let bytes = BodyExt::collect(req.into_body()).await.unwrap().to_bytes();
+                    Ok::<_, Infallible>(
+                        HyperResponse::builder()
+                            .status(HyperStatusCode::OK)
+                            .header("content-type", "application/octet-stream")
+                            .body(Full::new(bytes))
+                            .unwrap(),
+                    )
+                });
+                let _ = http1::Builder::new().serve_connection(io, service).await;
+            }
+        });
+
+        let mut definition = create_test_service_definition();
+        definition.server.as_mut().unwrap().proxy_base_url =
+            Some(format!("http://127.0.0.1:{}", upstream_port));
+
+        let service_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            drop(listener);
+            port
+        };
+
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, mut rx) = broadcast::channel(100);
+        let mut service = ServiceInstance::new(definition, service_port, storage, tx).unwrap();
+        service.start().await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+
+        let payload: Vec<u8> = vec![0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe, 0x80];
+        let url = format!("http://127.0.0.1:{}/api/v1/upload", service_port);
+        let resp = reqwest::Client::new()
+            .post(&url)
+            .header("content-type", "application/octet-stream")
+            .body(payload.clone())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), ReqStatusCode::OK);
+        assert_eq!(resp.bytes().await.unwrap().as_ref(), payload.as_slice());
+
+        let mut entries = Vec::new();
+        while let Ok(entry) = rx.try_recv() {
+            entries.push(entry);
+        }
+        let proxied = entries
+            .iter()
+            .find(|e| e.method == "POST")
+            .expect("proxied request is logged");
+        assert_eq!(proxied.payload.as_deref(), Some("<binary 8 bytes>"));
+        assert!(entries
+            .iter()
+            .any(|e| e.path == "Request body: <binary 8 bytes>"));
+
+        service.stop().await.unwrap();
+        // The shared proxy client keeps the upstream connection alive
+        upstream_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_proxy_response_transform_unwraps_json() {
+        let free_port = || {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let upstream: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: upstream
+server:
+  base_path: /
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '{"data": {"items": [{"id": 1}, {"id": 2}]}, "meta": {"total": 2}}'
+"#,
+        )
+        .unwrap();
+        let upstream_port = free_port();
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+        let (tx, _) = broadcast::channel(100);
+        let mut upstream =
+            ServiceInstance::new(upstream, upstream_port, storage.clone(), tx.clone()).unwrap();
+        upstream.start().await.unwrap();
+
+        let definition: ServiceDefinition = serde_yaml::from_str(&format!(
+            r#"
+name: gateway
+server:
+  base_path: /api
+  proxy_base_url: http://127.0.0.1:{}
+  proxy_response_transform: data.items
+endpoints:
+  - method: GET
+    path: /health
+    responses:
+      200:
+        content_type: application/json
+        body: '{{}}'
+"#,
+            upstream_port
+        ))
+        .unwrap();
+        let service_port = free_port();
+        let mut service = ServiceInstance::new(definition, service_port, storage, tx).unwrap();
+        service.start().await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+
+        let resp = reqwest::get(format!("http://127.0.0.1:{}/api/users", service_port))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), ReqStatusCode::OK);
+        let body: Value = resp.json().await.unwrap();
+        assert_eq!(body, serde_json::json!([{"id": 1}, {"id": 2}]));
+
+        service.stop().await.unwrap();
+        upstream.stop().await.unwrap();
     }
 
     #[tokio::test]
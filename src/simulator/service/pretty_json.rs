@@ -0,0 +1,91 @@
+/// Whether a content type carries JSON (`application/json` or a `+json` suffix).
+pub fn is_json_content_type(content_type: &str) -> bool {
+    let media = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    media == "application/json" || media.ends_with("+json")
+}
+
+/// Re-indent a JSON body with two spaces, keeping the authored key order.
+///
+/// Returns `None` for non-JSON content types and bodies that are not valid JSON.
+pub fn pretty_print(content_type: &str, body: &str) -> Option<String> {
+    if !is_json_content_type(content_type)
+        || serde_json::from_str::<serde::de::IgnoredAny>(body).is_err()
+    {
+        return None;
+    }
+
+    let mut out = String::with_capacity(body.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = body.chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                if matches!(chars.peek(), Some('}' | ']')) {
+                    out.extend(chars.next());
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_json_and_keeps_key_order() {
+        let body = r#"{"name":"Ada, \"the\" first","id":1,"tags":[],"roles":["admin",{"x":{}}]}"#;
+        assert_eq!(
+            pretty_print("application/json; charset=utf-8", body).unwrap(),
+            "{\n  \"name\": \"Ada, \\\"the\\\" first\",\n  \"id\": 1,\n  \"tags\": [],\n  \"roles\": [\n    \"admin\",\n    {\n      \"x\": {}\n    }\n  ]\n}"
+        );
+        assert_eq!(pretty_print("text/plain", body), None);
+        assert_eq!(pretty_print("application/json", "{not json"), None);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::ServiceDefinition;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A compiled `proxy_response_transform` expression
+pub type Transform = jmespath::Expression<'static>;
+
+/// Every `proxy_response_transform` of a service, compiled when the service
+/// is created and keyed by the expression text
+pub type ProxyTransforms = HashMap<String, Transform>;
+
+/// Compile the service-wide and per-endpoint transforms
+pub fn compile_all(definition: &ServiceDefinition) -> ApicentricResult<ProxyTransforms> {
+    let expressions = definition
+        .server
+        .iter()
+        .filter_map(|server| server.proxy_response_transform.as_ref())
+        .chain(
+            definition
+                .endpoints
+                .iter()
+                .flatten()
+                .filter_map(|endpoint| endpoint.proxy_response_transform.as_ref()),
+        );
+
+    let mut transforms = ProxyTransforms::new();
+    for expression in expressions {
+        if !transforms.contains_key(expression) {
+            let compiled = jmespath::compile(expression).map_err(|e| {
+                ApicentricError::config_error(
+                    format!("Invalid proxy_response_transform '{}': {}", expression, e),
+                    Some("Use a JMESPath expression such as 'data.items'"),
+                )
+            })?;
+            transforms.insert(expression.clone(), compiled);
+        }
+    }
+    Ok(transforms)
+}
+
+/// Apply a transform to a JSON document; `None` when the search fails
+pub fn apply(transform: &Transform, document: &Value) -> Option<Value> {
+    match transform.search(document) {
+        Ok(result) => serde_json::to_value(&*result).ok(),
+        Err(e) => {
+            log::warn!("proxy_response_transform '{}' failed: {}", transform, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn compiles_once_and_applies_jmespath() {
+        let definition: ServiceDefinition = serde_yaml::from_str(
+            "name: upstream\nserver:\n  base_path: /\n  proxy_response_transform: data.items\nendpoints:\n  - method: GET\n    path: /names\n    proxy_response_transform: 'data.items[?id > `1`].profile.name'\n    responses: {}\n",
+        )
+        .unwrap();
+        let transforms = compile_all(&definition).unwrap();
+        assert_eq!(transforms.len(), 2);
+
+        let doc = json!({
+            "data": {
+                "items": [
+                    { "id": 1, "profile": { "name": "Ana" } },
+                    { "id": 2, "profile": { "name": "Bo" } }
+                ]
+            }
+        });
+        let names = &transforms["data.items[?id > `1`].profile.name"];
+        assert_eq!(apply(names, &doc), Some(json!(["Bo"])));
+    }
+
+    #[test]
+    fn invalid_expressions_fail_when_compiled() {
+        let definition: ServiceDefinition = serde_yaml::from_str(
+            "name: upstream\nserver:\n  base_path: /\n  proxy_response_transform: 'data.['\nendpoints: []\n",
+        )
+        .unwrap();
+        let err = compile_all(&definition).unwrap_err();
+        assert!(err.to_string().contains("Invalid proxy_response_transform"));
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Sliding one-minute window of accepted request times
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    accepted: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    /// Count a request against `requests_per_minute`.
+    ///
+    /// Returns how long until the oldest request in the window expires when
+    /// the limit is already reached; the rejected request is not counted.
+    pub fn check(&mut self, requests_per_minute: u32, now: Instant) -> Result<(), Duration> {
+        while self
+            .accepted
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= WINDOW)
+        {
+            self.accepted.pop_front();
+        }
+        if self.accepted.len() >= requests_per_minute as usize {
+            let oldest = self.accepted.front().copied().unwrap_or(now);
+            return Err(WINDOW.saturating_sub(now.duration_since(oldest)));
+        }
+        self.accepted.push_back(now);
+        Ok(())
+    }
+}
+
+/// `Retry-After` value in whole seconds, rounded up and at least one
+pub fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_millis().div_ceil(1000) as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_once_window_is_full_until_oldest_expires() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+        assert!(limiter.check(2, start).is_ok());
+        assert!(limiter.check(2, start + Duration::from_secs(10)).is_ok());
+
+        let wait = limiter
+            .check(2, start + Duration::from_secs(30))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(30));
+        assert_eq!(retry_after_secs(wait), 30);
+        assert_eq!(retry_after_secs(Duration::from_millis(1)), 1);
+
+        assert!(limiter.check(2, start + Duration::from_secs(60)).is_ok());
+        assert!(limiter.check(2, start + Duration::from_secs(61)).is_err());
+    }
+}
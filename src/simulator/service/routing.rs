@@ -1,4 +1,6 @@
+use super::ServiceInstance;
 use crate::simulator::config::EndpointDefinition;
+use regex::Regex;
 use std::collections::HashMap;
 
 /// Extracted path parameters from a request
@@ -37,6 +39,59 @@ impl PathParameters {
     }
 }
 
+/// Endpoint path regexes compiled once per definition, by endpoint index,
+/// and the order endpoints are tried in
+#[derive(Debug, Default)]
+pub(crate) struct RouteTable {
+    /// `None` for paths that fail to compile; those are matched exactly instead
+    regexes: Vec<Option<Regex>>,
+    /// Endpoint indices, most specific first
+    order: Vec<usize>,
+}
+
+impl RouteTable {
+    pub(crate) fn compile(endpoints: &[EndpointDefinition]) -> Self {
+        let regexes = endpoints
+            .iter()
+            .map(|endpoint| {
+                Regex::new(&ServiceInstance::endpoint_path_to_regex_static(
+                    &endpoint.path,
+                ))
+                .ok()
+            })
+            .collect();
+        // Endpoints constrained by query parameters are more specific, so they
+        // are tried first; then literal paths beat parameters and parameters
+        // beat wildcards, with declaration order breaking ties
+        let mut order: Vec<usize> = (0..endpoints.len()).collect();
+        order.sort_by_cached_key(|&index| {
+            let endpoint = &endpoints[index];
+            let has_query = endpoint.query_match.as_ref().is_some_and(|q| !q.is_empty());
+            std::cmp::Reverse((has_query, ServiceInstance::path_specificity(&endpoint.path)))
+        });
+        Self { regexes, order }
+    }
+
+    /// Endpoint indices in the order requests are matched against them
+    pub(crate) fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Compiled path regex of the endpoint at `index`
+    pub(crate) fn regex(&self, index: usize) -> Option<&Regex> {
+        self.regexes.get(index)?.as_ref()
+    }
+
+    /// Indices of the endpoints whose compiled path matches `path`
+    pub(crate) fn matching<'a>(&'a self, path: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.regexes
+            .iter()
+            .enumerate()
+            .filter(move |(_, regex)| regex.as_ref().is_some_and(|re| re.is_match(path)))
+            .map(|(index, _)| index)
+    }
+}
+
 /// Route matching result with extracted parameters
 #[derive(Debug, Clone)]
 pub struct RouteMatch {
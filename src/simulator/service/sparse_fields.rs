@@ -0,0 +1,113 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Fieldsets requested with JSON:API-style `fields[<type>]=a,b` query parameters.
+pub fn requested_fields(query: &str) -> HashMap<String, Vec<String>> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .filter_map(|(key, value)| {
+            let resource_type = key.strip_prefix("fields[")?.strip_suffix(']')?;
+            let fields = value
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(String::from)
+                .collect();
+            Some((resource_type.to_string(), fields))
+        })
+        .collect()
+}
+
+/// Prune a rendered JSON body down to the requested fieldsets.
+///
+/// JSON:API documents keep `id` and `type` on every resource in `data` and
+/// `included` and filter its `attributes` and `relationships`. Plain objects,
+/// or arrays of them, keep only the listed keys. A resource uses the fieldset
+/// named by its `type` member, or the only one requested when it has none.
+///
+/// Returns `None` when no fieldset was requested or the body is not JSON.
+pub fn prune(body: &str, fields: &HashMap<String, Vec<String>>) -> Option<String> {
+    if fields.is_empty() {
+        return None;
+    }
+    let mut value: Value = serde_json::from_str(body).ok()?;
+    match value.as_object_mut() {
+        Some(document) if document.contains_key("data") => {
+            for member in ["data", "included"] {
+                if let Some(resources) = document.get_mut(member) {
+                    for_each_object(resources, |resource| prune_resource(resource, fields));
+                }
+            }
+        }
+        _ => for_each_object(&mut value, |resource| prune_resource(resource, fields)),
+    }
+    serde_json::to_string(&value).ok()
+}
+
+fn for_each_object(value: &mut Value, mut f: impl FnMut(&mut Map<String, Value>)) {
+    match value {
+        Value::Object(object) => f(object),
+        Value::Array(items) => items
+            .iter_mut()
+            .filter_map(Value::as_object_mut)
+            .for_each(f),
+        _ => {}
+    }
+}
+
+fn prune_resource(resource: &mut Map<String, Value>, fields: &HashMap<String, Vec<String>>) {
+    let keep = match resource.get("type").and_then(Value::as_str) {
+        Some(resource_type) => fields.get(resource_type),
+        None if fields.len() == 1 => fields.values().next(),
+        None => None,
+    };
+    let Some(keep) = keep else {
+        return;
+    };
+    let wanted = |key: &String| keep.iter().any(|k| k == key);
+
+    if resource.contains_key("attributes") || resource.contains_key("relationships") {
+        for member in ["attributes", "relationships"] {
+            if let Some(Value::Object(values)) = resource.get_mut(member) {
+                values.retain(|key, _| wanted(key));
+            }
+        }
+    } else {
+        resource.retain(|key, _| wanted(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn prunes_json_api_resources_by_type() {
+        let fields = requested_fields("fields%5Barticles%5D=title&fields[people]=name");
+        let body = json!({
+            "data": [{
+                "type": "articles",
+                "id": "1",
+                "attributes": { "title": "Hi", "body": "..." },
+                "relationships": { "author": { "data": { "type": "people", "id": "9" } } }
+            }],
+            "included": [{ "type": "people", "id": "9", "attributes": { "name": "Ana", "age": 30 } }]
+        });
+
+        let pruned: Value =
+            serde_json::from_str(&prune(&body.to_string(), &fields).unwrap()).unwrap();
+        assert_eq!(
+            pruned,
+            json!({
+                "data": [{ "type": "articles", "id": "1", "attributes": { "title": "Hi" }, "relationships": {} }],
+                "included": [{ "type": "people", "id": "9", "attributes": { "name": "Ana" } }]
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_bodies_alone_without_fieldsets() {
+        assert!(requested_fields("page=2&fields=id").is_empty());
+        assert_eq!(prune(r#"{"id": 1}"#, &HashMap::new()), None);
+    }
+}
@@ -1,10 +1,16 @@
 use crate::errors::{ApicentricError, ApicentricResult};
-use crate::simulator::config::ScenarioStrategy;
-use crate::simulator::log::{RequestLog, RequestLogEntry};
+use crate::simulator::config::{ScenarioStrategy, StateTransition};
+use crate::simulator::log::{LogRedactor, RequestLog, RequestLogEntry};
+use crate::simulator::service::rate_limit::RateLimiter;
 use crate::storage::Storage;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::{Duration, Instant};
+
+/// Most tenant partitions kept at once; the least recently used is dropped
+/// to make room for a new tenant
+pub const MAX_TENANT_PARTITIONS: usize = 1024;
 
 /// Shared in-memory data bucket for stateful routes
 #[derive(Debug, Clone)]
@@ -43,11 +49,26 @@ impl DataBucket {
 pub struct ServiceState {
     pub(crate) fixtures: HashMap<String, Value>,
     pub(crate) runtime_data: HashMap<String, Value>,
-    pub(crate) initial_fixtures: HashMap<String, Value>, // Backup of original fixtures for reset
+    pub(crate) initial_fixtures: Arc<HashMap<String, Value>>, // Backup of original fixtures for reset
     request_log: RequestLog,
     bucket: DataBucket,
     response_counters: HashMap<usize, usize>,
+    sticky_buckets: HashMap<(usize, String), usize>,
+    /// Times each `serve_times`-limited response was served, by endpoint and status
+    serve_counts: HashMap<(usize, u16), usize>,
     log_sender: Option<tokio::sync::broadcast::Sender<RequestLogEntry>>,
+    redactor: LogRedactor,
+    /// Isolated fixtures and runtime data per tenant, keyed by partition header value
+    tenants: HashMap<String, ServiceState>,
+    /// Tenant names from least to most recently used
+    tenant_order: VecDeque<String>,
+    /// Requests accepted in the current window, shared by every connection
+    rate_limiter: RateLimiter,
+    /// Windows of endpoints that set their own rate limit
+    endpoint_rate_limiters: HashMap<usize, RateLimiter>,
+    /// Current state machine state and the state a reset returns to
+    machine_state: Option<String>,
+    initial_machine_state: Option<String>,
 }
 
 impl ServiceState {
@@ -66,14 +87,113 @@ impl ServiceState {
     ) -> Self {
         let fixtures = fixtures.unwrap_or_default();
         Self {
-            initial_fixtures: fixtures.clone(),
+            initial_fixtures: Arc::new(fixtures.clone()),
             fixtures,
             runtime_data: HashMap::new(),
             request_log: RequestLog::new(storage),
             bucket: DataBucket::new(bucket),
             response_counters: HashMap::new(),
+            sticky_buckets: HashMap::new(),
+            serve_counts: HashMap::new(),
             log_sender,
+            redactor: LogRedactor::default(),
+            tenants: HashMap::new(),
+            tenant_order: VecDeque::new(),
+            rate_limiter: RateLimiter::default(),
+            endpoint_rate_limiters: HashMap::new(),
+            machine_state: None,
+            initial_machine_state: None,
+        }
+    }
+
+    /// State for a tenant, created from the initial fixtures on first use.
+    /// `None` is the shared partition. Past [`MAX_TENANT_PARTITIONS`] the
+    /// least recently used tenant is dropped and starts over on its next request.
+    pub fn partition_mut(&mut self, tenant: Option<&str>) -> &mut ServiceState {
+        let Some(tenant) = tenant else {
+            return self;
+        };
+        if let Some(position) = self.tenant_order.iter().position(|name| name == tenant) {
+            let name = self.tenant_order.remove(position).unwrap();
+            self.tenant_order.push_back(name);
+        } else {
+            if self.tenants.len() >= MAX_TENANT_PARTITIONS {
+                if let Some(evicted) = self.tenant_order.pop_front() {
+                    self.tenants.remove(&evicted);
+                }
+            }
+            let fresh = Self {
+                fixtures: (*self.initial_fixtures).clone(),
+                runtime_data: HashMap::new(),
+                initial_fixtures: Arc::clone(&self.initial_fixtures),
+                request_log: self.request_log.clone(),
+                bucket: self.bucket.clone(),
+                response_counters: HashMap::new(),
+                sticky_buckets: HashMap::new(),
+                serve_counts: HashMap::new(),
+                log_sender: self.log_sender.clone(),
+                redactor: self.redactor.clone(),
+                tenants: HashMap::new(),
+                tenant_order: VecDeque::new(),
+                rate_limiter: RateLimiter::default(),
+                endpoint_rate_limiters: HashMap::new(),
+                machine_state: self.initial_machine_state.clone(),
+                initial_machine_state: self.initial_machine_state.clone(),
+            };
+            self.tenants.insert(tenant.to_string(), fresh);
+            self.tenant_order.push_back(tenant.to_string());
         }
+        self.tenants.get_mut(tenant).unwrap()
+    }
+
+    /// State a tenant reads; tenants not yet created by [`Self::partition_mut`]
+    /// fall back to the shared partition
+    pub fn partition(&self, tenant: Option<&str>) -> &ServiceState {
+        tenant
+            .and_then(|tenant| self.tenants.get(tenant))
+            .unwrap_or(self)
+    }
+
+    /// Count a request against the service-wide limit, or the window of
+    /// `endpoint` when it has a limit of its own, returning how long to wait
+    /// when `requests_per_minute` has been reached
+    pub fn check_rate_limit(
+        &mut self,
+        endpoint: Option<usize>,
+        requests_per_minute: u32,
+    ) -> Result<(), Duration> {
+        let limiter = match endpoint {
+            Some(index) => self.endpoint_rate_limiters.entry(index).or_default(),
+            None => &mut self.rate_limiter,
+        };
+        limiter.check(requests_per_minute, Instant::now())
+    }
+
+    /// Start the state machine in `initial`, or disable it with `None`
+    pub fn set_state_machine(&mut self, initial: Option<String>) {
+        self.machine_state = initial.clone();
+        self.initial_machine_state = initial;
+    }
+
+    /// Current state machine state
+    pub fn machine_state(&self) -> Option<&str> {
+        self.machine_state.as_deref()
+    }
+
+    /// Move the state machine along `transition`, or return the current state
+    /// when the transition may not start from it
+    pub fn apply_transition(&mut self, transition: &StateTransition) -> Result<(), String> {
+        let current = self.machine_state.clone().unwrap_or_default();
+        if !transition.from.is_empty() && !transition.from.contains(&current) {
+            return Err(current);
+        }
+        self.machine_state = Some(transition.to.clone());
+        Ok(())
+    }
+
+    /// Set the redactor applied to log entries before they are recorded
+    pub fn set_log_redactor(&mut self, redactor: LogRedactor) {
+        self.redactor = redactor;
     }
 
     /// Get the next response index based on the scenario strategy.
@@ -98,13 +218,71 @@ impl ServiceState {
                 *counter = (*counter + 1) % total;
                 idx
             }
-            ScenarioStrategy::Random => {
+            // Without weights every candidate gets an equal share
+            ScenarioStrategy::Random | ScenarioStrategy::Weighted => {
                 use rand::Rng;
                 rand::thread_rng().gen_range(0..total)
             }
         }
     }
 
+    /// Count one serve of a response limited to `limit` serves, or return
+    /// `false` once it has been served that many times
+    pub fn take_serve(&mut self, endpoint_index: usize, status: u16, limit: usize) -> bool {
+        let count = self
+            .serve_counts
+            .entry((endpoint_index, status))
+            .or_insert(0);
+        if *count >= limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// Pick a response index in proportion to `weights`.
+    ///
+    /// When a `client` id is given the first pick is remembered per endpoint so
+    /// the same client keeps receiving the same variant.
+    pub fn weighted_response_index(
+        &mut self,
+        endpoint_index: usize,
+        weights: &[u32],
+        client: Option<String>,
+    ) -> usize {
+        if let Some(idx) = client
+            .as_ref()
+            .and_then(|id| self.sticky_buckets.get(&(endpoint_index, id.clone())))
+        {
+            return *idx;
+        }
+
+        let total: u64 = weights.iter().map(|w| u64::from(*w)).sum();
+        let idx = if total == 0 {
+            0
+        } else {
+            use rand::Rng;
+            let mut roll = rand::thread_rng().gen_range(0..total);
+            weights
+                .iter()
+                .position(|w| {
+                    let w = u64::from(*w);
+                    if roll < w {
+                        true
+                    } else {
+                        roll -= w;
+                        false
+                    }
+                })
+                .unwrap_or(0)
+        };
+
+        if let Some(id) = client {
+            self.sticky_buckets.insert((endpoint_index, id), idx);
+        }
+        idx
+    }
+
     /// Get a clone of the data bucket.
     ///
     /// # Returns
@@ -280,9 +458,13 @@ impl ServiceState {
         }
     }
 
-    /// Reset fixtures to their initial state
+    /// Reset fixtures and the state machine to their initial state and drop
+    /// all tenant partitions
     pub fn reset_fixtures(&mut self) {
-        self.fixtures = self.initial_fixtures.clone();
+        self.fixtures = (*self.initial_fixtures).clone();
+        self.machine_state = self.initial_machine_state.clone();
+        self.tenants.clear();
+        self.tenant_order.clear();
     }
 
     /// Get runtime data by key
@@ -337,6 +519,7 @@ impl ServiceState {
 
     /// Append a request log entry
     pub fn add_log_entry(&mut self, entry: RequestLogEntry) {
+        let entry = self.redactor.redact(entry);
         self.request_log.add(entry.clone());
         if let Some(sender) = &self.log_sender {
             let _ = sender.send(entry);
@@ -361,3 +544,32 @@ impl ServiceState {
             .query(service, route, method, status, limit)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SqliteStorage;
+
+    #[test]
+    fn least_recently_used_tenant_is_evicted_past_the_cap() {
+        let storage = Arc::new(SqliteStorage::init_db(":memory:").unwrap());
+        let mut state = ServiceState::new(None, None, storage, None);
+
+        state
+            .partition_mut(Some("kept"))
+            .set_runtime_data("marker".to_string(), Value::Bool(true));
+        for i in 1..MAX_TENANT_PARTITIONS {
+            state.partition_mut(Some(&format!("tenant-{}", i)));
+        }
+        // Touching "kept" makes "tenant-1" the least recently used
+        state.partition_mut(Some("kept"));
+        state.partition_mut(Some("newcomer"));
+
+        assert_eq!(state.tenants.len(), MAX_TENANT_PARTITIONS);
+        assert!(!state.tenants.contains_key("tenant-1"));
+        assert!(state
+            .partition(Some("kept"))
+            .get_runtime_data("marker")
+            .is_some());
+    }
+}
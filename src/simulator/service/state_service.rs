@@ -58,6 +58,14 @@ mod tests {
             Ok(None)
         }
 
+        fn save_scenario(&self, _service: &str, _scenario: Option<&str>) -> ApicentricResult<()> {
+            Ok(())
+        }
+
+        fn load_scenario(&self, _service: &str) -> ApicentricResult<Option<String>> {
+            Ok(None)
+        }
+
         fn append_log(&self, _entry: &RequestLogEntry) -> ApicentricResult<()> {
             Ok(())
         }
@@ -0,0 +1,196 @@
+use super::{PathParameters, ServiceBody, ServiceState};
+use crate::simulator::config::PeriodicMessage;
+use crate::simulator::template::{RequestContext, TemplateContext, TemplateEngine};
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use serde_json::Value;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Boundary used for `multipart/mixed` responses that do not declare one
+const DEFAULT_BOUNDARY: &str = "apicentric-record";
+
+/// Record framing applied when a JSON array is streamed element by element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordFraming {
+    /// Newline-delimited JSON (`application/x-ndjson`)
+    Ndjson,
+    /// RFC 7464 JSON text sequences (`application/json-seq`)
+    JsonSeq,
+    /// One `application/json` part per record (`multipart/mixed`)
+    Multipart { boundary: String },
+}
+
+impl RecordFraming {
+    /// Detect the framing from a response content type
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mut params = content_type.split(';').map(str::trim);
+        let mime = params.next()?.to_ascii_lowercase();
+        match mime.as_str() {
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => {
+                Some(Self::Ndjson)
+            }
+            "application/json-seq" => Some(Self::JsonSeq),
+            "multipart/mixed" => {
+                let boundary = params
+                    .find_map(|p| p.strip_prefix("boundary="))
+                    .map(|b| b.trim_matches('"').to_string())
+                    .unwrap_or_else(|| DEFAULT_BOUNDARY.to_string());
+                Some(Self::Multipart { boundary })
+            }
+            _ => None,
+        }
+    }
+
+    /// Content type to send, adding the boundary when it was left implicit
+    pub fn content_type(&self, declared: &str) -> String {
+        match self {
+            Self::Multipart { boundary } if !declared.contains("boundary=") => {
+                format!("{}; boundary={}", declared, boundary)
+            }
+            _ => declared.to_string(),
+        }
+    }
+
+    /// Split a rendered JSON array body into framed records.
+    ///
+    /// Returns `None` when the body is not a JSON array.
+    pub fn frame(&self, body: &str) -> Option<Vec<Bytes>> {
+        let Ok(Value::Array(items)) = serde_json::from_str::<Value>(body) else {
+            return None;
+        };
+        let mut records: Vec<Bytes> = items
+            .iter()
+            .map(|item| {
+                let json = item.to_string();
+                let framed = match self {
+                    Self::Ndjson => format!("{}\n", json),
+                    Self::JsonSeq => format!("\u{1e}{}\n", json),
+                    Self::Multipart { boundary } => format!(
+                        "--{}\r\nContent-Type: application/json\r\n\r\n{}\r\n",
+                        boundary, json
+                    ),
+                };
+                Bytes::from(framed)
+            })
+            .collect();
+        if let Self::Multipart { boundary } = self {
+            records.push(Bytes::from(format!("--{}--\r\n", boundary)));
+        }
+        Some(records)
+    }
+}
+
+/// Records written one frame at a time with a delay in between, attached to a
+/// response as an extension
+#[derive(Debug, Clone)]
+pub struct RecordStream {
+    pub records: Vec<Bytes>,
+    pub delay: Duration,
+}
+
+/// Frame a message as one Server-Sent Event, sending each line as `data:`
+pub fn sse_frame(message: &str) -> Bytes {
+    let mut frame = String::with_capacity(message.len() + 8);
+    for line in message.lines() {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    if frame.is_empty() {
+        frame.push_str("data: \n");
+    }
+    frame.push('\n');
+    Bytes::from(frame)
+}
+
+/// Server-Sent Events written until the client disconnects, attached to a
+/// response as an extension. Messages are templates rendered against the
+/// service state as it stands when each one is sent.
+#[derive(Clone)]
+pub struct EventStream {
+    pub initial: Vec<String>,
+    pub periodic: Option<PeriodicMessage>,
+    pub template_engine: Arc<TemplateEngine>,
+    pub state: Arc<RwLock<ServiceState>>,
+    pub tenant: Option<String>,
+    pub path_params: PathParameters,
+    pub request: RequestContext,
+}
+
+impl EventStream {
+    /// Render a message template against the current service state
+    pub async fn render(&self, template: &str) -> String {
+        let context = {
+            let state = self.state.read().await;
+            TemplateContext::new(
+                state.partition(self.tenant.as_deref()),
+                &self.path_params,
+                self.request.clone(),
+            )
+        };
+        self.template_engine
+            .render(template, &context)
+            .unwrap_or_else(|e| {
+                log::warn!("Event stream template rendering error: {}", e);
+                template.to_string()
+            })
+    }
+
+    /// Body sending the initial messages and then one periodic message per
+    /// interval; without a periodic message the connection is held open
+    pub fn into_body(self) -> ServiceBody {
+        let frames = futures_util::stream::unfold((self, 0usize), |(events, sent)| async move {
+            let template = match events.initial.get(sent) {
+                Some(message) => message.clone(),
+                None => {
+                    let Some(periodic) = &events.periodic else {
+                        std::future::pending::<()>().await;
+                        return None;
+                    };
+                    tokio::time::sleep(Duration::from_millis(periodic.interval_ms.max(1))).await;
+                    periodic.message.clone()
+                }
+            };
+            let frame = sse_frame(&events.render(&template).await);
+            Some((frame, (events, sent + 1)))
+        });
+        StreamBody::new(frames.map(|frame| Ok::<_, Infallible>(Frame::data(frame)))).boxed_unsync()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_json_seq_and_multipart() {
+        let seq = RecordFraming::from_content_type("application/json-seq").unwrap();
+        let records = seq.frame(r#"[{"a":1},2]"#).unwrap();
+        assert_eq!(records[0], Bytes::from("\u{1e}{\"a\":1}\n"));
+        assert_eq!(records[1], Bytes::from("\u{1e}2\n"));
+
+        let multipart = RecordFraming::from_content_type("multipart/mixed").unwrap();
+        assert_eq!(
+            multipart.content_type("multipart/mixed"),
+            "multipart/mixed; boundary=apicentric-record"
+        );
+        let records = multipart.frame("[1]").unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1], Bytes::from("--apicentric-record--\r\n"));
+
+        assert!(seq.frame(r#"{"not": "an array"}"#).is_none());
+        assert!(RecordFraming::from_content_type("application/json").is_none());
+    }
+
+    #[test]
+    fn sse_frame_prefixes_every_line() {
+        assert_eq!(sse_frame(r#"{"n":1}"#), Bytes::from("data: {\"n\":1}\n\n"));
+        assert_eq!(sse_frame("a\nb"), Bytes::from("data: a\ndata: b\n\n"));
+        assert_eq!(sse_frame(""), Bytes::from("data: \n\n"));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::TlsConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+/// An accepted connection, plain TCP or TLS
+pub trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Connection for T {}
+
+/// The certificate a client presented during the TLS handshake
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCert {
+    /// Distinguished name, e.g. `O=Acme, CN=billing`
+    pub subject: String,
+    /// Common name from the subject, when it has one
+    pub cn: Option<String>,
+}
+
+/// Build the acceptor for `server.tls`. With a `client_ca`, client
+/// certificates are requested and must chain to it, but connections without
+/// one are still accepted so requests can be answered with a 401.
+pub fn acceptor(cfg: &TlsConfig) -> ApicentricResult<TlsAcceptor> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?;
+
+    let builder = match &cfg.client_ca {
+        Some(ca) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(ca)? {
+                roots.add(cert).map_err(tls_error)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(tls_error)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let key = PrivateKeyDer::from_pem_file(&cfg.key).map_err(|e| {
+        ApicentricError::config_error(
+            format!("Invalid TLS key {}: {}", cfg.key.display(), e),
+            Some("server.tls.key must be a PEM encoded private key"),
+        )
+    })?;
+    let config = builder
+        .with_single_cert(read_certs(&cfg.cert)?, key)
+        .map_err(tls_error)?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// The client certificate of an accepted connection, if one was presented
+pub fn client_cert(stream: &TlsStream<TcpStream>) -> Option<ClientCert> {
+    let der = stream.get_ref().1.peer_certificates()?.first()?;
+    let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref()).ok()?;
+    let cn = cert
+        .subject()
+        .iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(str::to_string);
+    Some(ClientCert {
+        subject: cert.subject().to_string(),
+        cn,
+    })
+}
+
+fn read_certs(path: &Path) -> ApicentricResult<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| {
+            ApicentricError::config_error(
+                format!("Invalid TLS certificate {}: {}", path.display(), e),
+                Some("Certificates must be PEM encoded"),
+            )
+        })?;
+    if certs.is_empty() {
+        return Err(ApicentricError::config_error(
+            format!("No certificates found in {}", path.display()),
+            Some("Certificates must be PEM encoded"),
+        ));
+    }
+    Ok(certs)
+}
+
+fn tls_error(e: impl std::fmt::Display) -> ApicentricError {
+    ApicentricError::config_error(
+        format!("Invalid TLS configuration: {}", e),
+        Some("Check server.tls certificate, key and client_ca"),
+    )
+}
@@ -116,6 +116,7 @@ impl ServiceInstance {
                     "/telemetry",
                     200,
                     payload,
+                    None,
                 )
                 .await;
 
@@ -0,0 +1,185 @@
+use super::{EventStream, ServiceState};
+use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::log::RequestLogEntry;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::header::HeaderMap;
+use hyper::http::Extensions;
+use hyper::{Response, StatusCode};
+use tokio::sync::RwLock;
+
+/// Endpoint a WebSocket connection belongs to, used to log its messages
+#[derive(Debug, Clone)]
+pub struct ConnectionLog {
+    pub service: String,
+    pub endpoint: usize,
+    pub path: String,
+}
+
+impl ConnectionLog {
+    /// Log a client message against the connection's upgrade request
+    pub async fn record(&self, state: &RwLock<ServiceState>, payload: Option<String>) {
+        let entry = RequestLogEntry::new(
+            self.service.clone(),
+            Some(self.endpoint),
+            "WS".to_string(),
+            self.path.clone(),
+            StatusCode::SWITCHING_PROTOCOLS.as_u16(),
+            payload,
+        );
+        state.write().await.add_log_entry(entry);
+    }
+}
+
+#[cfg(feature = "websockets")]
+fn upgrade_required() -> ApicentricResult<Response<Full<Bytes>>> {
+    Response::builder()
+        .status(StatusCode::UPGRADE_REQUIRED)
+        .header("upgrade", "websocket")
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from(
+            r#"{"error": "This endpoint only accepts WebSocket connections"}"#,
+        )))
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to build upgrade required response: {}", e),
+                None::<String>,
+            )
+        })
+}
+
+/// Answer a WebSocket handshake and serve the connection once hyper hands it
+/// over: `initial` messages on connect, the `periodic` message on its interval
+/// and the `on_message` reply to every client message.
+///
+/// Requests that are not WebSocket upgrades get 426 Upgrade Required.
+#[cfg(feature = "websockets")]
+pub fn accept(
+    headers: &HeaderMap,
+    extensions: &mut Extensions,
+    events: EventStream,
+    on_message: Option<String>,
+    log: ConnectionLog,
+) -> ApicentricResult<Response<Full<Bytes>>> {
+    use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+
+    let is_upgrade = headers
+        .get("upgrade")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let key = headers
+        .get("sec-websocket-key")
+        .filter(|_| is_upgrade)
+        .map(|key| derive_accept_key(key.as_bytes()));
+    let (Some(accept_key), Some(on_upgrade)) =
+        (key, extensions.remove::<hyper::upgrade::OnUpgrade>())
+    else {
+        return upgrade_required();
+    };
+
+    tokio::spawn(serve(on_upgrade, events, on_message, log));
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header("connection", "upgrade")
+        .header("upgrade", "websocket")
+        .header("sec-websocket-accept", accept_key)
+        .body(Full::new(Bytes::new()))
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to build WebSocket handshake: {}", e),
+                None::<String>,
+            )
+        })
+}
+
+#[cfg(feature = "websockets")]
+async fn serve(
+    on_upgrade: hyper::upgrade::OnUpgrade,
+    events: EventStream,
+    on_message: Option<String>,
+    log: ConnectionLog,
+) {
+    use futures_util::{SinkExt, StreamExt};
+    use hyper_util::rt::TokioIo;
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::{protocol::Role, Message};
+    use tokio_tungstenite::WebSocketStream;
+
+    let upgraded = match on_upgrade.await {
+        Ok(upgraded) => upgraded,
+        Err(err) => {
+            log::debug!("WebSocket upgrade failed for {}: {}", log.path, err);
+            return;
+        }
+    };
+    let mut ws = WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None).await;
+
+    for template in &events.initial {
+        if ws
+            .send(Message::Text(events.render(template).await))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+
+    // Without a periodic message the ticker exists but is never polled
+    let period = events
+        .periodic
+        .as_ref()
+        .map_or(Duration::from_secs(3600), |p| {
+            Duration::from_millis(p.interval_ms.max(1))
+        });
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick(), if events.periodic.is_some() => {
+                let Some(periodic) = &events.periodic else { continue };
+                let message = events.render(&periodic.message).await;
+                if ws.send(Message::Text(message)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = ws.next() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Binary(bytes))) => String::from_utf8_lossy(&bytes).into_owned(),
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Pings are answered by tungstenite itself
+                    Some(Ok(_)) => continue,
+                };
+                log.record(&events.state, Some(text.clone())).await;
+                let Some(template) = &on_message else { continue };
+                let mut reply = events.clone();
+                reply.request.body = Some(
+                    serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)),
+                );
+                if ws.send(Message::Text(reply.render(template).await)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "websockets"))]
+pub fn accept(
+    _headers: &HeaderMap,
+    _extensions: &mut Extensions,
+    _events: EventStream,
+    _on_message: Option<String>,
+    _log: ConnectionLog,
+) -> ApicentricResult<Response<Full<Bytes>>> {
+    Response::builder()
+        .status(StatusCode::NOT_IMPLEMENTED)
+        .body(Full::new(Bytes::from("WebSockets not enabled")))
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to build WebSocket response: {}", e),
+                None::<String>,
+            )
+        })
+}
@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use serde_json::Value;
 
-use crate::simulator::service::{routing::PathParameters, state::ServiceState};
+use crate::simulator::service::{routing::PathParameters, state::ServiceState, tls::ClientCert};
+use crate::simulator::soap::is_xml_content_type;
 
 /// Template context containing all available data for rendering
 #[derive(Debug, Clone)]
@@ -12,6 +13,8 @@ pub struct TemplateContext {
     pub runtime: HashMap<String, Value>,
     pub env: HashMap<String, String>,
     pub request: RequestContext,
+    /// Current state machine state, exposed as `state.current`
+    pub machine_state: Option<String>,
 }
 
 /// Request context information available in templates
@@ -21,7 +24,13 @@ pub struct RequestContext {
     pub path: String,
     pub query: HashMap<String, String>,
     pub headers: HashMap<String, String>,
+    /// Cookies parsed from the `Cookie` header
+    pub cookies: HashMap<String, String>,
     pub body: Option<Value>,
+    /// Media type the body was parsed as, `None` when there is no parsed body
+    pub content_type: Option<String>,
+    /// Certificate the client presented over mutual TLS
+    pub client_cert: Option<ClientCert>,
 }
 
 impl TemplateContext {
@@ -37,6 +46,38 @@ impl TemplateContext {
             runtime: state.all_runtime_data().clone(),
             env: std::env::vars().collect(),
             request: request_context,
+            machine_state: state.machine_state().map(str::to_string),
+        }
+    }
+
+    /// Create a synthetic context for render-checking an endpoint: the
+    /// service fixtures, a placeholder for every `{param}` in the path and no body
+    pub fn sample(fixtures: HashMap<String, Value>, method: &str, path: &str) -> Self {
+        let params = path
+            .split('/')
+            .filter_map(|seg| seg.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .map(|name| {
+                let name = name.trim_start_matches('*');
+                let name = name.split_once(':').map_or(name, |(name, _)| name);
+                (name.to_string(), "1".to_string())
+            })
+            .collect();
+        Self {
+            fixtures,
+            params,
+            runtime: HashMap::new(),
+            env: std::env::vars().collect(),
+            request: RequestContext {
+                method: method.to_string(),
+                path: path.to_string(),
+                query: HashMap::new(),
+                headers: HashMap::new(),
+                cookies: HashMap::new(),
+                body: None,
+                content_type: None,
+                client_cert: None,
+            },
+            machine_state: None,
         }
     }
 
@@ -52,8 +93,12 @@ impl TemplateContext {
                 path: "/".to_string(),
                 query: HashMap::new(),
                 headers: HashMap::new(),
+                cookies: HashMap::new(),
                 body: None,
+                content_type: None,
+                client_cert: None,
             },
+            machine_state: None,
         }
     }
 }
@@ -67,12 +112,56 @@ impl RequestContext {
         headers: HashMap<String, String>,
         body: Option<Value>,
     ) -> Self {
+        let cookies = headers
+            .get("cookie")
+            .map(|header| parse_cookies(header))
+            .unwrap_or_default();
+        let content_type = body.as_ref().map(|_| {
+            body_content_type(headers.get("content-type").map_or("", String::as_str)).to_string()
+        });
         Self {
             method,
             path,
             query,
             headers,
+            cookies,
             body,
+            content_type,
+            client_cert: None,
         }
     }
+
+    /// Attach the client certificate of the connection the request came on
+    pub fn with_client_cert(mut self, client_cert: Option<ClientCert>) -> Self {
+        self.client_cert = client_cert;
+        self
+    }
+}
+
+/// Media type a request body is parsed as given its `Content-Type` header:
+/// form-encoded, XML, or JSON for anything else
+pub fn body_content_type(header: &str) -> &'static str {
+    if header
+        .to_ascii_lowercase()
+        .contains("application/x-www-form-urlencoded")
+    {
+        "application/x-www-form-urlencoded"
+    } else if is_xml_content_type(header) {
+        "application/xml"
+    } else {
+        "application/json"
+    }
+}
+
+/// Parse a `Cookie` request header into name/value pairs
+pub fn parse_cookies(header: &str) -> HashMap<String, String> {
+    header
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty())
+                .then(|| (name.to_string(), value.trim().trim_matches('"').to_string()))
+        })
+        .collect()
 }
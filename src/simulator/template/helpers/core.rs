@@ -1,4 +1,8 @@
-use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Duration, Utc};
+use handlebars::{
+    Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderErrorReason,
+};
 use serde_json::Value;
 
 pub fn register_core_helpers(handlebars: &mut Handlebars) {
@@ -6,93 +10,127 @@ pub fn register_core_helpers(handlebars: &mut Handlebars) {
     handlebars.register_helper("find_by_field", Box::new(find_by_field_helper));
     handlebars.register_helper("find_by_multi_field", Box::new(find_by_multi_field_helper));
     handlebars.register_helper("filter", Box::new(filter_helper));
+    handlebars.register_helper("count", Box::new(count_helper));
     handlebars.register_helper("json", Box::new(json_helper));
+    handlebars.register_helper("jsonpath", Box::new(jsonpath_helper));
     handlebars.register_helper("merge", Box::new(merge_helper));
     handlebars.register_helper("select", Box::new(select_helper));
     handlebars.register_helper("default", Box::new(default_helper));
+    handlebars.register_helper("date", Box::new(date_helper));
 }
 
-/// Helper for getting array length
+/// Resolve an array parameter: an array value, the JSON text written by a
+/// subexpression such as `(filter ...)`, or a dotted path like
+/// `"fixtures.org.members"` looked up in the template context
+fn array_param(h: &Helper, index: usize, ctx: &Context) -> Option<Vec<Value>> {
+    let value = h.param(index)?.value();
+    match value {
+        Value::Array(items) => Some(items.clone()),
+        Value::String(text) => match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(items)) => Some(items),
+            _ => lookup_path(ctx.data(), text)?.as_array().cloned(),
+        },
+        _ => None,
+    }
+}
+
+/// Follow a dotted path (`org.members`, `profile.active`) into a value
+fn lookup_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        })
+}
+
+/// Whether `item` has `value` at the (possibly dotted) field `key`
+fn item_matches(item: &Value, key: &str, value: &Value) -> bool {
+    lookup_path(item, key).is_some_and(|item_value| item_value == value)
+}
+
+/// Helper returning the first array item whose `key` field equals `value`
 pub fn find_helper(
     h: &Helper,
     _: &Handlebars,
-    _: &Context,
+    ctx: &Context,
     _: &mut RenderContext,
     out: &mut dyn Output,
 ) -> HelperResult {
-    if let Some(array_param) = h.param(0) {
-        if let Some(array) = array_param.value().as_array() {
-            if let Some(key_param) = h.param(1) {
-                if let Some(key) = key_param.value().as_str() {
-                    if let Some(value_param) = h.param(2) {
-                        // Find item where item[key] == value
-                        for item in array {
-                            if let Some(obj) = item.as_object() {
-                                if let Some(item_value) = obj.get(key) {
-                                    if item_value == value_param.value() {
-                                        out.write(
-                                            &serde_json::to_string(item).unwrap_or_default(),
-                                        )?;
-                                        return Ok(());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    if let (Some(array), Some(key), Some(value_param)) = (
+        array_param(h, 0, ctx),
+        h.param(1).and_then(|p| p.value().as_str()),
+        h.param(2),
+    ) {
+        if let Some(item) = array
+            .iter()
+            .find(|item| item_matches(item, key, value_param.value()))
+        {
+            out.write(&serde_json::to_string(item).unwrap_or_default())?;
+            return Ok(());
         }
     }
     out.write("null")?;
     Ok(())
 }
 
-/// Helper for filtering arrays
+/// Helper for filtering arrays to the items whose `key` field equals `value`
 pub fn filter_helper(
     h: &Helper,
     _: &Handlebars,
-    _: &Context,
+    ctx: &Context,
     _: &mut RenderContext,
     out: &mut dyn Output,
 ) -> HelperResult {
-    if let Some(array_param) = h.param(0) {
-        if let Some(array) = array_param.value().as_array() {
-            if let Some(key_param) = h.param(1) {
-                if let Some(key) = key_param.value().as_str() {
-                    if let Some(value_param) = h.param(2) {
-                        // Filter items where item[key] == value
-                        let filtered: Vec<&Value> = array
-                            .iter()
-                            .filter(|item| {
-                                if let Some(obj) = item.as_object() {
-                                    if let Some(item_value) = obj.get(key) {
-                                        return item_value == value_param.value();
-                                    }
-                                }
-                                false
-                            })
-                            .collect();
-
-                        out.write(&serde_json::to_string(&filtered).unwrap_or_default())?;
-                        return Ok(());
-                    }
-                }
-            }
-        }
+    if let (Some(array), Some(key), Some(value_param)) = (
+        array_param(h, 0, ctx),
+        h.param(1).and_then(|p| p.value().as_str()),
+        h.param(2),
+    ) {
+        let filtered: Vec<&Value> = array
+            .iter()
+            .filter(|item| item_matches(item, key, value_param.value()))
+            .collect();
+        out.write(&serde_json::to_string(&filtered).unwrap_or_default())?;
+        return Ok(());
     }
     out.write("[]")?;
     Ok(())
 }
 
+/// Helper counting array items, or only those whose `key` field equals
+/// `value` when both are given: `{{count (filter list "active" true)}}`
+pub fn count_helper(
+    h: &Helper,
+    _: &Handlebars,
+    ctx: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let array = array_param(h, 0, ctx).unwrap_or_default();
+    let count = match (h.param(1).and_then(|p| p.value().as_str()), h.param(2)) {
+        (Some(key), Some(value_param)) => array
+            .iter()
+            .filter(|item| item_matches(item, key, value_param.value()))
+            .count(),
+        _ => array.len(),
+    };
+    out.write(&count.to_string())?;
+    Ok(())
+}
+
 /// Helper for JSON serialization
 pub fn json_helper(
     h: &Helper,
-    _: &Handlebars,
+    r: &Handlebars,
     _: &Context,
     _: &mut RenderContext,
     out: &mut dyn Output,
 ) -> HelperResult {
     if let Some(param) = h.param(0) {
+        // Bare `{{fixtures.x}}` is rewritten to this helper, so it enforces strict mode itself
+        if r.strict_mode() && param.is_value_missing() {
+            return Err(RenderErrorReason::MissingVariable(param.relative_path().cloned()).into());
+        }
         let value = param.value();
         if let Some(s) = value.as_str() {
             out.write(s)?;
@@ -104,6 +142,63 @@ pub fn json_helper(
     Ok(())
 }
 
+/// Helper for JSONPath extraction, e.g. `{{jsonpath fixtures.data "$.users[0].email"}}`
+///
+/// A singular path (names and indexes only) writes its match like `json`
+/// does, and nothing when absent. Any other path writes a JSON array of every match.
+pub fn jsonpath_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let source = h
+        .param(1)
+        .and_then(|p| p.value().as_str())
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("jsonpath", 1))?;
+    let path = serde_json_path::JsonPath::parse(source)
+        .map_err(|e| RenderErrorReason::Other(format!("Invalid JSONPath '{}': {}", source, e)))?;
+
+    // Accept the JSON text written by a subexpression as well as a value
+    let value = h.param(0).map(|p| p.value()).unwrap_or(&Value::Null);
+    let parsed = value
+        .as_str()
+        .and_then(|text| serde_json::from_str::<Value>(text).ok());
+    let matches = path.query(parsed.as_ref().unwrap_or(value)).all();
+
+    if is_singular_path(source) {
+        match matches.first() {
+            Some(Value::String(s)) => out.write(s)?,
+            Some(value) => out.write(&value.to_string())?,
+            None => {}
+        }
+    } else {
+        let all = Value::Array(matches.into_iter().cloned().collect());
+        out.write(&all.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether a path selects at most one value: no wildcard, slice, union,
+/// filter or descendant segment outside quoted names
+fn is_singular_path(path: &str) -> bool {
+    let mut quote = None;
+    let mut previous = None;
+    for c in path.chars() {
+        match quote {
+            Some(q) if c == q && previous != Some('\\') => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if matches!(c, '*' | ':' | ',' | '?') => return false,
+            None if c == '.' && previous == Some('.') => return false,
+            None => {}
+        }
+        previous = Some(c);
+    }
+    true
+}
+
 /// Helper for finding items by field
 pub fn find_by_field_helper(
     h: &Helper,
@@ -254,6 +349,43 @@ pub fn default_helper(
     Ok(())
 }
 
+/// Helper for formatted timestamps, e.g. `{{date "%Y-%m-%d" offset=86400}}`
+///
+/// The format uses strftime specifiers and defaults to ISO-8601; `offset`
+/// shifts the current time by that many seconds (negative for the past).
+pub fn date_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let offset = match h.hash_get("offset").map(|p| p.value()) {
+        None => 0,
+        Some(value) => value
+            .as_i64()
+            .or_else(|| value.as_str()?.trim().parse().ok())
+            .ok_or_else(|| {
+                RenderErrorReason::InvalidParamType("date offset must be a number of seconds")
+            })?,
+    };
+    let time = Utc::now() + Duration::seconds(offset);
+
+    match h.param(0).and_then(|p| p.value().as_str()) {
+        Some(format) => {
+            let items: Vec<Item> = StrftimeItems::new(format).collect();
+            if items.contains(&Item::Error) {
+                return Err(
+                    RenderErrorReason::Other(format!("Invalid date format '{}'", format)).into(),
+                );
+            }
+            out.write(&time.format_with_items(items.into_iter()).to_string())?;
+        }
+        None => out.write(&time.to_rfc3339())?,
+    }
+    Ok(())
+}
+
 /// Helper for finding items by multiple fields
 pub fn find_by_multi_field_helper(
     h: &Helper,
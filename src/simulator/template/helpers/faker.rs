@@ -3,7 +3,9 @@ use fake::{
     faker::{internet::en::FreeEmail, lorem::en::Sentence, name::en::Name},
     Fake,
 };
-use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use handlebars::{
+    Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderErrorReason,
+};
 use uuid::Uuid;
 
 /// Register faker-related helpers
@@ -12,6 +14,7 @@ pub fn register(handlebars: &mut Handlebars) {
     handlebars.register_helper("random", Box::new(random_helper));
     handlebars.register_helper("random_string", Box::new(random_string_helper));
     handlebars.register_helper("faker", Box::new(faker_helper));
+    handlebars.register_helper("uuid", Box::new(uuid_helper));
 }
 
 /// Helper for generating current timestamp
@@ -28,6 +31,28 @@ pub fn now_helper(
     Ok(())
 }
 
+/// Helper for generating UUIDs: random v4 by default, `{{uuid "v7"}}` for
+/// time-ordered ids
+pub fn uuid_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let id = match h.param(0).and_then(|v| v.value().as_str()) {
+        None | Some("v4") => Uuid::new_v4(),
+        Some("v7") => Uuid::now_v7(),
+        Some(other) => {
+            return Err(
+                RenderErrorReason::Other(format!("Unsupported UUID version '{}'", other)).into(),
+            )
+        }
+    };
+    out.write(&id.to_string())?;
+    Ok(())
+}
+
 /// Helper for generating random values
 pub fn random_helper(
     h: &Helper,
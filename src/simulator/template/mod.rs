@@ -4,15 +4,17 @@
 //! that supports dynamic responses based on request data, fixtures, and service state.
 
 use crate::errors::{ApicentricError, ApicentricResult};
+use crate::simulator::config::{ResponseDefinition, ServiceDefinition};
 use crate::simulator::service::state::DataBucket;
-use handlebars::Handlebars;
+use handlebars::{Handlebars, RenderErrorReason};
 use serde_json::{Map, Value};
+use std::collections::HashMap;
 
 pub mod context;
 pub mod helpers;
 pub mod preprocessor;
 
-pub use context::{RequestContext, TemplateContext};
+pub use context::{body_content_type, parse_cookies, RequestContext, TemplateContext};
 use helpers::{bucket::register_bucket_helpers, core::register_core_helpers};
 use preprocessor::TemplatePreprocessor;
 
@@ -20,6 +22,8 @@ use preprocessor::TemplatePreprocessor;
 pub struct TemplateEngine {
     handlebars: Handlebars<'static>,
     preprocessor: TemplatePreprocessor,
+    /// Original source of each compiled template, keyed by registration name
+    compiled: HashMap<String, String>,
 }
 
 /// Port trait for rendering templates
@@ -56,6 +60,7 @@ impl TemplateEngine {
         Ok(Self {
             handlebars,
             preprocessor: TemplatePreprocessor,
+            compiled: HashMap::new(),
         })
     }
 
@@ -65,6 +70,25 @@ impl TemplateEngine {
         Ok(())
     }
 
+    /// Register named partials that templates can include with `{{> name}}`
+    pub fn register_partials(
+        &mut self,
+        partials: &HashMap<String, String>,
+    ) -> ApicentricResult<()> {
+        for (name, template) in partials {
+            let processed = self.preprocessor.preprocess(template);
+            self.handlebars
+                .register_partial(name, processed)
+                .map_err(|e| {
+                    ApicentricError::config_error(
+                        format!("Partial registration failed for '{}': {}", name, e),
+                        Some("Check partial template syntax"),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
     /// Register built-in template helpers
     fn register_helpers(handlebars: &mut Handlebars) -> ApicentricResult<()> {
         helpers::faker::register(handlebars);
@@ -84,12 +108,33 @@ impl TemplateEngine {
 
         self.handlebars
             .render_template(&processed_template, &json_context)
-            .map_err(|e| {
-                ApicentricError::runtime_error(
-                    format!("Template rendering failed: {}", e),
-                    Some("Check template syntax and available context variables"),
-                )
-            })
+            .map_err(|e| Self::render_error(&e, None))
+    }
+
+    /// Map a Handlebars failure, calling out undefined variables in strict mode
+    fn render_error(error: &handlebars::RenderError, name: Option<&str>) -> ApicentricError {
+        if let RenderErrorReason::MissingVariable(variable) = error.reason() {
+            let variable = variable.as_deref().unwrap_or("?");
+            log::warn!("Template references undefined variable '{}'", variable);
+            return ApicentricError::runtime_error(
+                format!("Template references undefined variable '{}'", variable),
+                Some("Fix the variable name or disable server.strict_templates"),
+            );
+        }
+        let message = match name {
+            Some(name) => format!("Template rendering failed for '{}': {}", name, error),
+            None => format!("Template rendering failed: {}", error),
+        };
+        ApicentricError::runtime_error(
+            message,
+            Some("Check template syntax and available context variables"),
+        )
+    }
+
+    /// Fail rendering when a template references a variable missing from the
+    /// context; by default such references render as empty
+    pub fn set_strict_mode(&mut self, strict: bool) {
+        self.handlebars.set_strict_mode(strict);
     }
 
     /// Convert template context to JSON for Handlebars
@@ -181,26 +226,146 @@ impl TemplateEngine {
             ),
         );
 
+        request_obj.insert(
+            "cookies".to_string(),
+            Value::Object(
+                context
+                    .request
+                    .cookies
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                    .collect(),
+            ),
+        );
+
         // Add request body if present
         if let Some(ref body) = context.request.body {
             request_obj.insert("body".to_string(), body.clone());
         }
+        if let Some(cert) = &context.request.client_cert {
+            request_obj.insert(
+                "client_cert".to_string(),
+                serde_json::json!({ "cn": cert.cn, "subject": cert.subject }),
+            );
+        }
+        if let Some(content_type) = &context.request.content_type {
+            request_obj.insert(
+                "content_type".to_string(),
+                Value::String(content_type.clone()),
+            );
+        }
 
         json_context.insert("request".to_string(), Value::Object(request_obj));
 
+        // Add state machine state
+        if let Some(current) = &context.machine_state {
+            json_context.insert(
+                "state".to_string(),
+                serde_json::json!({ "current": current }),
+            );
+        }
+
         Ok(Value::Object(json_context))
     }
 
-    /// Compile and cache a template for better performance
+    /// Preprocess, compile and cache a template under `name` for `render_compiled`
     pub fn compile_template(&mut self, name: &str, template: &str) -> ApicentricResult<()> {
+        let processed = self.preprocessor.preprocess(template);
         self.handlebars
-            .register_template_string(name, template)
+            .register_template_string(name, processed)
             .map_err(|e| {
                 ApicentricError::config_error(
                     format!("Template compilation failed for '{}': {}", name, e),
                     Some("Check template syntax"),
                 )
-            })
+            })?;
+        self.compiled.insert(name.to_string(), template.to_string());
+        Ok(())
+    }
+
+    /// Render `template` through the copy compiled under `name` when it is
+    /// still the same source, falling back to ad-hoc rendering otherwise
+    /// (e.g. for scenario overrides or endpoints added at runtime)
+    pub fn render_cached(
+        &self,
+        name: &str,
+        template: &str,
+        context: &TemplateContext,
+    ) -> ApicentricResult<String> {
+        match self.compiled.get(name) {
+            Some(source) if source == template => self.render_compiled(name, context),
+            _ => self.render(template, context),
+        }
+    }
+
+    /// Render every response template of a service against sample data.
+    ///
+    /// Returns one message per failing template, prefixed with its location
+    /// (e.g. `GET /users/{id} response 200 body`).
+    pub fn check_service_templates(service: &ServiceDefinition) -> ApicentricResult<Vec<String>> {
+        let mut engine = Self::new()?;
+        engine.register_bucket_helpers(DataBucket::new(service.bucket.clone()))?;
+        if let Some(partials) = &service.partials {
+            engine.register_partials(partials)?;
+        }
+
+        let fixtures = service.fixtures.clone().unwrap_or_default();
+        let mut failures = Vec::new();
+        for endpoint in service.endpoints.as_deref().unwrap_or_default() {
+            let context =
+                TemplateContext::sample(fixtures.clone(), &endpoint.method, &endpoint.path);
+            let endpoint_name = format!("{} {}", endpoint.method.to_uppercase(), endpoint.path);
+
+            let mut statuses: Vec<&u16> = endpoint.responses.keys().collect();
+            statuses.sort();
+            for status in statuses {
+                let location = format!("{} response {}", endpoint_name, status);
+                engine.check_response(
+                    &endpoint.responses[status],
+                    &location,
+                    &context,
+                    &mut failures,
+                );
+            }
+            for (index, scenario) in endpoint.scenarios.iter().flatten().enumerate() {
+                let name = scenario
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("#{}", index));
+                let location = format!("{} scenario '{}'", endpoint_name, name);
+                engine.check_response(
+                    &scenario.response.definition,
+                    &location,
+                    &context,
+                    &mut failures,
+                );
+            }
+        }
+        Ok(failures)
+    }
+
+    fn check_response(
+        &self,
+        response: &ResponseDefinition,
+        location: &str,
+        context: &TemplateContext,
+        failures: &mut Vec<String>,
+    ) {
+        let mut templates = vec![("body".to_string(), &response.body)];
+        if let Some(condition) = &response.condition {
+            templates.push(("condition".to_string(), condition));
+        }
+        for (name, body) in response.examples.iter().flatten() {
+            templates.push((format!("example '{}'", name), body));
+        }
+        for (name, value) in response.headers.iter().flatten() {
+            templates.push((format!("header '{}'", name), value));
+        }
+        for (part, template) in templates {
+            if let Err(e) = self.render(template, context) {
+                failures.push(format!("{} {}: {}", location, part, e));
+            }
+        }
     }
 
     /// Render a pre-compiled template
@@ -211,12 +376,9 @@ impl TemplateEngine {
     ) -> ApicentricResult<String> {
         let json_context = self.context_to_json(context)?;
 
-        self.handlebars.render(name, &json_context).map_err(|e| {
-            ApicentricError::runtime_error(
-                format!("Template rendering failed for '{}': {}", name, e),
-                Some("Check template syntax and available context variables"),
-            )
-        })
+        self.handlebars
+            .render(name, &json_context)
+            .map_err(|e| Self::render_error(&e, Some(name)))
     }
 }
 
@@ -226,6 +388,48 @@ mod tests {
     use crate::simulator::service::state::DataBucket;
     use serde_json::json;
 
+    #[test]
+    fn check_service_templates_reports_failing_location() {
+        let service: ServiceDefinition = serde_yaml::from_str(
+            r#"
+name: broken
+fixtures:
+  users: [{id: "1", name: Ada}]
+endpoints:
+  - method: GET
+    path: /users/{id}
+    responses:
+      200:
+        content_type: application/json
+        body: '{{find fixtures.users "id" params.id}}'
+      404:
+        content_type: application/json
+        body: '{"missing": "{{#if params.id}}"}'
+"#,
+        )
+        .unwrap();
+
+        let failures = TemplateEngine::check_service_templates(&service).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("GET /users/{id} response 404 body:"));
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_undefined_variables() {
+        let mut engine = TemplateEngine::new().unwrap();
+        let mut context = TemplateContext::minimal();
+        context.params.insert("id".to_string(), "7".to_string());
+
+        assert_eq!(engine.render("[{{params.typo}}]", &context).unwrap(), "[]");
+
+        engine.set_strict_mode(true);
+        assert_eq!(engine.render("{{params.id}}", &context).unwrap(), "7");
+        let err = engine.render("[{{params.typo}}]", &context).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Template references undefined variable 'params.typo'"));
+    }
+
     #[test]
     fn test_template_engine_creation() {
         let engine = TemplateEngine::new();
@@ -266,6 +470,63 @@ mod tests {
         assert_eq!(result.unwrap(), "POST /users");
     }
 
+    #[test]
+    fn test_compiled_template_matches_ad_hoc_rendering() {
+        let mut engine = TemplateEngine::new().unwrap();
+        let mut context = TemplateContext::minimal();
+        context.request.method = "POST".to_string();
+        context
+            .fixtures
+            .insert("items".to_string(), json!([{"id": 1}, {"id": 2}]));
+        let template = r#"{"method": "{{request.method | lower}}", "items": {{fixtures.items}}, "count": {{length fixtures.items}}}"#;
+
+        engine.compile_template("0:200:body", template).unwrap();
+        let ad_hoc = engine.render(template, &context).unwrap();
+        assert_eq!(
+            engine.render_compiled("0:200:body", &context).unwrap(),
+            ad_hoc
+        );
+        assert_eq!(
+            engine
+                .render_cached("0:200:body", template, &context)
+                .unwrap(),
+            ad_hoc
+        );
+        // A different source under the same name renders ad hoc
+        assert_eq!(
+            engine
+                .render_cached("0:200:body", "{{request.method}}", &context)
+                .unwrap(),
+            "POST"
+        );
+    }
+
+    #[test]
+    fn test_each_block_iterates_fixture_array() {
+        let engine = TemplateEngine::new().unwrap();
+        let mut context = TemplateContext::minimal();
+        context.fixtures.insert(
+            "users".to_string(),
+            json!([{"name": "Ada"}, {"name": "Grace"}, {"name": "Linus"}]),
+        );
+
+        let result = engine
+            .render(
+                "{{#each fixtures.users}}{{this.name}}{{#unless @last}}, {{/unless}}{{/each}}",
+                &context,
+            )
+            .unwrap();
+        assert_eq!(result, "Ada, Grace, Linus");
+
+        let result = engine
+            .render(
+                "{{#each fixtures.users as | user |}}{{user.name}};{{/each}}",
+                &context,
+            )
+            .unwrap();
+        assert_eq!(result, "Ada;Grace;Linus;");
+    }
+
     #[test]
     fn test_now_helper() {
         let engine = TemplateEngine::new().unwrap();
@@ -330,4 +591,17 @@ mod tests {
         assert_eq!(result, "42");
         assert_eq!(bucket.get("foo"), Some(json!(42)));
     }
+
+    #[test]
+    fn test_partials_rendering() {
+        let mut engine = TemplateEngine::new().unwrap();
+        let mut partials = HashMap::new();
+        partials.insert("greeting".to_string(), "Hello {{params.name}}".to_string());
+        engine.register_partials(&partials).unwrap();
+        let mut context = TemplateContext::minimal();
+        context.params.insert("name".to_string(), "Ada".to_string());
+
+        let result = engine.render("{{> greeting}}!", &context).unwrap();
+        assert_eq!(result, "Hello Ada!");
+    }
 }
@@ -30,6 +30,16 @@ impl TemplatePreprocessor {
             .replace_all(&result, |caps: &regex::Captures| {
                 let content = caps.get(1).unwrap().as_str().trim();
 
+                // Leave block helper tags (`{{#each}}`, `{{/each}}`, `{{else}}`) to Handlebars
+                let tag = content.trim_start_matches('~');
+                if tag.starts_with(['#', '/', '^'])
+                    || tag == "else"
+                    || tag.starts_with("else ")
+                    || tag.starts_with("else~")
+                {
+                    return caps[0].to_string();
+                }
+
                 // Skip if it's already processed (contains json, length, etc.)
                 if content.starts_with("json ")
                     || content.starts_with("length ")
@@ -163,6 +173,17 @@ mod tests {
         assert_eq!(result, "{{json fixtures.user}}");
     }
 
+    #[test]
+    fn leaves_block_helper_tags_alone() {
+        let pre = TemplatePreprocessor;
+        let tpl = "{{#each fixtures.users as | user |}}{{user.name}}{{else}}none{{/each}}";
+        assert_eq!(pre.preprocess(tpl), tpl);
+        assert_eq!(
+            pre.preprocess("{{~#each fixtures.users~}}{{ fixtures.tag }}{{~/each}}"),
+            "{{~#each fixtures.users~}}{{json fixtures.tag}}{{~/each}}"
+        );
+    }
+
     #[test]
     fn handles_pipe_expression() {
         let pre = TemplatePreprocessor;
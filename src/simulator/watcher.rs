@@ -28,6 +28,8 @@ impl ConfigWatcher {
                                 let change = match event.kind {
                                     EventKind::Create(_) => ConfigChange::ServiceAdded(name),
                                     EventKind::Remove(_) => ConfigChange::ServiceRemoved(name),
+                                    // Reading the file during a reload must not trigger another
+                                    EventKind::Access(_) => return,
                                     _ => ConfigChange::ServiceModified(name),
                                 };
                                 let _ = tx.blocking_send(change);
@@ -6,7 +6,7 @@ use serde::Deserialize;
 use serde_json::Value;
 
 use crate::simulator::config::{
-    EndpointDefinition, EndpointKind, ResponseDefinition, ScenarioConditions, ScenarioDefinition,
+    EndpointDefinition, ResponseDefinition, ScenarioConditions, ScenarioDefinition,
     ScenarioResponse, ScenarioStrategy, ServerConfig, ServiceDefinition,
 };
 
@@ -130,18 +130,18 @@ fn convert(stubs: &[WiremockStub], meta: Option<&WiremockMeta>) -> ServiceDefini
         version: None,
         description: None,
         server: Some(ServerConfig {
-            port: None,
             base_path: "/".into(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints,
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -173,6 +173,8 @@ fn convert_stub(stub: &WiremockStub) -> EndpointDefinition {
                 } else {
                     None
                 },
+                weight: None,
+                sticky: None,
             });
         }
         responses.insert(404, empty_not_found_response());
@@ -184,10 +186,15 @@ fn convert_stub(stub: &WiremockStub) -> EndpointDefinition {
                 conditions: Some(ScenarioConditions {
                     query: None,
                     headers: None,
+                    cookies: None,
                     body: Some(body),
+                    body_regex: None,
+                    content_type: None,
                 }),
                 response: ScenarioResponse { status, definition },
                 strategy: None,
+                weight: None,
+                sticky: None,
             });
             responses.insert(404, empty_body_mismatch_response());
         } else {
@@ -198,20 +205,17 @@ fn convert_stub(stub: &WiremockStub) -> EndpointDefinition {
     }
 
     EndpointDefinition {
-        kind: EndpointKind::Http,
         method,
         path,
         header_match,
         description: stub.name.clone(),
-        parameters: None,
-        request_body: None,
         responses,
         scenarios: if scenarios.is_empty() {
             None
         } else {
             Some(scenarios)
         },
-        stream: None,
+        ..Default::default()
     }
 }
 
@@ -314,7 +318,16 @@ fn convert_response(response: &WiremockResponse) -> (u16, ResponseDefinition) {
             schema: None,
             script: None,
             headers: headers_opt,
+            trailers: None,
+            examples: None,
+            record_delay_ms: None,
+            pretty_json: None,
+            sparse_fields: false,
+            serve_times: None,
+            exec: None,
             side_effects: None,
+            body_base64: None,
+            body_file: None,
         },
     )
 }
@@ -327,7 +340,16 @@ fn empty_default_response() -> ResponseDefinition {
         schema: None,
         script: None,
         headers: None,
+        trailers: None,
+        examples: None,
+        record_delay_ms: None,
+        pretty_json: None,
+        sparse_fields: false,
+        serve_times: None,
+        exec: None,
         side_effects: None,
+        body_base64: None,
+        body_file: None,
     }
 }
 
@@ -339,7 +361,16 @@ fn empty_not_found_response() -> ResponseDefinition {
         schema: None,
         script: None,
         headers: None,
+        trailers: None,
+        examples: None,
+        record_delay_ms: None,
+        pretty_json: None,
+        sparse_fields: false,
+        serve_times: None,
+        exec: None,
         side_effects: None,
+        body_base64: None,
+        body_file: None,
     }
 }
 
@@ -351,7 +382,16 @@ fn empty_body_mismatch_response() -> ResponseDefinition {
         schema: None,
         script: None,
         headers: None,
+        trailers: None,
+        examples: None,
+        record_delay_ms: None,
+        pretty_json: None,
+        sparse_fields: false,
+        serve_times: None,
+        exec: None,
         side_effects: None,
+        body_base64: None,
+        body_file: None,
     }
 }
 
@@ -1,6 +1,8 @@
 use crate::errors::ApicentricResult;
 use crate::simulator::config::ServiceDefinition;
 use crate::simulator::log::RequestLogEntry;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 #[derive(Debug, Default)]
 pub struct LogStats {
@@ -10,9 +12,28 @@ pub struct LogStats {
     pub avg_response_time_ms: f64,
 }
 
+/// A previously saved service definition
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceVersion {
+    pub saved_at: DateTime<Utc>,
+    pub definition: ServiceDefinition,
+}
+
 pub trait Storage: Send + Sync {
     fn save_service(&self, service: &ServiceDefinition) -> ApicentricResult<()>;
     fn load_service(&self, name: &str) -> ApicentricResult<Option<ServiceDefinition>>;
+    /// Save a service and add it to its version history. Stores without
+    /// history just save it.
+    fn save_service_version(&self, service: &ServiceDefinition) -> ApicentricResult<()> {
+        self.save_service(service)
+    }
+    /// Versions kept for a service, oldest first
+    fn service_history(&self, _name: &str) -> ApicentricResult<Vec<ServiceVersion>> {
+        Ok(Vec::new())
+    }
+    /// Remember the active scenario of a service; `None` clears it.
+    fn save_scenario(&self, service: &str, scenario: Option<&str>) -> ApicentricResult<()>;
+    fn load_scenario(&self, service: &str) -> ApicentricResult<Option<String>>;
     fn append_log(&self, entry: &RequestLogEntry) -> ApicentricResult<()>;
     fn query_logs(
         &self,
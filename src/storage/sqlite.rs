@@ -1,15 +1,19 @@
 use std::path::Path;
 
-use rusqlite::{params, Connection, ToSql};
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
 use std::sync::Mutex;
 
 use crate::errors::{ApicentricError, ApicentricResult};
 use crate::simulator::config::ServiceDefinition;
 use crate::simulator::log::RequestLogEntry;
-use crate::storage::{LogStats, Storage};
+use crate::storage::{LogStats, ServiceVersion, Storage};
+
+/// Versions of each service definition kept unless configured otherwise
+pub const DEFAULT_HISTORY_LIMIT: usize = 10;
 
 pub struct SqliteStorage {
     conn: Mutex<Connection>,
+    history_limit: usize,
 }
 
 impl SqliteStorage {
@@ -42,6 +46,33 @@ impl SqliteStorage {
             )
         })?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS service_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                saved_at TEXT NOT NULL,
+                definition TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to create service_versions table: {}", e),
+                None::<String>,
+            )
+        })?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS scenarios (service TEXT PRIMARY KEY, scenario TEXT NOT NULL)",
+            [],
+        )
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to create scenarios table: {}", e),
+                None::<String>,
+            )
+        })?;
+
         // Try to add the payload column if it doesn't exist (migrations)
         let _ = conn.execute("ALTER TABLE logs ADD COLUMN payload TEXT", []);
 
@@ -54,7 +85,8 @@ impl SqliteStorage {
                 method TEXT NOT NULL,
                 path TEXT NOT NULL,
                 status INTEGER NOT NULL,
-                payload TEXT
+                payload TEXT,
+                duration_ms INTEGER
             )",
             [],
         )
@@ -64,11 +96,20 @@ impl SqliteStorage {
                 None::<String>,
             )
         })?;
+        // Databases created before request timing was recorded
+        let _ = conn.execute("ALTER TABLE logs ADD COLUMN duration_ms INTEGER", []);
 
         Ok(Self {
             conn: Mutex::new(conn),
+            history_limit: DEFAULT_HISTORY_LIMIT,
         })
     }
+
+    /// Keep the last `limit` versions of each service definition
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self
+    }
 }
 
 impl Storage for SqliteStorage {
@@ -133,6 +174,147 @@ impl Storage for SqliteStorage {
         }
     }
 
+    fn save_service_version(&self, service: &ServiceDefinition) -> ApicentricResult<()> {
+        let value = serde_json::to_value(service).map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to serialize service: {}", e),
+                None::<String>,
+            )
+        })?;
+        let json = value.to_string();
+        let db_error = |e: rusqlite::Error| {
+            ApicentricError::runtime_error(
+                format!("Failed to save service version: {}", e),
+                None::<String>,
+            )
+        };
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| ApicentricError::runtime_error("DB locked".to_string(), None::<String>))?;
+        let tx = conn.unchecked_transaction().map_err(db_error)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO services (name, definition) VALUES (?1, ?2)",
+            params![service.name, json],
+        )
+        .map_err(db_error)?;
+
+        // Restarts save the same definition again; only changes make a version
+        let latest: Option<String> = tx
+            .query_row(
+                "SELECT definition FROM service_versions WHERE name = ?1 ORDER BY id DESC LIMIT 1",
+                params![service.name],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?;
+        let unchanged = latest
+            .and_then(|latest| serde_json::from_str::<serde_json::Value>(&latest).ok())
+            .is_some_and(|latest| latest == value);
+        if !unchanged {
+            tx.execute(
+                "INSERT INTO service_versions (name, saved_at, definition) VALUES (?1, ?2, ?3)",
+                params![service.name, chrono::Utc::now().to_rfc3339(), json],
+            )
+            .map_err(db_error)?;
+            tx.execute(
+                "DELETE FROM service_versions WHERE name = ?1 AND id NOT IN (
+                    SELECT id FROM service_versions WHERE name = ?1 ORDER BY id DESC LIMIT ?2
+                )",
+                params![service.name, self.history_limit as i64],
+            )
+            .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)
+    }
+
+    fn service_history(&self, name: &str) -> ApicentricResult<Vec<ServiceVersion>> {
+        let db_error = |e: rusqlite::Error| {
+            ApicentricError::runtime_error(
+                format!("Failed to load service history: {}", e),
+                None::<String>,
+            )
+        };
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| ApicentricError::runtime_error("DB locked".to_string(), None::<String>))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT saved_at, definition FROM service_versions WHERE name = ?1 ORDER BY id",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![name], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(db_error)?;
+
+        let mut versions = Vec::new();
+        for row in rows {
+            let (saved_at, json) = row.map_err(db_error)?;
+            let saved_at = chrono::DateTime::parse_from_rfc3339(&saved_at)
+                .map_err(|e| {
+                    ApicentricError::runtime_error(
+                        format!("Invalid service version timestamp: {}", e),
+                        None::<String>,
+                    )
+                })?
+                .with_timezone(&chrono::Utc);
+            let definition = serde_json::from_str(&json).map_err(|e| {
+                ApicentricError::runtime_error(
+                    format!("Failed to deserialize service: {}", e),
+                    None::<String>,
+                )
+            })?;
+            versions.push(ServiceVersion {
+                saved_at,
+                definition,
+            });
+        }
+        Ok(versions)
+    }
+
+    fn save_scenario(&self, service: &str, scenario: Option<&str>) -> ApicentricResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| ApicentricError::runtime_error("DB locked".to_string(), None::<String>))?;
+        let result = match scenario {
+            Some(scenario) => conn.execute(
+                "INSERT OR REPLACE INTO scenarios (service, scenario) VALUES (?1, ?2)",
+                params![service, scenario],
+            ),
+            None => conn.execute("DELETE FROM scenarios WHERE service = ?1", params![service]),
+        };
+        result.map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to save scenario: {}", e),
+                None::<String>,
+            )
+        })?;
+        Ok(())
+    }
+
+    fn load_scenario(&self, service: &str) -> ApicentricResult<Option<String>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| ApicentricError::runtime_error("DB locked".to_string(), None::<String>))?;
+        conn.query_row(
+            "SELECT scenario FROM scenarios WHERE service = ?1",
+            params![service],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| {
+            ApicentricError::runtime_error(
+                format!("Failed to load scenario: {}", e),
+                None::<String>,
+            )
+        })
+    }
+
     fn append_log(&self, entry: &RequestLogEntry) -> ApicentricResult<()> {
         let conn = self
             .conn
@@ -140,7 +322,7 @@ impl Storage for SqliteStorage {
             .map_err(|_| ApicentricError::runtime_error("DB locked".to_string(), None::<String>))?;
 
         conn.execute(
-                "INSERT INTO logs (timestamp, service, endpoint, method, path, status, payload) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                "INSERT INTO logs (timestamp, service, endpoint, method, path, status, payload, duration_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                 params![
                     entry.timestamp.to_rfc3339(),
                     entry.service,
@@ -148,7 +330,8 @@ impl Storage for SqliteStorage {
                     entry.method,
                     entry.path,
                     entry.status as i64,
-                    entry.payload
+                    entry.payload,
+                    entry.duration_ms.map(|v| v as i64)
                 ],
             )
             .map_err(|e| ApicentricError::runtime_error(format!("Failed to insert log: {}", e), None::<String>))?;
@@ -165,7 +348,7 @@ impl Storage for SqliteStorage {
         limit: usize,
     ) -> ApicentricResult<Vec<RequestLogEntry>> {
         let mut sql = String::from(
-            "SELECT timestamp, service, endpoint, method, path, status, payload FROM logs",
+            "SELECT timestamp, service, endpoint, method, path, status, payload, duration_ms FROM logs",
         );
         let mut conditions: Vec<String> = Vec::new();
         let mut params: Vec<Box<dyn ToSql>> = Vec::new();
@@ -225,6 +408,7 @@ impl Storage for SqliteStorage {
                         path: row.get(4)?,
                         status: row.get::<_, i64>(5)? as u16,
                         payload: row.get(6)?,
+                        duration_ms: row.get::<_, Option<i64>>(7)?.map(|v| v as u64),
                     })
                 },
             )
@@ -296,3 +480,38 @@ impl Storage for SqliteStorage {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(version: &str) -> ServiceDefinition {
+        serde_yaml::from_str(&format!("name: users\nversion: \"{}\"\n", version)).unwrap()
+    }
+
+    #[test]
+    fn keeps_the_last_versions_of_a_service_in_order() {
+        let storage = SqliteStorage::init_db(":memory:")
+            .unwrap()
+            .with_history_limit(3);
+        for version in ["1", "2", "2", "3"] {
+            storage.save_service_version(&service(version)).unwrap();
+        }
+
+        let versions: Vec<_> = storage
+            .service_history("users")
+            .unwrap()
+            .into_iter()
+            .map(|v| v.definition.version.unwrap())
+            .collect();
+        assert_eq!(versions, ["1", "2", "3"]);
+
+        storage.save_service_version(&service("4")).unwrap();
+        let history = storage.service_history("users").unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].definition.version.as_deref(), Some("2"));
+        assert!(history[0].saved_at <= history[2].saved_at);
+        let current = storage.load_service("users").unwrap().unwrap();
+        assert_eq!(current.version.as_deref(), Some("4"));
+    }
+}
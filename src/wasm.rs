@@ -0,0 +1,234 @@
+//! Minimal mock handler for running the simulator inside a browser or Service Worker.
+//!
+//! Only the wasm-safe parts of a service definition are used: endpoints,
+//! responses, fixtures and Handlebars bodies. Scripts, scenarios, proxying,
+//! storage and networking need the full simulator and are ignored here.
+
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Deserialize)]
+struct WasmService {
+    #[serde(default)]
+    server: Option<WasmServer>,
+    #[serde(default)]
+    fixtures: Option<Map<String, Value>>,
+    #[serde(default)]
+    endpoints: Vec<WasmEndpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WasmServer {
+    #[serde(default)]
+    base_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WasmEndpoint {
+    method: String,
+    path: String,
+    #[serde(default)]
+    responses: BTreeMap<u16, WasmResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WasmResponse {
+    content_type: String,
+    /// Template string, or a structured YAML value served as JSON
+    body: serde_yaml::Value,
+    #[serde(default)]
+    headers: Option<HashMap<String, String>>,
+}
+
+/// Response produced by [`mock_response`]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+/// Resolve a request against a single service definition without a server.
+///
+/// `headers_json` is a JSON object of request headers (empty means none).
+/// The endpoint's lowest 2xx response is served, falling back to its first
+/// response; unknown routes get a JSON 404.
+pub fn mock_response(
+    service_yaml: &str,
+    method: &str,
+    path: &str,
+    headers_json: &str,
+    body: &str,
+) -> Result<MockResponse, String> {
+    let service: WasmService =
+        serde_yaml::from_str(service_yaml).map_err(|e| format!("Invalid service YAML: {}", e))?;
+    let headers: Map<String, Value> = if headers_json.trim().is_empty() {
+        Map::new()
+    } else {
+        serde_json::from_str(headers_json).map_err(|e| format!("Invalid headers JSON: {}", e))?
+    };
+
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let base_path = service
+        .server
+        .as_ref()
+        .map(|s| s.base_path.trim_end_matches('/'))
+        .unwrap_or("");
+    let relative = path.strip_prefix(base_path).unwrap_or(path);
+
+    let matched = service
+        .endpoints
+        .iter()
+        .filter(|ep| ep.method.eq_ignore_ascii_case(method))
+        .find_map(|ep| match_path(&ep.path, relative).map(|params| (ep, params)));
+    let Some((endpoint, params)) = matched else {
+        return Ok(MockResponse {
+            status: 404,
+            headers: BTreeMap::from([("content-type".into(), "application/json".into())]),
+            body: r#"{"error": "Not found"}"#.to_string(),
+        });
+    };
+    let (status, response) = endpoint
+        .responses
+        .iter()
+        .find(|(status, _)| (200..300).contains(*status))
+        .or_else(|| endpoint.responses.iter().next())
+        .ok_or_else(|| format!("Endpoint {} {} has no responses", method, endpoint.path))?;
+
+    let template = match &response.body {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_json::to_string(other).map_err(|e| e.to_string())?,
+    };
+    let query: Map<String, Value> = url::form_urlencoded::parse(query.as_bytes())
+        .map(|(k, v)| (k.into_owned(), Value::String(v.into_owned())))
+        .collect();
+    let context = json!({
+        "fixtures": service.fixtures.unwrap_or_default(),
+        "params": params,
+        "query": query,
+        "request": {
+            "method": method.to_uppercase(),
+            "path": path,
+            "headers": headers,
+            "query": query,
+            "body": serde_json::from_str::<Value>(body).unwrap_or(Value::String(body.to_string())),
+        },
+    });
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("json", Box::new(json_helper));
+    let rendered = handlebars
+        .render_template(&template, &context)
+        .map_err(|e| format!("Template rendering failed: {}", e))?;
+
+    let mut response_headers: BTreeMap<String, String> = response
+        .headers
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v))
+        .collect();
+    response_headers.insert("content-type".into(), response.content_type.clone());
+    Ok(MockResponse {
+        status: *status,
+        headers: response_headers,
+        body: rendered,
+    })
+}
+
+/// Match `/users/{id}`-style endpoint paths, returning the captured parameters
+fn match_path(pattern: &str, path: &str) -> Option<Map<String, Value>> {
+    let pattern: Vec<&str> = pattern.trim_matches('/').split('/').collect();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    if pattern.len() != segments.len() {
+        return None;
+    }
+    let mut params = Map::new();
+    for (expected, actual) in pattern.iter().zip(&segments) {
+        match expected.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+            Some(name) => {
+                params.insert(name.to_string(), Value::String(actual.to_string()));
+            }
+            None if expected == actual => {}
+            None => return None,
+        }
+    }
+    Some(params)
+}
+
+fn json_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        match param.value() {
+            Value::String(s) => out.write(s)?,
+            value => out.write(&serde_json::to_string(value).unwrap_or_default())?,
+        }
+    }
+    Ok(())
+}
+
+/// Browser entry point for [`mock_response`].
+///
+/// Returns `{status, headers, body}`, or `{error}` when the service or
+/// template is invalid.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn handle_mock_request(
+    service_yaml: &str,
+    method: &str,
+    path: &str,
+    headers_json: &str,
+    body: &str,
+) -> JsValue {
+    let result = match mock_response(service_yaml, method, path, headers_json, body) {
+        Ok(response) => serde_json::to_string(&response).unwrap_or_default(),
+        Err(error) => json!({ "error": error }).to_string(),
+    };
+    js_sys::JSON::parse(&result).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serves_fixture_backed_endpoint() {
+        let yaml = r#"
+name: users
+server:
+  base_path: /api
+fixtures:
+  users:
+    - { id: 1, name: Ada }
+endpoints:
+  - method: GET
+    path: /users/{id}
+    responses:
+      404:
+        content_type: application/json
+        body: '{}'
+      200:
+        content_type: application/json
+        body: '{"id": {{params.id}}, "all": {{json fixtures.users}}}'
+"#;
+        let resp = mock_response(yaml, "get", "/api/users/1", "", "").unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.body, r#"{"id": 1, "all": [{"id":1,"name":"Ada"}]}"#);
+        assert_eq!(resp.headers["content-type"], "application/json");
+        assert_eq!(
+            mock_response(yaml, "GET", "/api/nope", "", "")
+                .unwrap()
+                .status,
+            404
+        );
+    }
+}
@@ -1,7 +1,6 @@
 use apicentric::simulator::{
     config::{
-        EndpointDefinition, EndpointKind, ResponseDefinition, ServerConfig, ServiceDefinition,
-        SimulatorConfig,
+        EndpointDefinition, ResponseDefinition, ServerConfig, ServiceDefinition, SimulatorConfig,
     },
     manager::ApiSimulatorManager,
 };
@@ -23,6 +22,8 @@ async fn global_simulator_manager() -> &'static ApiSimulatorManager {
             config.enabled = true;
             config.admin_port = Some(9999);
             config.services_dir = services_dir.path().to_path_buf();
+            // The shared manager outlives this function, so the directory is kept
+            config.db_path = services_dir.keep().join("apicentric.db");
 
             let manager = ApiSimulatorManager::new(config);
             manager.start().await.unwrap();
@@ -39,21 +40,16 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
         server: Some(ServerConfig {
             port,
             base_path: format!("/api/{}", name),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(vec![EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/test".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
             responses: {
                 let mut responses = HashMap::new();
                 responses.insert(
@@ -65,16 +61,25 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
                 responses
             },
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         }]),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -116,3 +121,100 @@ async fn test_admin_server_log_retrieval() {
         .unwrap();
     assert_eq!(log_entry.service, "test-service");
 }
+
+fn stateful_service(name: &str, port: u16) -> ServiceDefinition {
+    serde_yaml::from_str(&format!(
+        r#"
+name: {name}
+server:
+  port: {port}
+  base_path: /api/{name}
+fixtures:
+  items: [1, 2]
+endpoints:
+  - method: GET
+    path: /items
+    responses:
+      200:
+        content_type: application/json
+        body: "{{{{json fixtures.items}}}}"
+"#
+    ))
+    .unwrap()
+}
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+async fn mutate_state(manager: &ApiSimulatorManager, names: &[&str]) {
+    let registry = manager.service_registry().read().await;
+    for name in names {
+        let service = registry.get_service(name).unwrap().read().await;
+        service
+            .add_to_fixture_array("items", serde_json::json!(3))
+            .await
+            .unwrap();
+        service
+            .set_runtime_data("touched", serde_json::json!(true))
+            .await;
+    }
+}
+
+async fn assert_state_reset(manager: &ApiSimulatorManager, names: &[&str]) {
+    let registry = manager.service_registry().read().await;
+    for name in names {
+        let service = registry.get_service(name).unwrap().read().await;
+        assert_eq!(
+            service.get_fixtures().await["items"],
+            serde_json::json!([1, 2])
+        );
+        assert!(!service.has_runtime_data("touched").await);
+    }
+}
+
+#[tokio::test]
+async fn test_reset_all_reverts_every_service() {
+    let names = ["orders", "users"];
+    let services_dir = tempfile::tempdir().unwrap();
+    for name in names {
+        let path = services_dir.path().join(format!("{}.yaml", name));
+        serde_yaml::to_writer(
+            std::fs::File::create(path).unwrap(),
+            &stateful_service(name, free_port()),
+        )
+        .unwrap();
+    }
+    let admin_port = free_port();
+    let mut config = SimulatorConfig::default_config();
+    config.enabled = true;
+    config.admin_port = Some(admin_port);
+    config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+
+    mutate_state(&manager, &names).await;
+    manager.reset_all().await;
+    assert_state_reset(&manager, &names).await;
+
+    mutate_state(&manager, &names).await;
+    std::env::set_var("APICENTRIC_ADMIN_TOKEN", "test-token");
+    let res = reqwest::Client::new()
+        .post(format!(
+            "http://localhost:{}/apicentric-admin/reset",
+            admin_port
+        ))
+        .bearer_auth("test-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 204);
+    assert_state_reset(&manager, &names).await;
+
+    manager.stop().await.unwrap();
+}
@@ -3,8 +3,11 @@ use predicates::prelude::*;
 
 #[test]
 fn test_simulator_start_output() {
+    let dir = tempfile::tempdir().unwrap();
     let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
-    cmd.arg("--dry-run")
+    cmd.arg("--db-path")
+        .arg(dir.path().join("apicentric.db"))
+        .arg("--dry-run")
         .arg("simulator")
         .arg("start")
         .assert()
@@ -13,3 +16,228 @@ fn test_simulator_start_output() {
             "Dry run: Would start API simulator",
         ));
 }
+
+#[test]
+fn test_config_show_reflects_env_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_apicentric"))
+        .current_dir(dir.path())
+        .env("APICENTRIC_SIMULATOR_ENABLED", "true")
+        .env("APICENTRIC_ADMIN_PORT", "9123")
+        .arg("--config")
+        .arg(dir.path().join("missing.json"))
+        .arg("config")
+        .arg("show")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["simulator"]["enabled"], true);
+    assert_eq!(config["simulator"]["admin_port"], 9123);
+}
+
+#[test]
+fn test_simulator_start_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    let dir = tempfile::tempdir().unwrap();
+    let port = 18190;
+    let yaml = format!(
+        "name: piped\nserver:\n  port: {port}\n  base_path: /api\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{{\"pong\": true}}'\n"
+    );
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_apicentric"))
+        .current_dir(dir.path())
+        .env("APICENTRIC_SIMULATOR_ENABLED", "true")
+        .arg("--config")
+        .arg(dir.path().join("missing.json"))
+        .arg("--db-path")
+        .arg(dir.path().join("apicentric.db"))
+        .args(["simulator", "start", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(yaml.as_bytes())
+        .unwrap();
+
+    let url = format!("http://127.0.0.1:{port}/api/ping");
+    let deadline = Instant::now() + Duration::from_secs(15);
+    let body = loop {
+        if let Ok(resp) = reqwest::blocking::get(&url) {
+            break Some(resp.json::<serde_json::Value>().unwrap());
+        }
+        if Instant::now() > deadline {
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(body.expect("piped service never came up")["pong"], true);
+}
+
+#[test]
+fn test_simulator_start_json_summary() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let dir = tempfile::tempdir().unwrap();
+    let services_dir = dir.path().join("services");
+    std::fs::create_dir(&services_dir).unwrap();
+    for (name, port) in [("orders", 18191), ("users", 18192)] {
+        let yaml = format!(
+            "name: {name}\nserver:\n  port: {port}\n  base_path: /{name}\nendpoints:\n  - method: GET\n    path: /\n    responses:\n      200:\n        content_type: application/json\n        body: '[]'\n  - method: POST\n    path: /\n    responses:\n      201:\n        content_type: application/json\n        body: '{{}}'\n"
+        );
+        std::fs::write(services_dir.join(format!("{name}.yaml")), yaml).unwrap();
+    }
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_apicentric"))
+        .current_dir(dir.path())
+        .env("APICENTRIC_SIMULATOR_ENABLED", "true")
+        .arg("--config")
+        .arg(dir.path().join("missing.json"))
+        .arg("--db-path")
+        .arg(dir.path().join("apicentric.db"))
+        .args(["simulator", "start", "--json", "--services-dir"])
+        .arg(&services_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    child.kill().ok();
+    child.wait().ok();
+
+    let summary: serde_json::Value = serde_json::from_str(&line).unwrap();
+    let mut services = summary["services"].as_array().unwrap().clone();
+    services.sort_by_key(|s| s["name"].as_str().unwrap().to_string());
+    assert_eq!(services.len(), 2);
+    assert_eq!(services[0]["name"], "orders");
+    assert_eq!(services[0]["port"], 18191);
+    assert_eq!(services[0]["base_path"], "/orders");
+    assert_eq!(services[0]["endpoints"], 2);
+    assert_eq!(services[1]["name"], "users");
+    assert_eq!(services[1]["port"], 18192);
+}
+
+#[test]
+fn test_simulator_start_with_fixture_overrides() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let dir = tempfile::tempdir().unwrap();
+    let services_dir = dir.path().join("services");
+    std::fs::create_dir(&services_dir).unwrap();
+    let port = 18193;
+    std::fs::write(
+        services_dir.join("users.yaml"),
+        format!(
+            "name: users\nserver:\n  port: {port}\n  base_path: /api\nfixtures:\n  users: []\nendpoints:\n  - method: GET\n    path: /users\n    responses:\n      200:\n        content_type: application/json\n        body: '{{{{json fixtures.users}}}}'\n  - method: GET\n    path: /flag\n    responses:\n      200:\n        content_type: application/json\n        body: '{{\"enabled\": {{{{fixtures.feature_flag}}}}}}'\n"
+        ),
+    )
+    .unwrap();
+    let users_file = dir.path().join("users.json");
+    std::fs::write(&users_file, r#"[{"id": 7, "name": "Injected"}]"#).unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_apicentric"))
+        .current_dir(dir.path())
+        .env("APICENTRIC_SIMULATOR_ENABLED", "true")
+        .arg("--config")
+        .arg(dir.path().join("missing.json"))
+        .arg("--db-path")
+        .arg(dir.path().join("apicentric.db"))
+        .args(["simulator", "start", "--json", "--services-dir"])
+        .arg(&services_dir)
+        .arg("--set")
+        .arg(format!("users=@{}", users_file.display()))
+        .args(["--set", "feature_flag=true"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // The JSON summary is printed once all services are listening
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+
+    let base = format!("http://127.0.0.1:{port}/api");
+    let users =
+        reqwest::blocking::get(format!("{base}/users")).and_then(|r| r.json::<serde_json::Value>());
+    let flag =
+        reqwest::blocking::get(format!("{base}/flag")).and_then(|r| r.json::<serde_json::Value>());
+    child.kill().ok();
+    child.wait().ok();
+
+    let users = users.unwrap();
+    assert_eq!(users[0]["id"], 7);
+    assert_eq!(users[0]["name"], "Injected");
+    assert_eq!(flag.unwrap()["enabled"], true);
+}
+
+#[cfg(feature = "file-watch")]
+#[test]
+fn test_generate_query_watch_regenerates_on_change() {
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("shop.yaml");
+    let output = dir.path().join("shop.ts");
+    let service = |path: &str| {
+        format!(
+            "name: shop\nserver:\n  base_path: /api\nendpoints:\n  - method: GET\n    path: {path}\n    responses:\n      200:\n        content_type: application/json\n        body: '[]'\n"
+        )
+    };
+    std::fs::write(&input, service("/items")).unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_apicentric"))
+        .current_dir(dir.path())
+        .args(["simulator", "generate-query", "--watch", "--file"])
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let wait_for = |needle: &str| {
+        let deadline = Instant::now() + Duration::from_secs(15);
+        loop {
+            let generated = std::fs::read_to_string(&output).unwrap_or_default();
+            if generated.contains(needle) {
+                return true;
+            }
+            if Instant::now() > deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    };
+    let initial = wait_for("'/items'");
+    // Give the watcher time to register before editing the source
+    std::thread::sleep(Duration::from_millis(500));
+    std::fs::write(&input, service("/products")).unwrap();
+    let regenerated = wait_for("'/products'");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert!(initial, "hooks were never generated");
+    assert!(regenerated, "hooks were not regenerated after the edit");
+}
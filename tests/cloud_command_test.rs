@@ -13,8 +13,14 @@ fn test_cloud_command_starts_server() {
     assert!(build_status.success(), "Failed to build apicentric binary");
 
     // Run the cloud command in the background
-    let mut cmd = Command::new("target/debug/apicentric");
+    // `cloud` keeps its services and database in the working directory
+    let temp_dir = tempfile::tempdir().unwrap();
+    let binary = std::env::current_dir()
+        .unwrap()
+        .join("target/debug/apicentric");
+    let mut cmd = Command::new(binary);
     let mut child = cmd
+        .current_dir(temp_dir.path())
         .arg("cloud")
         .spawn()
         .expect("Failed to start apicentric cloud");
@@ -3,8 +3,8 @@
 use apicentric::cloud::CloudServer;
 use apicentric::simulator::{
     config::{
-        EndpointDefinition, EndpointKind, PortRange, ResponseDefinition, ServerConfig,
-        ServiceDefinition, SimulatorConfig,
+        EndpointDefinition, PortRange, ResponseDefinition, ServerConfig, ServiceDefinition,
+        SimulatorConfig,
     },
     manager::ApiSimulatorManager,
 };
@@ -19,22 +19,17 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
         server: Some(ServerConfig {
             port,
             base_path: "/api".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(vec![
             EndpointDefinition {
-                kind: EndpointKind::Http,
                 method: "GET".to_string(),
                 path: "/users".to_string(),
-                header_match: None,
-                description: None,
-                parameters: None,
-                request_body: None,
                 responses: {
                     let mut responses = HashMap::new();
                     responses.insert(
@@ -46,22 +41,25 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     );
                     responses
                 },
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             },
             EndpointDefinition {
-                kind: EndpointKind::Http,
                 method: "POST".to_string(),
                 path: "/users".to_string(),
-                header_match: None,
-                description: None,
-                parameters: None,
-                request_body: None,
                 responses: {
                     let mut responses = HashMap::new();
                     responses.insert(
@@ -73,17 +71,26 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
                             schema: None,
                             script: None,
                             headers: None,
+                            trailers: None,
+                            examples: None,
+                            record_delay_ms: None,
+                            pretty_json: None,
+                            sparse_fields: false,
+                            serve_times: None,
+                            exec: None,
                             side_effects: None,
+                            body_base64: None,
+                            body_file: None,
                         },
                     );
                     responses
                 },
-                scenarios: None,
-                stream: None,
+                ..Default::default()
             },
         ]),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -1,8 +1,16 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
+use std::path::Path;
 use tempfile::tempdir;
 
+/// The CLI with its database kept in `dir` instead of the working directory
+fn apicentric(dir: &Path) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    cmd.arg("--db-path").arg(dir.join("apicentric.db"));
+    cmd
+}
+
 #[test]
 fn test_dockerize_command() {
     let input_dir = tempdir().unwrap();
@@ -15,7 +23,7 @@ fn test_dockerize_command() {
     )
     .unwrap();
 
-    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    let mut cmd = apicentric(input_dir.path());
     cmd.arg("simulator")
         .arg("dockerize")
         .arg("--services")
@@ -2,8 +2,16 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
+use std::path::Path;
 use tempfile::tempdir;
 
+/// The CLI with its database kept in `dir` instead of the working directory
+fn apicentric(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    cmd.arg("--db-path").arg(dir.join("apicentric.db"));
+    cmd
+}
+
 #[test]
 fn test_validate_corrupt_yaml() {
     let dir = tempdir().unwrap();
@@ -22,7 +30,7 @@ endpoints:
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator")
         .arg("validate")
         .arg("--file")
@@ -52,7 +60,7 @@ endpoints: []
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator")
         .arg("validate")
         .arg("--file")
@@ -82,7 +90,7 @@ fn test_import_corrupt_csv() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator")
         .arg("import")
         .arg("--file")
@@ -99,7 +107,8 @@ fn test_import_corrupt_csv() {
 
 #[test]
 fn test_start_missing_service_file() {
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("new")
         .arg("myservice")
         .arg("--template")
@@ -112,7 +121,8 @@ fn test_start_missing_service_file() {
 
 #[test]
 fn test_cli_missing_subcommand() {
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     // No args
     cmd.assert()
         .failure()
@@ -121,7 +131,8 @@ fn test_cli_missing_subcommand() {
 
 #[test]
 fn test_cli_unknown_argument() {
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator").arg("status").arg("--what-is-this");
 
     cmd.assert().failure().stderr(
@@ -132,7 +143,8 @@ fn test_cli_unknown_argument() {
 
 #[test]
 fn test_dockerize_missing_files() {
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator")
         .arg("dockerize")
         .arg("--services")
@@ -148,7 +160,8 @@ fn test_dockerize_missing_files() {
 #[cfg(feature = "iot")]
 #[test]
 fn test_twin_run_missing_device() {
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("twin").arg("run").arg("non_existent_device");
 
     cmd.assert().failure().stderr(
@@ -165,7 +178,8 @@ fn test_contract_testing_quiet() {
     // But we can check help? No help doesn't show args nicely in manual parser.
     // We can run with invalid args and see if it parses --quiet.
 
-    let mut cmd = Command::cargo_bin("apicentric").unwrap();
+    let dir = tempdir().unwrap();
+    let mut cmd = apicentric(dir.path());
     cmd.arg("simulator")
         .arg("test")
         .arg("--path")
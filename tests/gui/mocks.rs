@@ -90,6 +90,7 @@ pub fn create_test_log_entry(method: &str, path: &str, status: u16) -> RequestLo
         path: path.to_string(),
         status,
         payload: None,
+        duration_ms: None,
     }
 }
 
@@ -105,6 +106,11 @@ pub fn create_test_manager() -> Arc<ApiSimulatorManager> {
         db_path: std::path::PathBuf::from(":memory:"),
         admin_port: None,
         global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
     };
     Arc::new(ApiSimulatorManager::new(config))
 }
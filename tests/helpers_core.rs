@@ -0,0 +1,152 @@
+use apicentric::simulator::template::helpers::core;
+use handlebars::Handlebars;
+use serde_json::json;
+
+fn fixtures() -> serde_json::Value {
+    json!({
+        "fixtures": {
+            "org": {
+                "members": [
+                    { "name": "Ada", "active": true, "profile": { "role": "admin" } },
+                    { "name": "Grace", "active": false, "profile": { "role": "dev" } },
+                    { "name": "Linus", "active": true, "profile": { "role": "dev" } }
+                ]
+            }
+        }
+    })
+}
+
+#[test]
+fn filter_and_find_reach_nested_arrays_and_fields() {
+    let mut h = Handlebars::new();
+    core::register_core_helpers(&mut h);
+    let data = fixtures();
+
+    let out = h
+        .render_template(
+            "{{filter \"fixtures.org.members\" \"profile.role\" \"dev\"}}",
+            &data,
+        )
+        .unwrap();
+    let names: Vec<String> = serde_json::from_str::<Vec<serde_json::Value>>(&out)
+        .unwrap()
+        .iter()
+        .map(|m| m["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["Grace", "Linus"]);
+
+    let out = h
+        .render_template("{{find fixtures.org.members \"active\" false}}", &data)
+        .unwrap();
+    let found: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(found["name"], "Grace");
+}
+
+#[test]
+fn count_helper_counts_matches() {
+    let mut h = Handlebars::new();
+    core::register_core_helpers(&mut h);
+    let data = fixtures();
+
+    let out = h
+        .render_template(
+            "{\"active_members\": {{count (filter fixtures.org.members \"active\" true)}}}",
+            &data,
+        )
+        .unwrap();
+    assert_eq!(out, "{\"active_members\": 2}");
+    let out = h
+        .render_template("{{count fixtures.org.members}}", &data)
+        .unwrap();
+    assert_eq!(out, "3");
+    let out = h
+        .render_template(
+            "{{count fixtures.org.members \"profile.role\" \"admin\"}}",
+            &data,
+        )
+        .unwrap();
+    assert_eq!(out, "1");
+}
+
+#[test]
+fn date_formats_current_time_with_optional_format() {
+    let mut h = Handlebars::new();
+    core::register_core_helpers(&mut h);
+
+    let out = h
+        .render_template("{{date \"%Y-%m-%d\"}}", &json!({}))
+        .unwrap();
+    assert_eq!(out, chrono::Utc::now().format("%Y-%m-%d").to_string());
+
+    let out = h.render_template("{{date}}", &json!({})).unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(&out).is_ok(),
+        "{}",
+        out
+    );
+
+    assert!(h.render_template("{{date \"%Q\"}}", &json!({})).is_err());
+}
+
+#[test]
+fn date_offset_produces_a_future_date() {
+    let mut h = Handlebars::new();
+    core::register_core_helpers(&mut h);
+
+    let out = h
+        .render_template("{{date \"%Y-%m-%d\" offset=86400}}", &json!({}))
+        .unwrap();
+    let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+    assert_eq!(out, tomorrow.format("%Y-%m-%d").to_string());
+
+    let out = h
+        .render_template("{{date offset=3600}}", &json!({}))
+        .unwrap();
+    let rendered = chrono::DateTime::parse_from_rfc3339(&out).unwrap();
+    assert!(rendered > chrono::Utc::now() + chrono::Duration::minutes(59));
+}
+
+#[test]
+fn jsonpath_returns_single_match_or_array_of_matches() {
+    let mut h = Handlebars::new();
+    core::register_core_helpers(&mut h);
+    let data = json!({
+        "fixtures": {
+            "data": {
+                "users": [
+                    { "email": "ada@example.com", "roles": ["admin"] },
+                    { "email": "grace@example.com", "roles": ["dev"] }
+                ]
+            }
+        }
+    });
+
+    let out = h
+        .render_template("{{jsonpath fixtures.data \"$.users[0].email\"}}", &data)
+        .unwrap();
+    assert_eq!(out, "ada@example.com");
+
+    let out = h
+        .render_template("{{jsonpath fixtures.data \"$.users[*].email\"}}", &data)
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&out).unwrap(),
+        json!(["ada@example.com", "grace@example.com"])
+    );
+
+    let out = h
+        .render_template(
+            "{{jsonpath fixtures.data \"$.users[?@.roles[0] == 'admin'].email\"}}",
+            &data,
+        )
+        .unwrap();
+    assert_eq!(out, r#"["ada@example.com"]"#);
+
+    let out = h
+        .render_template("{{jsonpath fixtures.data \"$.users[5].email\"}}", &data)
+        .unwrap();
+    assert_eq!(out, "");
+    assert!(h
+        .render_template("{{jsonpath fixtures.data \"users\"}}", &data)
+        .is_err());
+}
@@ -20,3 +20,22 @@ fn random_string_length() {
         .unwrap();
     assert_eq!(out.len(), 5);
 }
+
+#[test]
+fn uuid_generates_distinct_valid_ids() {
+    let mut h = Handlebars::new();
+    faker::register(&mut h);
+
+    let first = h.render_template("{{uuid}}", &json!({})).unwrap();
+    let second = h.render_template("{{uuid}}", &json!({})).unwrap();
+    assert_ne!(first, second);
+    assert_eq!(uuid::Uuid::parse_str(&first).unwrap().get_version_num(), 4);
+
+    let ordered = h.render_template("{{uuid \"v7\"}}", &json!({})).unwrap();
+    assert_eq!(
+        uuid::Uuid::parse_str(&ordered).unwrap().get_version_num(),
+        7
+    );
+
+    assert!(h.render_template("{{uuid \"v9\"}}", &json!({})).is_err());
+}
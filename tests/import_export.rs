@@ -2,8 +2,16 @@
 use assert_cmd::Command;
 use serde_json::Value;
 use std::fs;
+use std::path::Path;
 use tempfile::TempDir;
 
+/// The CLI with its database kept in `dir` instead of the working directory
+fn apicentric(dir: &Path) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    cmd.arg("--db-path").arg(dir.join("apicentric.db"));
+    cmd
+}
+
 #[test]
 fn test_openapi_round_trip() {
     let temp_dir = TempDir::new().unwrap();
@@ -31,7 +39,7 @@ fn test_openapi_round_trip() {
     fs::write(&input_path, petstore_content).unwrap();
 
     // 1. Import OpenAPI to Apicentric YAML using the unified import command
-    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    let mut cmd = apicentric(temp_dir.path());
     cmd.arg("simulator")
         .arg("import")
         .arg("--input")
@@ -47,7 +55,7 @@ fn test_openapi_round_trip() {
     );
 
     // 2. Export Apicentric YAML back to OpenAPI using the unified export command
-    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    let mut cmd = apicentric(temp_dir.path());
     cmd.arg("simulator")
         .arg("export")
         .arg("--input")
@@ -84,7 +92,8 @@ fn test_openapi_round_trip() {
 fn test_large_url_import_repro() {
     let url = "https://raw.githubusercontent.com/kubernetes/kubernetes/master/api/openapi-spec/swagger.json";
 
-    let mut cmd = Command::new(env!("CARGO_BIN_EXE_apicentric"));
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = apicentric(temp_dir.path());
     cmd.arg("simulator")
         .arg("import")
         .arg("--url")
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::ApiSimulatorManager;
+use tempfile::TempDir;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+type Fields = HashMap<String, String>;
+
+/// Layer keeping the fields of every `simulator` event
+#[derive(Clone, Default)]
+struct CaptureLayer {
+    events: Arc<Mutex<Vec<Fields>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "simulator" {
+            return;
+        }
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+}
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+async fn startup_events_carry_service_name_and_duration() {
+    let capture = CaptureLayer::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    let port = free_port();
+    std::fs::write(
+        services_dir.join("timed.yaml"),
+        format!(
+            "name: timed\nserver:\n  port: {port}\n  base_path: /timed\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{{}}'\n"
+        ),
+    )
+    .unwrap();
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+    manager.stop().await.unwrap();
+
+    let events = capture.events.lock().unwrap().clone();
+    let event = |message: &str| {
+        events
+            .iter()
+            .find(|fields| fields.get("message").map(String::as_str) == Some(message))
+            .unwrap_or_else(|| panic!("no '{}' event in {:?}", message, events))
+            .clone()
+    };
+    let duration = |fields: &Fields| -> f64 { fields["duration_ms"].parse().unwrap() };
+
+    let service = event("Service started");
+    assert_eq!(service["service"], "timed");
+    assert_eq!(service["port"], port.to_string());
+    assert!(duration(&service) >= 0.0);
+
+    let started = event("API Simulator started");
+    assert_eq!(started["service_count"], "1");
+    assert!(duration(&started) >= duration(&service));
+
+    let stopped = event("Service stopped");
+    assert_eq!(stopped["service"], "timed");
+    assert!(duration(&stopped) >= 0.0);
+    assert!(duration(&event("API Simulator stopped")) >= 0.0);
+}
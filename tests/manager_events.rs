@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::{ApiSimulatorManager, SimulatorEvent};
+use tempfile::TempDir;
+use tokio::sync::broadcast::Receiver;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Next event other than per-request traffic, which also carries debug entries
+async fn next_lifecycle_event(rx: &mut Receiver<SimulatorEvent>) -> SimulatorEvent {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            match rx.recv().await.unwrap() {
+                SimulatorEvent::RequestHandled(_) => continue,
+                event => return event,
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for simulator event")
+}
+
+#[tokio::test]
+async fn subscribe_events_reports_lifecycle_and_traffic() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    let port = free_port();
+    std::fs::write(
+        services_dir.join("alpha.yaml"),
+        format!(
+            "name: alpha\nserver:\n  port: {port}\n  base_path: /alpha\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{{}}'\n"
+        ),
+    )
+    .unwrap();
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    let mut events = manager.subscribe_events();
+    manager.start().await.unwrap();
+
+    match next_lifecycle_event(&mut events).await {
+        SimulatorEvent::ServiceStarted {
+            name,
+            port: started,
+        } => {
+            assert_eq!(name, "alpha");
+            assert_eq!(started, port);
+        }
+        other => panic!("expected ServiceStarted, got {:?}", other),
+    }
+
+    let url = format!("http://127.0.0.1:{}/alpha/ping", port);
+    reqwest::get(&url).await.unwrap();
+    let handled = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let SimulatorEvent::RequestHandled(entry) = events.recv().await.unwrap() {
+                if entry.method == "GET" {
+                    return entry;
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for RequestHandled");
+    assert_eq!(handled.service, "alpha");
+    assert_eq!(handled.status, 200);
+
+    manager.set_scenario(Some("outage".into())).await.unwrap();
+    assert!(matches!(
+        next_lifecycle_event(&mut events).await,
+        SimulatorEvent::ScenarioChanged(Some(ref s)) if s == "outage"
+    ));
+
+    manager.stop().await.unwrap();
+    assert!(matches!(
+        next_lifecycle_event(&mut events).await,
+        SimulatorEvent::ServiceStopped { ref name } if name == "alpha"
+    ));
+}
@@ -0,0 +1,162 @@
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::{ApiSimulatorManager, LogFilter};
+use tempfile::TempDir;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+async fn query_logs_filters_across_services() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+
+    let ports = [free_port(), free_port()];
+    for (name, port) in ["alpha", "beta"].iter().zip(ports) {
+        let content = format!(
+            "name: {name}\nserver:\n  port: {port}\n  base_path: /{name}\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{{}}'\n"
+        );
+        std::fs::write(services_dir.join(format!("{name}.yaml")), content).unwrap();
+    }
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+
+    let client = reqwest::Client::new();
+    for (name, port) in ["alpha", "beta"].iter().zip(ports) {
+        for path in ["ping", "missing"] {
+            let url = format!("http://127.0.0.1:{}/{}/{}", port, name, path);
+            client.get(&url).send().await.unwrap();
+        }
+    }
+
+    let ok = manager
+        .query_logs(&LogFilter {
+            method: Some("get".to_string()),
+            status: Some(200),
+            ..LogFilter::default()
+        })
+        .await
+        .unwrap();
+    let mut services: Vec<_> = ok.iter().map(|e| e.service.as_str()).collect();
+    services.sort();
+    assert_eq!(services, vec!["alpha", "beta"]);
+    assert!(ok.iter().all(|e| e.path.ends_with("/ping")));
+
+    let beta_missing = manager
+        .query_logs(&LogFilter {
+            service: Some("beta".to_string()),
+            route: Some("missing".to_string()),
+            ..LogFilter::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(beta_missing.len(), 1);
+    assert_eq!(beta_missing[0].status, 404);
+
+    manager.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn endpoint_stats_summarize_traffic_per_endpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+
+    let port = free_port();
+    std::fs::write(
+        services_dir.join("alpha.yaml"),
+        format!(
+            r#"name: alpha
+server:
+  port: {port}
+  base_path: /alpha
+endpoints:
+  - method: GET
+    path: /users/{{id}}
+    responses:
+      200:
+        content_type: application/json
+        body: '{{}}'
+  - method: GET
+    path: /boom
+    responses:
+      500:
+        content_type: application/json
+        body: '{{}}'
+"#
+        ),
+    )
+    .unwrap();
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+
+    let client = reqwest::Client::new();
+    for path in ["users/1", "users/2", "users/3", "boom", "missing"] {
+        let url = format!("http://127.0.0.1:{}/alpha/{}", port, path);
+        client.get(&url).send().await.unwrap();
+    }
+
+    let stats = manager.endpoint_stats().await.unwrap();
+    let summary: Vec<_> = stats
+        .iter()
+        .map(|s| {
+            let classes: Vec<_> = s
+                .status_classes
+                .iter()
+                .map(|(class, count)| (class.as_str(), *count))
+                .collect();
+            (s.method.as_str(), s.path.as_str(), s.count, classes)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("GET", "/alpha/missing", 1, vec![("4xx", 1)]),
+            ("GET", "/boom", 1, vec![("5xx", 1)]),
+            ("GET", "/users/{id}", 3, vec![("2xx", 3)]),
+        ]
+    );
+    assert!(stats.iter().all(|s| s.service == "alpha"));
+    assert!(stats.iter().all(|s| s.avg_latency_ms.is_some()));
+
+    manager.stop().await.unwrap();
+}
@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::ApiSimulatorManager;
+use tempfile::TempDir;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+async fn reload_answers_503_instead_of_refusing_connections() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    let port = free_port();
+    std::fs::write(
+        services_dir.join("alpha.yaml"),
+        format!(
+            "name: alpha\nserver:\n  port: {port}\n  base_path: /alpha\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{{}}'\n"
+        ),
+    )
+    .unwrap();
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: Some(2),
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/alpha/ping", port);
+    let done = Arc::new(AtomicBool::new(false));
+    let hammer = {
+        let done = Arc::clone(&done);
+        tokio::spawn(async move {
+            // Keep-alive connections exercise draining, fresh ones the socket handoff
+            let pooled = reqwest::Client::new();
+            let fresh = reqwest::Client::builder()
+                .pool_max_idle_per_host(0)
+                .build()
+                .unwrap();
+            let mut statuses = Vec::new();
+            while !done.load(Ordering::SeqCst) {
+                for client in [&pooled, &fresh] {
+                    let response = client
+                        .get(&url)
+                        .send()
+                        .await
+                        .expect("request failed during reload");
+                    if response.status() == 503 {
+                        assert_eq!(response.headers()["retry-after"], "2");
+                    }
+                    statuses.push(response.status().as_u16());
+                }
+            }
+            statuses
+        })
+    };
+
+    for _ in 0..3 {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        manager.reload_services().await.unwrap();
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    done.store(true, Ordering::SeqCst);
+
+    let statuses = hammer.await.unwrap();
+    assert!(statuses.iter().all(|s| *s == 200 || *s == 503));
+    assert_eq!(statuses.last(), Some(&200));
+
+    manager.stop().await.unwrap();
+}
@@ -0,0 +1,57 @@
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::ApiSimulatorManager;
+use tempfile::TempDir;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+async fn global_strict_templates_fail_undefined_references() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    let port = free_port();
+    std::fs::write(
+        services_dir.join("catalog.yaml"),
+        format!(
+            "name: catalog\nserver:\n  port: {port}\n  base_path: /catalog\nfixtures:\n  items: []\nendpoints:\n  - method: GET\n    path: /items\n    responses:\n      200:\n        content_type: application/json\n        body: '{{{{fixtures.doesnotexist}}}}'\n"
+        ),
+    )
+    .unwrap();
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir,
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: true,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    manager.start().await.unwrap();
+
+    let resp = reqwest::get(format!("http://127.0.0.1:{}/catalog/items", port))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 500);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("fixtures.doesnotexist"));
+
+    manager.stop().await.unwrap();
+}
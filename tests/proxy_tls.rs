@@ -0,0 +1,110 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+
+use apicentric::simulator::config::ServiceDefinition;
+use apicentric::simulator::service::ServiceInstance;
+use apicentric::storage::sqlite::SqliteStorage;
+use reqwest::StatusCode;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio::sync::broadcast;
+
+const CERT: &[u8] = include_bytes!("fixtures/tls/self_signed.crt");
+const KEY: &[u8] = include_bytes!("fixtures/tls/self_signed.key");
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// HTTPS server with a self-signed certificate answering every request with `secure`
+fn spawn_self_signed_upstream() -> u16 {
+    let cert = CertificateDer::from_pem_slice(CERT).unwrap();
+    let key = PrivateKeyDer::from_pem_slice(KEY).unwrap();
+    let config = Arc::new(
+        rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], key)
+            .unwrap(),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let conn = rustls::ServerConnection::new(Arc::clone(&config)).unwrap();
+            let mut tls = rustls::StreamOwned::new(conn, stream);
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match tls.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let _ = tls.write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\ncontent-length: 6\r\nconnection: close\r\n\r\nsecure",
+            );
+            tls.conn.send_close_notify();
+            let _ = tls.flush();
+        }
+    });
+    port
+}
+
+fn proxy_service(upstream: u16, accept_invalid_certs: bool) -> ServiceDefinition {
+    serde_yaml::from_str(&format!(
+        r#"
+name: gateway
+server:
+  base_path: /
+  proxy_base_url: https://127.0.0.1:{upstream}
+  proxy_tls:
+    accept_invalid_certs: {accept_invalid_certs}
+endpoints: []
+"#
+    ))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn proxies_to_self_signed_https_upstream_when_allowed() {
+    let upstream = spawn_self_signed_upstream();
+    let storage = Arc::new(SqliteStorage::init_db(":memory:").unwrap());
+    let client = reqwest::Client::new();
+
+    let port = free_port();
+    let (tx, _) = broadcast::channel(10);
+    let mut trusting = ServiceInstance::new(
+        proxy_service(upstream, true),
+        port,
+        storage.clone(),
+        tx.clone(),
+    )
+    .unwrap();
+    trusting.start().await.unwrap();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/hello", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.text().await.unwrap(), "secure");
+    trusting.stop().await.unwrap();
+
+    let port = free_port();
+    let mut strict =
+        ServiceInstance::new(proxy_service(upstream, false), port, storage, tx).unwrap();
+    strict.start().await.unwrap();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/hello", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+    strict.stop().await.unwrap();
+}
@@ -21,12 +21,12 @@ fn generated_hooks_compile_and_call_endpoints() {
     std::fs::create_dir_all(&stub_dir).unwrap();
     std::fs::write(
         stub_dir.join("index.d.ts"),
-        "export function useQuery(key: any, fn: any): any;\nexport function useMutation(fn: any): any;",
+        "export function useQuery(key: any, fn: any): any;\nexport function useMutation(fn: any, options?: any): any;\nexport function useQueryClient(): any;",
     )
     .unwrap();
     std::fs::write(
         stub_dir.join("index.js"),
-        "exports.useQuery=(k,f)=>f();exports.useMutation=(f)=>({mutate:f});",
+        "exports.useQuery=(k,f)=>f();exports.useMutation=(f)=>({mutate:f});exports.useQueryClient=()=>({});",
     )
     .unwrap();
 
@@ -3,8 +3,8 @@
 use apicentric::cloud::CloudServer;
 use apicentric::simulator::{
     config::{
-        EndpointDefinition, EndpointKind, PortRange, ResponseDefinition, ServerConfig,
-        ServiceDefinition, SimulatorConfig,
+        EndpointDefinition, PortRange, ResponseDefinition, ServerConfig, ServiceDefinition,
+        SimulatorConfig,
     },
     manager::ApiSimulatorManager,
 };
@@ -19,21 +19,16 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
         server: Some(ServerConfig {
             port,
             base_path: "/".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(vec![EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/users".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
             responses: {
                 let mut responses = HashMap::new();
                 responses.insert(
@@ -45,16 +40,25 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
                 responses
             },
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         }]),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -79,6 +83,7 @@ async fn test_recording_start_stop_status() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 9876,
         end: 9900,
@@ -200,6 +205,7 @@ async fn test_recording_generate_service() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 9880,
         end: 9900,
@@ -121,3 +121,65 @@ async fn test_recording_proxy_functionality() {
         "Should block private IP when env var is unset"
     );
 }
+
+#[tokio::test]
+async fn standalone_proxy_records_observed_endpoints_to_yaml() {
+    use apicentric::simulator::recording_proxy::ProxyRecorder;
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+    let target_url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::task::spawn(async move {
+                let service = service_fn(|_req: Request<hyper::body::Incoming>| async move {
+                    let mut resp = Response::new(Full::new(Bytes::from(r#"{"id":1}"#)));
+                    resp.headers_mut()
+                        .insert("content-type", "application/json".parse().unwrap());
+                    Ok::<_, Infallible>(resp)
+                });
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    });
+
+    let proxy_listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+    let proxy_port = proxy_listener.local_addr().unwrap().port();
+    drop(proxy_listener);
+
+    let output = tempfile::tempdir().unwrap();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let traffic = async move {
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let resp = reqwest::get(format!("http://127.0.0.1:{}/users", proxy_port))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.text().await.unwrap(), r#"{"id":1}"#);
+        stop.send(()).unwrap();
+    };
+    let recording = ProxyRecorder.record_until(
+        &target_url,
+        output.path().to_path_buf(),
+        proxy_port,
+        false,
+        async {
+            let _ = stopped.await;
+        },
+    );
+    let (path, ()) = tokio::join!(recording, traffic);
+
+    let yaml = std::fs::read_to_string(path.unwrap()).unwrap();
+    let service: apicentric::simulator::config::ServiceDefinition =
+        serde_yaml::from_str(&yaml).unwrap();
+    let endpoints = service.endpoints.unwrap();
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(endpoints[0].method, "GET");
+    assert_eq!(endpoints[0].path, "/users");
+    assert_eq!(endpoints[0].responses[&200].body, r#"{"id":1}"#);
+}
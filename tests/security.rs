@@ -19,7 +19,7 @@ async fn test_path_traversal_prevention() {
     std::fs::create_dir(&services_dir).unwrap();
 
     // Setup Simulator Manager with the temp directory
-    let config = SimulatorConfig::new(
+    let mut config = SimulatorConfig::new(
         false,
         services_dir.clone(),
         PortRange {
@@ -27,6 +27,7 @@ async fn test_path_traversal_prevention() {
             end: 9000,
         },
     );
+    config.db_path = temp_dir.path().join("apicentric.db");
     let manager = Arc::new(ApiSimulatorManager::new(config));
 
     // 1. Test Load Service Traversal
@@ -12,6 +12,7 @@ mod tests {
                 model: None,
             }),
             simulator: None,
+            retry: None,
         };
 
         config.redact_sensitive_fields();
@@ -32,6 +33,7 @@ mod tests {
                 model: None,
             }),
             simulator: None,
+            retry: None,
         };
 
         let mut new_config = ApicentricConfig {
@@ -42,6 +44,7 @@ mod tests {
                 model: Some("gpt-4".to_string()), // Changed field
             }),
             simulator: None,
+            retry: None,
         };
 
         new_config.merge_with_current(&current_config);
@@ -66,6 +69,7 @@ mod tests {
                 model: None,
             }),
             simulator: None,
+            retry: None,
         };
 
         let mut new_config = ApicentricConfig {
@@ -76,6 +80,7 @@ mod tests {
                 model: None,
             }),
             simulator: None,
+            retry: None,
         };
 
         new_config.merge_with_current(&current_config);
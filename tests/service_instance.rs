@@ -4,7 +4,7 @@ use reqwest::{Client, StatusCode};
 use tokio::sync::broadcast;
 
 use apicentric::errors::ApicentricResult;
-use apicentric::simulator::config::ServiceDefinition;
+use apicentric::simulator::config::{EndpointBuilder, ServiceDefinition};
 use apicentric::simulator::log::RequestLogEntry;
 use apicentric::simulator::service::ServiceInstance;
 use apicentric::storage::{LogStats, Storage};
@@ -18,6 +18,12 @@ impl Storage for NoopStorage {
     fn load_service(&self, _name: &str) -> ApicentricResult<Option<ServiceDefinition>> {
         Ok(None)
     }
+    fn save_scenario(&self, _service: &str, _scenario: Option<&str>) -> ApicentricResult<()> {
+        Ok(())
+    }
+    fn load_scenario(&self, _service: &str) -> ApicentricResult<Option<String>> {
+        Ok(None)
+    }
     fn append_log(&self, _entry: &RequestLogEntry) -> ApicentricResult<()> {
         Ok(())
     }
@@ -39,6 +45,14 @@ impl Storage for NoopStorage {
     }
 }
 
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
 fn test_service_definition() -> ServiceDefinition {
     let yaml = r#"
 name: test
@@ -101,12 +115,2323 @@ async fn cors_preflight_returns_no_content() {
 }
 
 #[tokio::test]
-async fn scenario_management_roundtrip() {
-    let def = test_service_definition();
+async fn plain_options_lists_allowed_methods() {
+    let mut def = test_service_definition();
+    def.endpoints.as_mut().unwrap().push(
+        serde_yaml::from_str(
+            r#"
+method: DELETE
+path: /hello
+responses:
+  204:
+    content_type: text/plain
+    body: ''
+"#,
+        )
+        .unwrap(),
+    );
     let (tx, _) = broadcast::channel(10);
     let storage = Arc::new(NoopStorage);
-    let service = ServiceInstance::new(def, 0, storage, tx).unwrap();
+    let port = 18090;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
 
-    service.set_scenario(Some("test".to_string())).await;
-    assert_eq!(service.get_scenario().await, Some("test".to_string()));
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/hello", port);
+    let plain = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(plain.status(), StatusCode::NO_CONTENT);
+    assert_eq!(plain.headers()["allow"], "DELETE, GET, OPTIONS");
+    assert!(plain.headers().get("access-control-allow-origin").is_none());
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("Origin", "http://example.com")
+        .header("Access-Control-Request-Method", "DELETE")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.status(), StatusCode::NO_CONTENT);
+    assert!(preflight
+        .headers()
+        .contains_key("access-control-allow-methods"));
+    assert!(preflight.headers().get("allow").is_none());
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn pretty_json_reindents_minified_fixture() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: pretty
+server:
+  base_path: /api
+  pretty_json: true
+fixtures:
+  user: {"id": 1, "roles": ["admin"]}
+endpoints:
+  - method: GET
+    path: /user
+    responses:
+      200:
+        content_type: application/json
+        body: "{{{json fixtures.user}}}"
+  - method: GET
+    path: /raw
+    responses:
+      200:
+        content_type: application/json
+        pretty_json: false
+        body: '{"id":1}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18091;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let body = client
+        .get(format!("http://127.0.0.1:{}/api/user", port))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        "{\n  \"id\": 1,\n  \"roles\": [\n    \"admin\"\n  ]\n}"
+    );
+
+    let raw = client
+        .get(format!("http://127.0.0.1:{}/api/raw", port))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(raw, r#"{"id":1}"#);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn configured_latency_delays_round_trip() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: laggy
+server:
+  base_path: /api
+behavior:
+  latency:
+    min_ms: 200
+    max_ms: 200
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18099;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let started = std::time::Instant::now();
+    let resp = Client::new()
+        .get(format!("http://127.0.0.1:{}/api/ping", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn mock_delay_header_delays_response_up_to_cap() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: slow
+server:
+  base_path: /api
+behavior:
+  max_mock_delay_ms: 300
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18092;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/ping", port);
+    let started = std::time::Instant::now();
+    let resp = client
+        .get(&url)
+        .header("X-Mock-Delay", "150")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+
+    let started = std::time::Instant::now();
+    client
+        .get(&url)
+        .header("X-Mock-Delay", "60000")
+        .send()
+        .await
+        .unwrap();
+    let elapsed = started.elapsed();
+    assert!(elapsed >= std::time::Duration::from_millis(300));
+    assert!(elapsed < std::time::Duration::from_secs(5));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn cors_none_emits_no_cors_headers() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: no-cors
+server:
+  base_path: /api
+  cors: none
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18093;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/ping", port);
+    let resp = client
+        .get(&url)
+        .header("Origin", "http://example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(!resp
+        .headers()
+        .keys()
+        .any(|k| k.as_str().starts_with("access-control-")));
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("Origin", "http://example.com")
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .unwrap();
+    assert!(!preflight
+        .headers()
+        .keys()
+        .any(|k| k.as_str().starts_with("access-control-")));
+
+    service.stop().await.unwrap();
+}
+
+fn signed_token(secret: &str, claims: serde_json::Value) -> String {
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn required_scopes_gate_endpoint_with_401_and_403() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: rbac
+server:
+  base_path: /api
+  auth:
+    jwt_secret: s3cret
+endpoints:
+  - method: DELETE
+    path: /users/{id}
+    requires: ["admin"]
+    responses:
+      200:
+        content_type: application/json
+        body: '{"deleted": true}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18094;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/users/7", port);
+    let exp = 4_102_444_800u64; // 2100-01-01
+    let admin = signed_token(
+        "s3cret",
+        serde_json::json!({ "sub": "ada", "exp": exp, "scope": "read admin" }),
+    );
+    let reader = signed_token(
+        "s3cret",
+        serde_json::json!({ "sub": "bob", "exp": exp, "scope": "read" }),
+    );
+
+    let resp = client
+        .delete(&url)
+        .bearer_auth(&admin)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .delete(&url)
+        .bearer_auth(&reader)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client.delete(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(resp.headers()["www-authenticate"], "Bearer");
+
+    let forged = signed_token(
+        "wrong",
+        serde_json::json!({ "sub": "eve", "exp": exp, "scope": "admin" }),
+    );
+    let resp = client
+        .delete(&url)
+        .bearer_auth(&forged)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(feature = "brotli")]
+#[tokio::test]
+async fn brotli_is_chosen_when_client_prefers_it() {
+    use std::io::Read;
+
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: compressed
+server:
+  base_path: /api
+  compression:
+    encodings: [gzip, br]
+endpoints:
+  - method: GET
+    path: /items
+    responses:
+      200:
+        content_type: application/json
+        body: '[{"id": 1, "name": "widget"}, {"id": 2, "name": "widget"}]'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18095;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let resp = Client::new()
+        .get(format!("http://127.0.0.1:{}/api/items", port))
+        .header("Accept-Encoding", "gzip;q=0.5, br")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-encoding"], "br");
+    let compressed = resp.bytes().await.unwrap();
+    let mut body = String::new();
+    brotli::Decompressor::new(compressed.as_ref(), 4096)
+        .read_to_string(&mut body)
+        .unwrap();
+    assert_eq!(
+        body,
+        r#"[{"id": 1, "name": "widget"}, {"id": 2, "name": "widget"}]"#
+    );
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn tenant_side_effects_stay_in_their_partition() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: tenants
+server:
+  base_path: /api
+  tenant_header: X-Tenant-Id
+fixtures:
+  items: []
+endpoints:
+  - method: POST
+    path: /items
+    responses:
+      201:
+        content_type: application/json
+        body: '{{json request.body}}'
+        side_effects:
+          - action: add_to_fixture
+            target: items
+            value: '{{json request.body}}'
+  - method: GET
+    path: /items
+    responses:
+      200:
+        content_type: application/json
+        body: '{{json fixtures.items}}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18096;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/items", port);
+    let resp = client
+        .post(&url)
+        .header("X-Tenant-Id", "a")
+        .json(&serde_json::json!({ "id": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let items = |tenant: Option<&'static str>| {
+        let mut req = client.get(&url);
+        if let Some(tenant) = tenant {
+            req = req.header("X-Tenant-Id", tenant);
+        }
+        async move { req.send().await.unwrap().text().await.unwrap() }
+    };
+    assert_eq!(items(Some("a")).await, r#"[{"id":1}]"#);
+    assert_eq!(items(Some("b")).await, "[]");
+    assert_eq!(items(None).await, "[]");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn internal_logs_endpoint_requires_token_or_can_be_disabled() {
+    let service_yaml = |extra: &str| {
+        format!(
+            r#"
+name: internal
+server:
+  base_path: /api
+{extra}
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#
+        )
+    };
+    let client = Client::new();
+
+    let def: ServiceDefinition =
+        serde_yaml::from_str(&service_yaml("  auth:\n    jwt_secret: s3cret")).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let mut secured = ServiceInstance::new(def, 18097, Arc::new(NoopStorage), tx).unwrap();
+    secured.start().await.unwrap();
+    let url = "http://127.0.0.1:18097/api/__apicentric/logs";
+    let resp = client.get(url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let token = signed_token(
+        "s3cret",
+        serde_json::json!({ "sub": "ops", "exp": 4_102_444_800u64 }),
+    );
+    let resp = client.get(url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    secured.stop().await.unwrap();
+
+    let def: ServiceDefinition =
+        serde_yaml::from_str(&service_yaml("  internal_endpoints: false")).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let mut disabled = ServiceInstance::new(def, 18098, Arc::new(NoopStorage), tx).unwrap();
+    disabled.start().await.unwrap();
+    let resp = client
+        .get("http://127.0.0.1:18098/api/__apicentric/logs")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    disabled.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn expect_100_continue_upload_completes() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: uploads
+server:
+  base_path: /api
+endpoints:
+  - method: POST
+    path: /upload
+    responses:
+      201:
+        content_type: application/json
+        body: '{"received": {{json request.body.size}}}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18100;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let body = format!(r#"{{"size": 65536, "data": "{}"}}"#, "x".repeat(65536));
+    let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .unwrap();
+    stream
+        .write_all(
+            format!(
+                "POST /api/upload HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nExpect: 100-continue\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .as_bytes(),
+        )
+        .await
+        .unwrap();
+
+    // The client holds the body back until the server asks for it
+    let mut interim = [0u8; 64];
+    let n = tokio::time::timeout(std::time::Duration::from_secs(5), stream.read(&mut interim))
+        .await
+        .expect("no 100 Continue before timeout")
+        .unwrap();
+    assert!(String::from_utf8_lossy(&interim[..n]).starts_with("HTTP/1.1 100 Continue"));
+
+    stream.write_all(body.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        stream.read_to_string(&mut response),
+    )
+    .await
+    .expect("upload did not complete")
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 201"));
+    assert!(response.ends_with(r#"{"received": 65536}"#));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn error_simulation_at_full_rate_fails_every_request() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: chaos
+server:
+  base_path: /api
+behavior:
+  error_simulation:
+    enabled: true
+    rate: 1.0
+    status_codes: [503]
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage =
+        Arc::new(apicentric::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+    let port = 18101;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    for _ in 0..5 {
+        let resp = client
+            .get(format!("http://127.0.0.1:{}/api/ping", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+    let logs = service.get_logs(20).await;
+    assert_eq!(logs.iter().filter(|l| l.status == 503).count(), 5);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn builder_service_serves_fixture_backed_endpoint() {
+    let def = ServiceDefinition::builder("users")
+        .base_path("/api")
+        .fixture("users", serde_json::json!([{ "id": 1, "name": "Ada" }]))
+        .endpoint(EndpointBuilder::get("/users").json_response(200, "{{json fixtures.users}}"))
+        .endpoint(EndpointBuilder::post("/users").json_response(201, "{{json request.body}}"))
+        .build();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18102;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/users", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()["content-type"].to_str().unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!([{ "id": 1, "name": "Ada" }]));
+
+    let resp = client
+        .post(format!("http://127.0.0.1:{}/api/users", port))
+        .json(&serde_json::json!({ "name": "Grace" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["name"], "Grace");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn rate_limit_answers_429_with_retry_after() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: limited
+server:
+  base_path: /api
+behavior:
+  rate_limiting:
+    enabled: true
+    requests_per_minute: 3
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage =
+        Arc::new(apicentric::storage::sqlite::SqliteStorage::init_db(":memory:").unwrap());
+    let port = 18103;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    // Separate clients open separate connections that must share one counter
+    let mut statuses = Vec::new();
+    for _ in 0..4 {
+        let resp = Client::new()
+            .get(format!("http://127.0.0.1:{}/api/ping", port))
+            .send()
+            .await
+            .unwrap();
+        statuses.push(resp.status());
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after: u64 = resp.headers()["retry-after"]
+                .to_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((1..=60).contains(&retry_after));
+        }
+    }
+    assert_eq!(
+        statuses,
+        vec![
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS
+        ]
+    );
+    let logs = service.get_logs(20).await;
+    assert_eq!(logs.iter().filter(|l| l.status == 429).count(), 1);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn state_machine_transitions_drive_responses() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: shop
+server:
+  base_path: /api
+state_machine:
+  initial: cart
+  states: [cart, checkout, paid]
+endpoints:
+  - method: GET
+    path: /order
+    responses:
+      200:
+        content_type: application/json
+        body: '{"state": "{{state.current}}"}'
+  - method: POST
+    path: /checkout
+    transition: { from: [cart], to: checkout }
+    responses:
+      200:
+        content_type: application/json
+        body: '{"state": "{{state.current}}"}'
+  - method: POST
+    path: /pay
+    transition: { from: [checkout], to: paid }
+    responses:
+      200:
+        content_type: application/json
+        body: '{"state": "{{state.current}}"}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18104;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = |path: &str| format!("http://127.0.0.1:{}/api{}", port, path);
+    let order_state = || async {
+        let body: serde_json::Value = client
+            .get(url("/order"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        body["state"].as_str().unwrap().to_string()
+    };
+
+    assert_eq!(order_state().await, "cart");
+    let resp = client.post(url("/pay")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(order_state().await, "cart");
+
+    let resp = client.post(url("/checkout")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["state"], "checkout");
+    assert_eq!(order_state().await, "checkout");
+    let resp = client.post(url("/checkout")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = client.post(url("/pay")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(order_state().await, "paid");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn cors_expose_headers_and_max_age_follow_config() {
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: exposed
+server:
+  base_path: /api
+  cors:
+    enabled: true
+    origins: ["*"]
+    expose_headers: [X-Request-Id, X-Total-Count]
+    max_age_secs: 600
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: text/plain
+        headers:
+          X-Request-Id: abc
+        body: pong
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18105;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/ping", port);
+    let resp = client
+        .get(&url)
+        .header("Origin", "http://example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()["access-control-expose-headers"],
+        "X-Request-Id, X-Total-Count"
+    );
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("Origin", "http://example.com")
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.headers()["access-control-max-age"], "600");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn scenario_management_roundtrip() {
+    let def = test_service_definition();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let service = ServiceInstance::new(def, 0, storage, tx).unwrap();
+
+    service.set_scenario(Some("test".to_string())).await;
+    assert_eq!(service.get_scenario().await, Some("test".to_string()));
+}
+
+#[tokio::test]
+async fn partials_are_shared_across_endpoints() {
+    let yaml = r#"
+name: partials
+server:
+  base_path: /api
+partials:
+  error_envelope: '{"error": {"code": "{{params.code}}", "path": "{{request.path}}"}}'
+endpoints:
+  - method: GET
+    path: /orders/{code}
+    responses:
+      404:
+        content_type: application/json
+        body: '{{> error_envelope}}'
+  - method: GET
+    path: /users/{code}
+    responses:
+      404:
+        content_type: application/json
+        body: '{{> error_envelope}}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18082;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    for (path, code) in [("orders", "missing-order"), ("users", "missing-user")] {
+        let url = format!("http://127.0.0.1:{}/api/{}/{}", port, path, code);
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body["error"]["code"], code);
+        assert_eq!(body["error"]["path"], format!("/{}/{}", path, code));
+    }
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn declared_trailers_are_sent_after_body() {
+    use http_body_util::{BodyExt, Empty};
+    use hyper::body::Bytes;
+    use hyper_util::rt::TokioIo;
+
+    let yaml = r#"
+name: trailers
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /stream/{id}
+    responses:
+      200:
+        content_type: application/json
+        body: '{"id": "{{params.id}}"}'
+        trailers:
+          grpc-status: "0"
+          x-stream-id: "{{params.id}}"
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18083;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .unwrap();
+    let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .unwrap();
+    tokio::spawn(conn);
+
+    let req = hyper::Request::get("/api/stream/42")
+        .header(hyper::header::HOST, "127.0.0.1")
+        .header(hyper::header::TE, "trailers")
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    let resp = sender.send_request(req).await.unwrap();
+    assert_eq!(resp.status(), hyper::StatusCode::OK);
+
+    let collected = resp.into_body().collect().await.unwrap();
+    let trailers = collected.trailers().cloned().expect("trailers present");
+    assert_eq!(trailers["grpc-status"], "0");
+    assert_eq!(trailers["x-stream-id"], "42");
+    let body: serde_json::Value = serde_json::from_slice(&collected.to_bytes()).unwrap();
+    assert_eq!(body["id"], "42");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn example_query_param_selects_named_example() {
+    let yaml = r#"
+name: examples
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[{"id": 1}]'
+        examples:
+          empty: '[]'
+      503:
+        content_type: application/json
+        body: '{"error": "unavailable"}'
+        examples:
+          maintenance: '{"error": "maintenance"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18084;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let base = format!("http://127.0.0.1:{}/api/users", port);
+
+    let resp = client
+        .get(format!("{}?__example=empty", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.text().await.unwrap(), "[]");
+
+    let resp = client
+        .get(format!("{}?__example=maintenance", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(resp.text().await.unwrap(), r#"{"error": "maintenance"}"#);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn ndjson_response_streams_one_fixture_per_line() {
+    let yaml = r#"
+name: ndjson
+server:
+  base_path: /api
+fixtures:
+  events:
+    - id: 1
+      kind: created
+    - id: 2
+      kind: updated
+    - id: 3
+      kind: deleted
+endpoints:
+  - method: GET
+    path: /events
+    responses:
+      200:
+        content_type: application/x-ndjson
+        body: '{{json fixtures.events}}'
+        record_delay_ms: 10
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18085;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/api/events", port);
+    let resp = Client::new().get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/x-ndjson");
+    let body = resp.text().await.unwrap();
+
+    let lines: Vec<serde_json::Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    for (index, (line, kind)) in lines
+        .iter()
+        .zip(["created", "updated", "deleted"])
+        .enumerate()
+    {
+        assert_eq!(line["id"], index as u64 + 1);
+        assert_eq!(line["kind"], kind);
+    }
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn redact_patterns_mask_logged_request_bodies() {
+    let yaml = r#"
+name: payments
+server:
+  base_path: /api
+behavior:
+  redact: ['\d{16}']
+endpoints:
+  - method: POST
+    path: /payments
+    responses:
+      201:
+        content_type: application/json
+        body: '{"status": "accepted"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, mut rx) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18086;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/api/payments", port);
+    let resp = Client::new()
+        .post(&url)
+        .header("content-type", "application/json")
+        .body(r#"{"card": "4111111111111111", "amount": 10}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let mut entries = Vec::new();
+    while let Ok(entry) = rx.try_recv() {
+        entries.push(entry);
+    }
+    let logged = entries
+        .iter()
+        .find(|e| e.endpoint == Some(0))
+        .expect("matched request is logged");
+    assert_eq!(
+        logged.payload.as_deref(),
+        Some(r#"{"card": "[REDACTED]", "amount": 10}"#)
+    );
+    assert!(entries
+        .iter()
+        .all(|e| !format!("{:?}", e).contains("4111111111111111")));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn stale_if_match_returns_precondition_failed() {
+    let yaml = r#"
+name: inventory
+server:
+  base_path: /api
+fixtures:
+  item:
+    name: widget
+endpoints:
+  - method: GET
+    path: /item
+    etag: '{{json fixtures.item}}'
+    responses:
+      200:
+        content_type: application/json
+        body: '{{json fixtures.item}}'
+  - method: PUT
+    path: /item
+    etag: '{{json fixtures.item}}'
+    responses:
+      200:
+        content_type: application/json
+        body: '{{json request.body}}'
+        side_effects:
+          - action: update_fixture
+            target: item
+            value: '{{json request.body}}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18087;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/api/item", port);
+    let client = Client::new();
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let etag = resp.headers()["etag"].to_str().unwrap().to_string();
+
+    let resp = client
+        .put(&url)
+        .header("content-type", "application/json")
+        .header("if-match", &etag)
+        .body(r#"{"name": "gadget"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let new_etag = resp.headers()["etag"].to_str().unwrap().to_string();
+    assert_ne!(new_etag, etag);
+
+    let resp = client
+        .put(&url)
+        .header("content-type", "application/json")
+        .header("if-match", &etag)
+        .body(r#"{"name": "stale"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(resp.headers()["etag"], new_etag.as_str());
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.text().await.unwrap(), r#"{"name":"gadget"}"#);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn typed_path_parameter_rejects_mismatched_segment() {
+    let yaml = r#"
+name: typed
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users/{id}
+    parameters:
+      - name: id
+        in: path
+        type: integer
+        required: true
+    responses:
+      200:
+        content_type: application/json
+        body: '{"id": {{params.id}}}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18088;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/users/42", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.text().await.unwrap(), r#"{"id": 42}"#);
+
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/users/abc", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("'id'"));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn accept_language_selects_localized_example() {
+    let yaml = r#"
+name: greetings
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /greeting
+    responses:
+      200:
+        content_type: text/plain
+        body: Hello
+        examples:
+          en: Hello
+          es: Hola
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18089;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/api/greeting", port);
+    let client = Client::new();
+    let resp = client
+        .get(&url)
+        .header("accept-language", "es")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-language"], "es");
+    assert_eq!(resp.text().await.unwrap(), "Hola");
+
+    let resp = client
+        .get(&url)
+        .header("accept-language", "fr-CA, fr;q=0.9")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("content-language").is_none());
+    assert_eq!(resp.text().await.unwrap(), "Hello");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn base64_body_is_sent_as_raw_bytes() {
+    let yaml = r#"
+name: assets
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /logo.png
+    responses:
+      200:
+        content_type: image/png
+        body_base64: iVBORw0KGgoA/3t7
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, mut rx) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18106;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let resp = Client::new()
+        .get(format!("http://127.0.0.1:{}/api/logo.png", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/png");
+    assert_eq!(
+        resp.bytes().await.unwrap().as_ref(),
+        &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0xff, b'{', b'{']
+    );
+
+    let mut entries = Vec::new();
+    while let Ok(entry) = rx.try_recv() {
+        entries.push(entry);
+    }
+    let logged = entries
+        .iter()
+        .find(|e| e.endpoint == Some(0))
+        .expect("matched request is logged");
+    assert_eq!(logged.status, 200);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn behavior_compression_gzips_bodies_over_threshold() {
+    use std::io::Read;
+
+    let large = format!(r#"{{"data": "{}"}}"#, "x".repeat(256));
+    let yaml = format!(
+        r#"
+name: bandwidth
+server:
+  base_path: /api
+behavior:
+  compression:
+    encodings: [gzip]
+    min_size: 64
+endpoints:
+  - method: GET
+    path: /large
+    responses:
+      200:
+        content_type: application/json
+        body: '{large}'
+  - method: GET
+    path: /small
+    responses:
+      200:
+        content_type: application/json
+        body: '{{"ok": true}}'
+"#
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18107;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/large", port))
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-encoding"], "gzip");
+    let compressed = resp.bytes().await.unwrap();
+    assert!(compressed.len() < large.len());
+    let mut body = String::new();
+    flate2::read::GzDecoder::new(compressed.as_ref())
+        .read_to_string(&mut body)
+        .unwrap();
+    assert_eq!(body, large);
+
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/small", port))
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("content-encoding").is_none());
+    assert_eq!(resp.text().await.unwrap(), r#"{"ok": true}"#);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn sparse_fieldsets_prune_response_keys() {
+    let yaml = r#"
+name: directory
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        sparse_fields: true
+        body: '[{"id": 1, "name": "Ana", "email": "ana@example.com"}, {"id": 2, "name": "Bo", "email": "bo@example.com"}]'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18108;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/users", port);
+    let body: serde_json::Value = client
+        .get(&url)
+        .query(&[("fields[user]", "id,name")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!([{"id": 1, "name": "Ana"}, {"id": 2, "name": "Bo"}])
+    );
+
+    let body: serde_json::Value = client.get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body[0]["email"], "ana@example.com");
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn cookie_condition_selects_scenario() {
+    let yaml = r#"
+name: accounts
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /me
+    responses:
+      401:
+        content_type: application/json
+        body: '{"error": "login required"}'
+    scenarios:
+      - conditions:
+          cookies:
+            session: abc123
+        response:
+          status: 200
+          content_type: application/json
+          body: '{"session": "{{request.cookies.session}}"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18109;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/me", port);
+    let resp = client
+        .get(&url)
+        .header("cookie", "theme=dark; session=abc123")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.text().await.unwrap(), r#"{"session": "abc123"}"#);
+
+    let resp = client
+        .get(&url)
+        .header("cookie", "session=other")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn header_lists_emit_repeated_set_cookie() {
+    let yaml = r#"
+name: login
+server:
+  base_path: /api
+endpoints:
+  - method: POST
+    path: /login
+    responses:
+      204:
+        content_type: application/json
+        body: ''
+        headers:
+          Set-Cookie:
+            - session=abc123; HttpOnly
+            - theme=dark
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18110;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let resp = Client::new()
+        .post(format!("http://127.0.0.1:{}/api/login", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let cookies: Vec<&str> = resp
+        .headers()
+        .get_all("set-cookie")
+        .iter()
+        .map(|v| v.to_str().unwrap())
+        .collect();
+    assert_eq!(cookies, ["session=abc123; HttpOnly", "theme=dark"]);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn request_body_is_validated_against_model() {
+    let yaml = r#"
+name: signup
+server:
+  base_path: /api
+models:
+  NewUser:
+    type: object
+    properties:
+      name: { type: string }
+      email: { type: string }
+    required: [name, email]
+endpoints:
+  - method: POST
+    path: /users
+    request_body:
+      schema: NewUser
+      content_type: application/json
+      validate: true
+    responses:
+      201:
+        content_type: application/json
+        body: '{"name": "{{request.body.name}}"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18111;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/users", port);
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "name": "Ana", "email": "ana@example.com" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(resp.text().await.unwrap(), r#"{"name": "Ana"}"#);
+
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "name": "Ana" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("NewUser"));
+    let details = body["details"].as_array().unwrap();
+    assert_eq!(details.len(), 1);
+    assert!(details[0].as_str().unwrap().contains("\"email\""));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn trailing_slash_ignore_mode_matches_both_forms() {
+    let yaml = r#"
+name: users
+server:
+  base_path: /api
+  trailing_slash:
+    mode: ignore
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[]'
+  - method: GET
+    path: /teams/
+    responses:
+      200:
+        content_type: application/json
+        body: '[]'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18112;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    for path in ["/api/users", "/api/users/", "/api/teams", "/api/teams/"] {
+        let resp = client
+            .get(format!("http://127.0.0.1:{}{}", port, path))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{}", path);
+        assert_eq!(resp.text().await.unwrap(), "[]");
+    }
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn gzip_body_file_is_served_compressed_or_decompressed() {
+    use std::io::Write;
+
+    let users = r#"[{"id": 1, "name": "Ana"}, {"id": 2, "name": "Bo"}]"#;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("users.json.gz");
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(users.as_bytes()).unwrap();
+    let stored = encoder.finish().unwrap();
+    std::fs::write(&path, &stored).unwrap();
+
+    let yaml = format!(
+        r#"
+name: users
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body_file: {}
+"#,
+        path.display()
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18113;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/users", port);
+    let resp = client
+        .get(&url)
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-encoding"], "gzip");
+    assert_eq!(resp.bytes().await.unwrap().as_ref(), stored.as_slice());
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("content-encoding").is_none());
+    assert_eq!(resp.text().await.unwrap(), users);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn serve_times_response_reverts_after_its_budget() {
+    let yaml = r#"
+name: status
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /health
+    responses:
+      200:
+        content_type: application/json
+        body: '{"status": "ok"}'
+      503:
+        content_type: application/json
+        body: '{"status": "maintenance"}'
+        serve_times: 2
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18114;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let mut statuses = Vec::new();
+    for _ in 0..3 {
+        let resp = client
+            .get(format!("http://127.0.0.1:{}/api/health", port))
+            .send()
+            .await
+            .unwrap();
+        statuses.push(resp.status());
+    }
+    assert_eq!(
+        statuses,
+        [
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::OK
+        ]
+    );
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn responses_branch_on_the_parsed_request_content_type() {
+    let yaml = r#"
+name: orders
+server:
+  base_path: /api
+endpoints:
+  - method: POST
+    path: /orders
+    responses:
+      201:
+        content_type: application/json
+        condition: '{{eq request.content_type "application/json"}}'
+        body: '{"accepted": "{{request.body.item}}", "as": "json"}'
+      202:
+        content_type: application/json
+        condition: '{{eq request.content_type "application/x-www-form-urlencoded"}}'
+        body: '{"accepted": "{{request.body.item}}", "as": "form"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18115;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/orders", port);
+
+    let resp = client
+        .post(&url)
+        .header("content-type", "application/json; charset=utf-8")
+        .body(r#"{"item": "book"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"accepted": "book", "as": "json"}));
+
+    let resp = client
+        .post(&url)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("item=book")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"accepted": "book", "as": "form"}));
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(feature = "scripting")]
+#[tokio::test]
+async fn script_returning_a_status_answers_directly() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("brew.rhai");
+    std::fs::write(
+        &script,
+        r#"
+if ctx.request.headers["x-brew"] == "coffee" {
+    #{ status: 418, headers: #{ "x-teapot": "yes" }, body: "I'm a teapot" }
+} else {
+    #{ greeting: "hello" }
+}
+"#,
+    )
+    .unwrap();
+
+    let yaml = format!(
+        r#"
+name: kitchen
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /brew
+    responses:
+      200:
+        content_type: application/json
+        body: '{{}}'
+        script: {}
+"#,
+        script.display()
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18116;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = format!("http://127.0.0.1:{}/api/brew", port);
+
+    let resp = client
+        .get(&url)
+        .header("x-brew", "coffee")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(resp.headers()["x-teapot"], "yes");
+    assert_eq!(resp.text().await.unwrap(), "I'm a teapot");
+
+    // Objects without a status still replace the body and land in runtime data
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"greeting": "hello"}));
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(feature = "scripting")]
+#[tokio::test]
+async fn runaway_script_times_out_and_falls_back_to_the_template() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("spin.rhai");
+    std::fs::write(&script, "while true {}").unwrap();
+
+    let yaml = format!(
+        r#"
+name: spinner
+server:
+  base_path: /api
+  script_timeout_ms: 200
+endpoints:
+  - method: GET
+    path: /spin
+    responses:
+      200:
+        content_type: application/json
+        body: '{{"fallback": true}}'
+        script: {}
+"#,
+        script.display()
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18117;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let resp = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        Client::new()
+            .get(format!("http://127.0.0.1:{}/api/spin", port))
+            .send(),
+    )
+    .await
+    .expect("script was not aborted")
+    .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"fallback": true}));
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(feature = "scripting")]
+#[tokio::test]
+async fn script_fetches_and_reshapes_an_upstream_response() {
+    let upstream_port = 18120;
+    let upstream: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: upstream
+server:
+  base_path: /up
+endpoints:
+  - method: GET
+    path: /users/1
+    responses:
+      200:
+        content_type: application/json
+        body: '{"id": 1, "first": "Ada", "last": "Lovelace"}'
+"#,
+    )
+    .unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let mut upstream =
+        ServiceInstance::new(upstream, upstream_port, Arc::new(NoopStorage), tx).unwrap();
+    upstream.start().await.unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("compose.rhai");
+    std::fs::write(
+        &script,
+        format!(
+            r#"
+let user = fetch("http://127.0.0.1:{upstream_port}/up/users/1");
+#{{ status: 200, body: #{{ name: user.body.first + " " + user.body.last, upstream: user.status }} }}
+"#
+        ),
+    )
+    .unwrap();
+
+    let yaml = format!(
+        r#"
+name: composer
+server:
+  base_path: /api
+  allow_script_network: true
+endpoints:
+  - method: GET
+    path: /profile
+    responses:
+      200:
+        content_type: application/json
+        body: '{{"fallback": true}}'
+        script: {}
+"#,
+        script.display()
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let port = 18119;
+    let mut service = ServiceInstance::new(def, port, Arc::new(NoopStorage), tx).unwrap();
+    service.start().await.unwrap();
+
+    let resp = Client::new()
+        .get(format!("http://127.0.0.1:{}/api/profile", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({"name": "Ada Lovelace", "upstream": 200})
+    );
+
+    service.stop().await.unwrap();
+    upstream.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn mutual_tls_exposes_the_client_cn_and_rejects_clients_without_a_cert() {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls/mtls");
+    let yaml = format!(
+        r#"
+name: secure
+server:
+  base_path: /api
+  tls:
+    cert: {fixtures}/server.crt
+    key: {fixtures}/server.key
+    client_ca: {fixtures}/ca.crt
+    require_client_cert: true
+endpoints:
+  - method: GET
+    path: /whoami
+    responses:
+      200:
+        content_type: application/json
+        body: '{{"cn": "{{{{request.client_cert.cn}}}}"}}'
+"#
+    );
+    let def: ServiceDefinition = serde_yaml::from_str(&yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18118;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let ca = reqwest::Certificate::from_pem(&std::fs::read(format!("{fixtures}/ca.crt")).unwrap())
+        .unwrap();
+    let url = format!("https://127.0.0.1:{}/api/whoami", port);
+
+    let mut identity = std::fs::read(format!("{fixtures}/client.crt")).unwrap();
+    identity.extend(std::fs::read(format!("{fixtures}/client.key")).unwrap());
+    let with_cert = Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(ca.clone())
+        .identity(reqwest::Identity::from_pem(&identity).unwrap())
+        .build()
+        .unwrap();
+    let resp = with_cert.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"cn": "test-client"}));
+
+    let without_cert = Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(ca)
+        .build()
+        .unwrap();
+    let resp = without_cert.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn sse_endpoint_streams_periodic_events() {
+    let yaml = r#"
+name: ticker
+server:
+  base_path: /api
+fixtures:
+  symbol: ACME
+endpoints:
+  - kind: sse
+    method: GET
+    path: /ticks
+    stream:
+      initial:
+        - '{"type": "hello"}'
+      periodic:
+        interval_ms: 20
+        message: '{"symbol": "{{fixtures.symbol}}"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18121;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("http://127.0.0.1:{}/api/ticks", port);
+    let mut resp = Client::new().get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "text/event-stream");
+
+    let mut received = String::new();
+    while received.matches("\n\n").count() < 3 {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(2), resp.chunk())
+            .await
+            .expect("event stream stalled")
+            .unwrap()
+            .expect("event stream ended early");
+        received.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let events: Vec<&str> = received.split_terminator("\n\n").collect();
+    assert_eq!(events[0], r#"data: {"type": "hello"}"#);
+    assert_eq!(events[1], r#"data: {"symbol": "ACME"}"#);
+    assert_eq!(events[2], r#"data: {"symbol": "ACME"}"#);
+    drop(resp);
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn exec_response_uses_stdout_of_allowlisted_command_only() {
+    let yaml = r#"
+name: exec
+server:
+  base_path: /api
+  allow_exec: true
+  exec_allowlist: [cat]
+endpoints:
+  - method: POST
+    path: /echo
+    responses:
+      200:
+        content_type: application/json
+        exec: [cat]
+  - method: GET
+    path: /greeting
+    responses:
+      200:
+        content_type: text/plain
+        exec: [echo, hello]
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18122;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://127.0.0.1:{}/api/echo", port))
+        .json(&serde_json::json!({"name": "Ada"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let echoed: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(echoed["request"]["method"], "POST");
+    assert_eq!(echoed["request"]["body"]["name"], "Ada");
+
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/api/greeting", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("exec_allowlist"));
+
+    service.stop().await.unwrap();
+}
+
+#[cfg(feature = "websockets")]
+#[tokio::test]
+async fn websocket_endpoint_greets_and_replies_to_messages() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let yaml = r#"
+name: chat
+server:
+  base_path: /api
+endpoints:
+  - kind: websocket
+    method: GET
+    path: /rooms/{room}
+    stream:
+      initial:
+        - '{"type": "welcome", "room": "{{params.room}}"}'
+      on_message: '{"type": "echo", "text": "{{request.body.text}}"}'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, mut rx) = broadcast::channel(16);
+    let storage = Arc::new(NoopStorage);
+    let port = free_port();
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let url = format!("ws://127.0.0.1:{}/api/rooms/lobby", port);
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    let welcome = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+        .await
+        .expect("no welcome received")
+        .unwrap()
+        .unwrap();
+    let welcome: serde_json::Value = serde_json::from_str(welcome.to_text().unwrap()).unwrap();
+    assert_eq!(
+        welcome,
+        serde_json::json!({"type": "welcome", "room": "lobby"})
+    );
+
+    ws.send(Message::Text(r#"{"text": "hi there"}"#.into()))
+        .await
+        .unwrap();
+    let reply = tokio::time::timeout(std::time::Duration::from_secs(2), ws.next())
+        .await
+        .expect("no reply received")
+        .unwrap()
+        .unwrap();
+    let reply: serde_json::Value = serde_json::from_str(reply.to_text().unwrap()).unwrap();
+    assert_eq!(
+        reply,
+        serde_json::json!({"type": "echo", "text": "hi there"})
+    );
+    ws.close(None).await.ok();
+
+    // The message is logged before its reply is sent
+    let mut logs = Vec::new();
+    while let Ok(entry) = rx.try_recv() {
+        logs.push(entry);
+    }
+    assert!(logs
+        .iter()
+        .any(|entry| entry.status == 101 && entry.method == "GET"));
+    assert!(logs
+        .iter()
+        .any(|entry| entry.method == "WS"
+            && entry.payload.as_deref() == Some(r#"{"text": "hi there"}"#)));
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn path_prefixes_serve_one_endpoint_under_each_prefix() {
+    let yaml = r#"
+name: versioned
+server:
+  base_path: /
+  path_prefixes: ["/v1", "/v2"]
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[{"id": 1}]'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18124;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    for prefix in ["/v1", "/v2"] {
+        let url = format!("http://127.0.0.1:{}{}/users", port, prefix);
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{}", url);
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body, serde_json::json!([{"id": 1}]));
+    }
+    let url = format!("http://127.0.0.1:{}/v10/users", port);
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn path_prefixes_do_not_hide_literal_prefixed_endpoints() {
+    let yaml = r#"
+name: versioned-health
+server:
+  base_path: /
+  path_prefixes: ["/v1"]
+endpoints:
+  - method: GET
+    path: /v1/health
+    responses:
+      200:
+        content_type: application/json
+        body: '{"status": "ok"}'
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '[{"id": 1}]'
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = 18125;
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/v1/health", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body, serde_json::json!({"status": "ok"}));
+
+    for path in ["/users", "/v1/users"] {
+        let url = format!("http://127.0.0.1:{}{}", port, path);
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{}", url);
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body, serde_json::json!([{"id": 1}]));
+    }
+
+    service.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn endpoint_behavior_overrides_service_rate_limit_and_errors() {
+    let yaml = r#"
+name: layered
+server:
+  base_path: /api
+behavior:
+  error_simulation:
+    enabled: true
+    rate: 1.0
+    status_codes: [503]
+  rate_limiting:
+    enabled: true
+    requests_per_minute: 100
+endpoints:
+  - method: GET
+    path: /flaky
+    responses:
+      200:
+        content_type: text/plain
+        body: never
+  - method: GET
+    path: /stable
+    behavior:
+      error_simulation:
+        enabled: false
+        rate: 0.0
+      rate_limiting:
+        enabled: true
+        requests_per_minute: 1
+    responses:
+      200:
+        content_type: text/plain
+        body: ok
+"#;
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let (tx, _) = broadcast::channel(10);
+    let storage = Arc::new(NoopStorage);
+    let port = free_port();
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+
+    let client = Client::new();
+    let url = |path: &str| format!("http://127.0.0.1:{}/api{}", port, path);
+    let resp = client.get(url("/flaky")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let resp = client.get(url("/stable")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.text().await.unwrap(), "ok");
+    let resp = client.get(url("/stable")).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    service.stop().await.unwrap();
 }
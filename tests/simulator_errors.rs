@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use apicentric::simulator::config::{ConfigLoader, PortRange, ServiceDefinition, SimulatorConfig};
+use apicentric::simulator::service::ServiceInstance;
+use apicentric::simulator::ApiSimulatorManager;
+use apicentric::storage::sqlite::SqliteStorage;
+use apicentric::{ApicentricError, SimulatorError};
+use tempfile::TempDir;
+use tokio::sync::broadcast;
+
+#[tokio::test]
+async fn starting_on_an_occupied_port_is_port_in_use() {
+    let occupied = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+    let port = occupied.local_addr().unwrap().port();
+
+    let def: ServiceDefinition = serde_yaml::from_str(
+        r#"
+name: busy
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /ping
+    responses:
+      200:
+        content_type: application/json
+        body: '{}'
+"#,
+    )
+    .unwrap();
+    let storage = Arc::new(SqliteStorage::init_db(":memory:").unwrap());
+    let (tx, _) = broadcast::channel(10);
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+
+    let err = service.start().await.unwrap_err();
+    assert!(
+        matches!(err, ApicentricError::Simulator(SimulatorError::PortInUse(p)) if p == port),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn unknown_service_is_service_not_found() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir: temp_dir.path().join("services"),
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+
+    for err in [
+        manager.start_service("ghost").await.unwrap_err(),
+        manager.stop_service("ghost").await.unwrap_err(),
+    ] {
+        match err {
+            ApicentricError::Simulator(SimulatorError::ServiceNotFound(name)) => {
+                assert_eq!(name, "ghost")
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn unparsable_yaml_is_an_invalid_definition() {
+    let err = ConfigLoader::load_service_from_yaml("name: [", "broken.yaml").unwrap_err();
+    match err {
+        ApicentricError::Simulator(SimulatorError::InvalidDefinition { file, reason }) => {
+            assert_eq!(file, PathBuf::from("broken.yaml"));
+            assert!(reason.contains("Invalid YAML"), "{reason}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -57,7 +57,9 @@ endpoints: []
 
 #[tokio::test]
 async fn simulator_manager_initializes_successfully() {
-    let config = SimulatorConfig::default_config();
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = SimulatorConfig::default_config();
+    config.db_path = temp_dir.path().join("apicentric.db");
     let manager = ApiSimulatorManager::new(config);
 
     // Manager should be created successfully with default config
@@ -69,7 +71,8 @@ async fn simulator_can_set_db_path() {
     let temp_dir = TempDir::new().unwrap();
     let db_path = temp_dir.path().join("test.db");
 
-    let config = SimulatorConfig::default_config();
+    let mut config = SimulatorConfig::default_config();
+    config.db_path = temp_dir.path().join("apicentric.db");
     let manager = ApiSimulatorManager::new(config);
 
     let result = manager.set_db_path(db_path.to_str().unwrap()).await;
@@ -78,7 +81,9 @@ async fn simulator_can_set_db_path() {
 
 #[tokio::test]
 async fn simulator_status_reflects_state() {
-    let config = SimulatorConfig::default_config();
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = SimulatorConfig::default_config();
+    config.db_path = temp_dir.path().join("apicentric.db");
     let manager = ApiSimulatorManager::new(config);
 
     let status = manager.get_status().await;
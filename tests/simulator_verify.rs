@@ -0,0 +1,95 @@
+#![cfg(feature = "contract-testing")]
+
+use std::sync::Arc;
+
+use apicentric::adapters::HttpClientBuilder;
+use apicentric::contract::{render_differences, InstanceVerifier};
+use apicentric::simulator::config::ServiceDefinition;
+use apicentric::simulator::service::ServiceInstance;
+use apicentric::storage::sqlite::SqliteStorage;
+use apicentric::ComplianceIssueType;
+use tokio::sync::broadcast;
+
+const DEFINITION: &str = r#"
+name: users
+server:
+  base_path: /api
+endpoints:
+  - method: GET
+    path: /users/{id}
+    responses:
+      200:
+        content_type: application/json
+        body: '{"id": 1, "name": "Ada"}'
+  - method: GET
+    path: /health
+    responses:
+      200:
+        content_type: text/plain
+        body: ok
+"#;
+
+async fn start_instance(yaml: &str) -> (ServiceInstance, String) {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let def: ServiceDefinition = serde_yaml::from_str(yaml).unwrap();
+    let storage = Arc::new(SqliteStorage::init_db(":memory:").unwrap());
+    let (tx, _) = broadcast::channel(10);
+    let mut service = ServiceInstance::new(def, port, storage, tx).unwrap();
+    service.start().await.unwrap();
+    (service, format!("http://127.0.0.1:{}", port))
+}
+
+#[tokio::test]
+async fn matching_instance_passes_verification() {
+    let (mut service, url) = start_instance(DEFINITION).await;
+    let definition: ServiceDefinition = serde_yaml::from_str(DEFINITION).unwrap();
+
+    let results = InstanceVerifier::new(HttpClientBuilder::new().build())
+        .verify(&definition, &url)
+        .await
+        .unwrap();
+    service.stop().await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    for result in &results {
+        assert!(
+            result.compliance_issue.is_none(),
+            "unexpected drift: {:?}",
+            result.compliance_issue
+        );
+    }
+}
+
+#[tokio::test]
+async fn tampered_instance_reports_body_drift() {
+    let tampered = DEFINITION.replace(r#""name": "Ada""#, r#""full_name": "Ada""#);
+    let (mut service, url) = start_instance(&tampered).await;
+    let definition: ServiceDefinition = serde_yaml::from_str(DEFINITION).unwrap();
+
+    let results = InstanceVerifier::new(HttpClientBuilder::new().build())
+        .verify(&definition, &url)
+        .await
+        .unwrap();
+    service.stop().await.unwrap();
+
+    let drifted: Vec<_> = results
+        .iter()
+        .filter_map(|result| result.compliance_issue.as_ref())
+        .collect();
+    assert_eq!(drifted.len(), 1);
+    let issue = drifted[0];
+    assert_eq!(
+        issue.issue_type,
+        ComplianceIssueType::ResponseSchemaMismatch
+    );
+    assert_eq!(issue.scenario_path, "GET /users/{id}");
+    let report = render_differences(issue);
+    assert!(
+        report.iter().any(|line| line.starts_with("/name:")),
+        "{report:?}"
+    );
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "file-watch")]
+
+use std::path::Path;
+use std::time::Duration;
+
+use apicentric::simulator::config::{PortRange, SimulatorConfig};
+use apicentric::simulator::{ApiSimulatorManager, ConfigChange};
+use tempfile::TempDir;
+use tokio::sync::broadcast::Receiver;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn write_service(dir: &Path, name: &str, port: u16, body: &str) {
+    let content = format!(
+        "name: {name}\nserver:\n  port: {port}\n  base_path: /{name}\nendpoints:\n  - method: GET\n    path: /ping\n    responses:\n      200:\n        content_type: application/json\n        body: '{body}'\n"
+    );
+    std::fs::write(dir.join(format!("{name}.yaml")), content).unwrap();
+}
+
+/// Wait until `expected` arrives, skipping the extra events editors and
+/// file systems emit along the way.
+async fn expect_change(rx: &mut Receiver<ConfigChange>, expected: ConfigChange) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if rx.recv().await.unwrap() == expected {
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {:?}", expected));
+}
+
+#[tokio::test]
+async fn watcher_reports_added_modified_and_removed_services() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    write_service(&services_dir, "base", free_port(), "{}");
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir: services_dir.clone(),
+        port_range: PortRange {
+            start: 9000,
+            end: 9200,
+        },
+        db_path: temp_dir.path().join("test.db"),
+        admin_port: None,
+        global_behavior: None,
+        limits: None,
+        env: None,
+        reload_retry_after_secs: None,
+        strict_templates: false,
+        service_history: None,
+    };
+    let manager = ApiSimulatorManager::new(config);
+    let mut changes = manager.subscribe_config_changes();
+    manager.start().await.unwrap();
+
+    let port = free_port();
+    write_service(&services_dir, "extra", port, "{}");
+    expect_change(&mut changes, ConfigChange::ServiceAdded("extra".into())).await;
+
+    write_service(&services_dir, "extra", port, r#"{"v": 2}"#);
+    expect_change(&mut changes, ConfigChange::ServiceModified("extra".into())).await;
+
+    std::fs::remove_file(services_dir.join("extra.yaml")).unwrap();
+    expect_change(&mut changes, ConfigChange::ServiceRemoved("extra".into())).await;
+
+    let status = manager.get_status().await;
+    let names: Vec<_> = status
+        .active_services
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["base"]);
+
+    manager.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn failed_reloads_are_not_reported_as_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let services_dir = temp_dir.path().join("services");
+    std::fs::create_dir_all(&services_dir).unwrap();
+    write_service(&services_dir, "only", free_port(), "{}");
+
+    let config = SimulatorConfig {
+        enabled: true,
+        services_dir: services_dir.clone(),
+        db_path: temp_dir.path().join("test.db"),
+        ..SimulatorConfig::default()
+    };
+    let manager = ApiSimulatorManager::new(config);
+    let mut changes = manager.subscribe_config_changes();
+    manager.start().await.unwrap();
+
+    // Removing the last service leaves nothing to reload
+    std::fs::remove_file(services_dir.join("only.yaml")).unwrap();
+    let change = tokio::time::timeout(Duration::from_secs(2), changes.recv()).await;
+    assert!(change.is_err(), "unexpected change: {:?}", change);
+
+    manager.stop().await.unwrap();
+}
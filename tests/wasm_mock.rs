@@ -0,0 +1,30 @@
+//! Run with `wasm-pack test --node` (or `--headless --firefox`).
+#![cfg(target_arch = "wasm32")]
+
+use apicentric::wasm::handle_mock_request;
+use wasm_bindgen_test::*;
+
+const SERVICE: &str = r#"
+name: users
+server:
+  base_path: /api
+fixtures:
+  users:
+    - { id: 1, name: Ada }
+endpoints:
+  - method: GET
+    path: /users
+    responses:
+      200:
+        content_type: application/json
+        body: '{{json fixtures.users}}'
+"#;
+
+#[wasm_bindgen_test]
+fn fixture_backed_endpoint_returns_fixture_body() {
+    let resp = handle_mock_request(SERVICE, "GET", "/api/users", "{}", "");
+    let status = js_sys::Reflect::get(&resp, &"status".into()).unwrap();
+    let body = js_sys::Reflect::get(&resp, &"body".into()).unwrap();
+    assert_eq!(status.as_f64(), Some(200.0));
+    assert_eq!(body.as_string().unwrap(), r#"[{"id":1,"name":"Ada"}]"#);
+}
@@ -11,8 +11,8 @@
 use apicentric::cloud::CloudServer;
 use apicentric::simulator::{
     config::{
-        EndpointDefinition, EndpointKind, PortRange, ResponseDefinition, ServerConfig,
-        ServiceDefinition, SimulatorConfig,
+        EndpointDefinition, PortRange, ResponseDefinition, ServerConfig, ServiceDefinition,
+        SimulatorConfig,
     },
     manager::ApiSimulatorManager,
 };
@@ -29,21 +29,16 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
         server: Some(ServerConfig {
             port,
             base_path: "/".to_string(),
-            proxy_base_url: None,
-            cors: None,
-            record_unknown: false,
+            ..Default::default()
         }),
         models: None,
         fixtures: None,
+        fixture_sort: None,
         bucket: None,
+        partials: None,
         endpoints: Some(vec![EndpointDefinition {
-            kind: EndpointKind::Http,
             method: "GET".to_string(),
             path: "/test".to_string(),
-            header_match: None,
-            description: None,
-            parameters: None,
-            request_body: None,
             responses: {
                 let mut responses = HashMap::new();
                 responses.insert(
@@ -55,16 +50,25 @@ fn create_test_service_definition(name: &str, port: Option<u16>) -> ServiceDefin
                         schema: None,
                         script: None,
                         headers: None,
+                        trailers: None,
+                        examples: None,
+                        record_delay_ms: None,
+                        pretty_json: None,
+                        sparse_fields: false,
+                        serve_times: None,
+                        exec: None,
                         side_effects: None,
+                        body_base64: None,
+                        body_file: None,
                     },
                 );
                 responses
             },
-            scenarios: None,
-            stream: None,
+            ..Default::default()
         }]),
         graphql: None,
         behavior: None,
+        state_machine: None,
         #[cfg(feature = "iot")]
         twin: None,
     }
@@ -85,6 +89,7 @@ async fn test_websocket_connection_establishment() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10000,
         end: 10100,
@@ -151,6 +156,7 @@ async fn test_websocket_ping_pong() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10100,
         end: 10200,
@@ -234,6 +240,7 @@ async fn test_websocket_log_streaming() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10200,
         end: 10300,
@@ -333,6 +340,7 @@ async fn test_websocket_service_status_updates() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10300,
         end: 10400,
@@ -429,6 +437,7 @@ async fn test_websocket_multiple_clients() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10400,
         end: 10500,
@@ -536,6 +545,7 @@ async fn test_websocket_reconnection() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10500,
         end: 10600,
@@ -630,6 +640,7 @@ async fn test_websocket_connection_cleanup() {
     let mut config = SimulatorConfig::default_config();
     config.enabled = true;
     config.services_dir = services_dir.path().to_path_buf();
+    config.db_path = services_dir.path().join("apicentric.db");
     config.port_range = PortRange {
         start: 10600,
         end: 10700,